@@ -0,0 +1,155 @@
+//! Error types shared by the day crates.
+//!
+//! Every day reports failures through the same `AppError`, so conversions and
+//! messages are consistent no matter which day produced them. Errors that only
+//! one day's puzzle can produce live in that day's own `PuzzleError` and are
+//! carried by `AppError::Puzzle`.
+
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use crate::msg;
+
+/// Custom error types for the application
+#[derive(Debug)]
+#[allow(clippy::enum_variant_names)]
+pub enum AppError {
+    /// Represents I/O operation failures
+    IoError(io::Error),
+    /// Represents missing or invalid command line arguments
+    ArgError(&'static str),
+    /// Represents errors in parsing string to integers
+    ParseError(std::num::ParseIntError),
+    /// Represents failure to create an ndarray Array2 from input data
+    Array2CreationError,
+    /// Represents failure to find a starting position in the grid
+    NoStartPosition,
+    /// Represents an input line that doesn't contain exactly two numbers
+    InvalidPairCount,
+    /// Represents an input number at or above the given limit
+    ValueTooLarge(i64),
+    /// Represents an input list longer than the given limit
+    ListTooLong(usize),
+    /// Represents a part other than 1 or 2
+    InvalidPart(u32),
+    /// Represents a part whose solution isn't implemented yet
+    Unsolved(u32),
+    /// Represents no input path being given and no cached input at the given path
+    MissingInput(PathBuf),
+    /// Represents a result too large for its integer type, naming what was being computed
+    Overflow(&'static str),
+    /// Represents a solver stopped through its `CancelToken` before finishing
+    Cancelled,
+    /// Represents an encrypted input at the given path with no passphrase to decrypt it
+    MissingKey(PathBuf),
+    /// Represents an encrypted input at the given path that couldn't be decrypted
+    DecryptError(PathBuf, age::DecryptError),
+    /// Represents a failure to encrypt an input
+    EncryptError(age::EncryptError),
+    /// Represents a line (1-based) with a different number of columns than the first line
+    ColumnCount { line: usize, expected: usize, got: usize },
+    /// Represents a grid line (1-based) whose length differs from the first line's
+    RaggedInput { line: usize, expected: usize, got: usize },
+    /// Represents input that is malformed as a whole, describing what is wrong
+    InvalidInput(&'static str),
+    /// Represents an error specific to one day's puzzle, such as its `PuzzleError`
+    Puzzle(Box<dyn Error + Send + Sync>),
+}
+
+impl AppError {
+    /// Returns the day-specific error inside `AppError::Puzzle`, if it is an `E`
+    ///
+    /// # Example
+    /// `error.puzzle::<PuzzleError>()` to match on a day's own errors
+    pub fn puzzle<E: Error + 'static>(&self) -> Option<&E> {
+        match self {
+            Self::Puzzle(e) => e.downcast_ref(),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for AppError {
+    fn from(error: io::Error) -> Self {
+        Self::IoError(error)
+    }
+}
+
+impl From<std::num::ParseIntError> for AppError {
+    fn from(error: std::num::ParseIntError) -> Self {
+        Self::ParseError(error)
+    }
+}
+
+impl From<ndarray::ShapeError> for AppError {
+    fn from(_: ndarray::ShapeError) -> Self {
+        Self::Array2CreationError
+    }
+}
+
+impl Error for AppError {}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::IoError(e) => msg!("error-io", e),
+            Self::ArgError(text) => msg!("error-arg", text),
+            Self::ParseError(e) => msg!("error-parse", e),
+            Self::Array2CreationError => msg!("error-array2"),
+            Self::NoStartPosition => msg!("error-no-start"),
+            Self::InvalidPairCount => msg!("error-pair-count"),
+            Self::ValueTooLarge(max) => msg!("error-value-too-large", max),
+            Self::ListTooLong(max) => msg!("error-list-too-long", max),
+            Self::InvalidPart(part) => msg!("error-invalid-part", part),
+            Self::Unsolved(part) => msg!("error-unsolved", part),
+            Self::MissingInput(cache) => msg!("error-missing-input", cache.display()),
+            Self::Overflow(what) => msg!("error-overflow", what),
+            Self::Cancelled => msg!("error-cancelled"),
+            Self::MissingKey(path) => msg!("error-missing-key", path.display()),
+            Self::DecryptError(path, e) => msg!("error-decrypt", path.display(), e),
+            Self::EncryptError(e) => msg!("error-encrypt", e),
+            Self::ColumnCount { line, expected, got } => msg!("error-column-count", line, got, expected),
+            Self::RaggedInput { line, expected, got } => msg!("error-ragged-input", line, got, expected),
+            Self::InvalidInput(text) => msg!("error-invalid-input", text),
+            Self::Puzzle(e) => e.to_string(),
+        };
+        f.write_str(&message)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conversions() {
+        let error: AppError = "x".parse::<i32>().unwrap_err().into();
+        assert!(matches!(error, AppError::ParseError(_)));
+
+        let shape_error = ndarray::Array2::from_shape_vec((2, 2), vec![0]).unwrap_err();
+        assert!(matches!(AppError::from(shape_error), AppError::Array2CreationError));
+    }
+
+    #[test]
+    fn test_display() {
+        assert_eq!(AppError::ArgError("No input file provided").to_string(), "Argument error: No input file provided");
+        assert_eq!(AppError::NoStartPosition.to_string(), "No starting position found in grid");
+        assert_eq!(AppError::ValueTooLarge(100_000).to_string(), "Input contains numbers >= 100000");
+        assert_eq!(AppError::Overflow("total distance").to_string(), "The total distance overflowed");
+        assert_eq!(
+            AppError::RaggedInput { line: 3, expected: 10, got: 9 }.to_string(),
+            "Line 3 has 9 characters, expected 10"
+        );
+    }
+
+    #[test]
+    fn test_puzzle() {
+        let error = AppError::Puzzle(Box::new(AppError::Unsolved(2)));
+        assert_eq!(error.to_string(), "Part 2 is not solved yet");
+        assert!(matches!(error.puzzle::<AppError>(), Some(AppError::Unsolved(2))));
+        assert!(error.puzzle::<std::num::ParseIntError>().is_none());
+        assert!(AppError::NoStartPosition.puzzle::<AppError>().is_none());
+    }
+}
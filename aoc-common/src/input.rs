@@ -0,0 +1,214 @@
+//! Locating puzzle inputs.
+//!
+//! Downloaded inputs are cached under `data/{year}/{day}/input.txt` at the
+//! workspace root, so they are fetched once and found from any day's directory.
+//!
+//! Since puzzle inputs shouldn't be published, they can instead be committed
+//! encrypted with age under `inputs/{year}/{day}.age`, using a passphrase.
+//! When a day has no cached input but has an encrypted one and the passphrase
+//! is in `AOC_INPUT_KEY`, the input is decrypted into the cache on first use.
+//!
+//! Days that read their input line by line take it from an `InputSource`
+//! instead: a file when a path is given, or else standard input.
+
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use age::secrecy::SecretString;
+
+use crate::errors::AppError;
+
+/// The Advent of Code event these solutions are for
+pub const YEAR: u32 = 2024;
+
+/// Environment variable holding the passphrase of the encrypted inputs
+pub const KEY_VAR: &str = "AOC_INPUT_KEY";
+
+/// Returns the workspace root, where the input cache lives
+fn workspace_root() -> &'static Path {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    manifest_dir.parent().unwrap_or(manifest_dir)
+}
+
+/// Returns where a day's downloaded input is cached, whether or not it exists yet
+pub fn cache_path(year: u32, day: u32) -> PathBuf {
+    workspace_root()
+        .join("data")
+        .join(year.to_string())
+        .join(day.to_string())
+        .join("input.txt")
+}
+
+/// Returns where a day's downloaded puzzle page is cached, next to its input
+pub fn puzzle_cache_path(year: u32, day: u32) -> PathBuf {
+    cache_path(year, day).with_file_name("puzzle.html")
+}
+
+/// Returns where a day's encrypted input is committed, whether or not it exists
+pub fn encrypted_path(year: u32, day: u32) -> PathBuf {
+    workspace_root()
+        .join("inputs")
+        .join(year.to_string())
+        .join(format!("{}.age", day))
+}
+
+/// Encrypts an input with a passphrase, in age's binary format
+pub fn encrypt_input(plaintext: &[u8], passphrase: &str) -> Result<Vec<u8>, AppError> {
+    let recipient = age::scrypt::Recipient::new(SecretString::from(passphrase.to_string()));
+    age::encrypt(&recipient, plaintext).map_err(AppError::EncryptError)
+}
+
+/// Decrypts an input encrypted by `encrypt_input`
+///
+/// # Arguments
+/// * `ciphertext` - The encrypted input
+/// * `passphrase` - The passphrase it was encrypted with
+/// * `path` - Where the input was read from, for errors
+pub fn decrypt_input(ciphertext: &[u8], passphrase: &str, path: &Path) -> Result<Vec<u8>, AppError> {
+    let identity = age::scrypt::Identity::new(SecretString::from(passphrase.to_string()));
+    age::decrypt(&identity, ciphertext).map_err(|e| AppError::DecryptError(path.to_path_buf(), e))
+}
+
+/// Decrypts the encrypted input at `encrypted` into `cache`, unless `cache`
+/// already exists or there is no encrypted input
+///
+/// # Returns
+/// The cached input if there is one now, or `AppError::MissingKey` if only
+/// the encrypted input exists and `passphrase` is `None`
+fn decrypt_into_cache(encrypted: &Path, cache: &Path, passphrase: Option<&str>) -> Result<Option<PathBuf>, AppError> {
+    if cache.exists() {
+        return Ok(Some(cache.to_path_buf()));
+    }
+    if !encrypted.exists() {
+        return Ok(None);
+    }
+    let passphrase = passphrase.ok_or_else(|| AppError::MissingKey(encrypted.to_path_buf()))?;
+    let plaintext = decrypt_input(&std::fs::read(encrypted)?, passphrase, encrypted)?;
+    if let Some(dir) = cache.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    std::fs::write(cache, plaintext)?;
+    Ok(Some(cache.to_path_buf()))
+}
+
+/// Returns the cached input for one of this year's days, decrypting the
+/// committed encrypted input into the cache first if only that exists
+///
+/// # Returns
+/// The cached input, `None` if the day has neither a cached nor an encrypted
+/// input, or an error if the encrypted input can't be decrypted
+pub fn cached_input(day: u32) -> Result<Option<PathBuf>, AppError> {
+    let passphrase = std::env::var(KEY_VAR).ok().filter(|key| !key.is_empty());
+    decrypt_into_cache(&encrypted_path(YEAR, day), &cache_path(YEAR, day), passphrase.as_deref())
+}
+
+/// Picks the input file for a day binary: the path given on the command line,
+/// or else the cached download
+///
+/// # Arguments
+/// * `path` - The input path argument, if one was given
+/// * `day` - The day being solved
+///
+/// # Returns
+/// The path to read, or `AppError::MissingInput` if no path was given and
+/// nothing is cached
+pub fn resolve_input(path: Option<&str>, day: u32) -> Result<String, AppError> {
+    match path {
+        Some(path) => Ok(path.to_string()),
+        None => cached_input(day)?
+            .map(|path| path.to_string_lossy().into_owned())
+            .ok_or_else(|| AppError::MissingInput(cache_path(YEAR, day))),
+    }
+}
+
+/// Where a day reading line by line takes its input from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InputSource {
+    /// A file at this path
+    File(PathBuf),
+    /// Standard input
+    Stdin,
+}
+
+impl InputSource {
+    /// The file at `path` if one was given, or else standard input
+    pub fn from_arg(path: Option<&str>) -> Self {
+        match path {
+            Some(path) => Self::File(PathBuf::from(path)),
+            None => Self::Stdin,
+        }
+    }
+
+    /// Opens the source for reading
+    ///
+    /// # Returns
+    /// A buffered reader over the source, or an error if the file can't be opened
+    pub fn open(&self) -> Result<Box<dyn BufRead>, AppError> {
+        match self {
+            Self::File(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+            Self::Stdin => Ok(Box::new(io::stdin().lock())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_path() {
+        let path = cache_path(2024, 6);
+        assert!(path.ends_with("data/2024/6/input.txt"));
+        assert!(puzzle_cache_path(2024, 6).ends_with("data/2024/6/puzzle.html"));
+        assert!(encrypted_path(2024, 6).ends_with("inputs/2024/6.age"));
+        assert_eq!(path.parent().and_then(Path::parent).and_then(Path::parent).and_then(Path::parent), Some(workspace_root()));
+    }
+
+    #[test]
+    fn test_explicit_path_wins() -> Result<(), AppError> {
+        assert_eq!(resolve_input(Some("data/inputtest"), 6)?, "data/inputtest");
+        Ok(())
+    }
+
+    #[test]
+    fn test_missing_input() {
+        // Day 26 never exists, so nothing can be cached for it
+        assert!(matches!(resolve_input(None, 26), Err(AppError::MissingInput(_))));
+    }
+
+    #[test]
+    fn test_decrypt_into_cache() -> Result<(), AppError> {
+        let dir = std::env::temp_dir().join(format!("aoc_common_decrypt_{}", std::process::id()));
+        let (encrypted, cache) = (dir.join("6.age"), dir.join("cache").join("input.txt"));
+        std::fs::create_dir_all(&dir)?;
+
+        assert!(decrypt_into_cache(&encrypted, &cache, None)?.is_none());
+        std::fs::write(&encrypted, encrypt_input(b"....#\n..^..\n", "hunter2")?)?;
+        assert!(matches!(decrypt_into_cache(&encrypted, &cache, None), Err(AppError::MissingKey(_))));
+        assert!(matches!(decrypt_into_cache(&encrypted, &cache, Some("wrong")), Err(AppError::DecryptError(..))));
+        assert!(!cache.exists());
+
+        assert_eq!(decrypt_into_cache(&encrypted, &cache, Some("hunter2"))?, Some(cache.clone()));
+        assert_eq!(std::fs::read_to_string(&cache)?, "....#\n..^..\n");
+        // Once cached, the passphrase isn't needed again
+        assert_eq!(decrypt_into_cache(&encrypted, &cache, None)?, Some(cache.clone()));
+
+        std::fs::remove_dir_all(&dir)?;
+        Ok(())
+    }
+
+    #[test]
+    fn test_input_source() -> Result<(), AppError> {
+        assert_eq!(InputSource::from_arg(None), InputSource::Stdin);
+
+        let source = InputSource::from_arg(Some("Cargo.toml"));
+        assert_eq!(source, InputSource::File(PathBuf::from("Cargo.toml")));
+        let first = source.open()?.lines().next().transpose()?;
+        assert_eq!(first.as_deref(), Some("[package]"));
+
+        let missing = InputSource::from_arg(Some("no/such/file"));
+        assert!(matches!(missing.open(), Err(AppError::IoError(_))));
+        Ok(())
+    }
+}
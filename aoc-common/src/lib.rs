@@ -0,0 +1,12 @@
+//! Code shared by the day crates.
+
+pub mod cancel;
+pub mod errors;
+pub mod input;
+pub mod logging;
+pub mod messages;
+pub mod output;
+pub mod solver;
+
+pub use errors::AppError;
+pub use solver::Solver;
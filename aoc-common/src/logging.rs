@@ -0,0 +1,82 @@
+//! Diagnostic logging for the day binaries.
+//!
+//! Libraries emit `tracing` events; binaries install a subscriber with
+//! `init`, which writes them to stderr so they never mix with the answers.
+//! Only warnings are shown by default, `-v` adds debug events such as how
+//! much input was read, and `-vv` adds per-item trace events.
+
+use std::io::IsTerminal;
+
+use tracing::Level;
+
+/// Removes `-v`, `-vv` and `--verbose` from `args` and returns the verbosity
+///
+/// # Arguments
+/// * `args` - Command-line arguments, without the program name
+///
+/// # Returns
+/// The number of `v`s given, so `-v -v` counts the same as `-vv`
+pub fn take_verbosity(args: &mut Vec<String>) -> u8 {
+    let mut verbosity = 0u8;
+    args.retain(|arg| match arg.as_str() {
+        "--verbose" => {
+            verbosity = verbosity.saturating_add(1);
+            false
+        }
+        flag if flag.len() > 1 && flag.starts_with('-') && flag[1..].bytes().all(|b| b == b'v') => {
+            verbosity = verbosity.saturating_add((flag.len() - 1) as u8);
+            false
+        }
+        _ => true,
+    });
+    verbosity
+}
+
+/// Returns the most detailed level shown at a verbosity
+pub fn max_level(verbosity: u8) -> Level {
+    match verbosity {
+        0 => Level::WARN,
+        1 => Level::DEBUG,
+        _ => Level::TRACE,
+    }
+}
+
+/// Installs a subscriber that writes events up to the verbosity's level to stderr
+pub fn init(verbosity: u8) {
+    tracing_subscriber::fmt()
+        .with_max_level(max_level(verbosity))
+        .with_writer(std::io::stderr)
+        .with_ansi(std::io::stderr().is_terminal())
+        .with_target(false)
+        .without_time()
+        .init();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_take_verbosity() {
+        let mut list = args(&["-vv", "input", "--svg", "out.svg", "-v"]);
+        assert_eq!(take_verbosity(&mut list), 3);
+        assert_eq!(list, args(&["input", "--svg", "out.svg"]));
+
+        let mut list = args(&["--verbose", "-", "-x"]);
+        assert_eq!(take_verbosity(&mut list), 1);
+        assert_eq!(list, args(&["-", "-x"]));
+
+        assert_eq!(take_verbosity(&mut args(&["input"])), 0);
+    }
+
+    #[test]
+    fn test_max_level() {
+        assert_eq!(max_level(0), Level::WARN);
+        assert_eq!(max_level(1), Level::DEBUG);
+        assert_eq!(max_level(5), Level::TRACE);
+    }
+}
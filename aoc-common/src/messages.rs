@@ -0,0 +1,232 @@
+//! Message catalog for user-facing text.
+//!
+//! Banners, answer sentences, summaries and error messages are looked up by
+//! key in a catalog instead of being written inline, so they can be shown in
+//! another language. Each catalog is a TOML file in `aoc-common/locales`
+//! mapping keys to templates, where `{0}`, `{1}`, ... stand for the
+//! arguments in order. English is the default, and any message a catalog
+//! leaves out falls back to English.
+//!
+//! The language is chosen with `--lang <code>`, or else the `AOC_LANG`
+//! environment variable. Adding a language means adding its file to
+//! `locales` and a line to `CATALOGS`.
+//!
+//! Usage text, command-line argument errors, JSON output and the day-specific
+//! renderings (maps, sparklines, `--explain` lines) are not in the catalog.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+use crate::errors::AppError;
+
+/// The shipped catalogs by language code, with English first
+const CATALOGS: &[(&str, &str)] = &[("en", include_str!("../locales/en.toml")), ("es", include_str!("../locales/es.toml"))];
+
+/// Environment variable choosing the language when `--lang` isn't given
+pub const LANG_VAR: &str = "AOC_LANG";
+
+/// The messages of the chosen language, and English to fall back on
+#[derive(Debug)]
+struct Catalog {
+    messages: HashMap<String, String>,
+    english: HashMap<String, String>,
+}
+
+impl Catalog {
+    /// Loads the catalog for a language code
+    ///
+    /// # Returns
+    /// The catalog, or `None` if there is none for the code
+    fn load(lang: &str) -> Option<Self> {
+        let (_, text) = CATALOGS.iter().find(|(code, _)| *code == lang)?;
+        Some(Self { messages: parse(text), english: parse(CATALOGS[0].1) })
+    }
+
+    /// Fills in the template for `key` with `args`, falling back to English,
+    /// and then to the key itself if no catalog has it
+    fn render(&self, key: &str, args: &[&dyn Display]) -> String {
+        let template = self.messages.get(key).or_else(|| self.english.get(key)).map_or(key, String::as_str);
+        fill(template, args)
+    }
+}
+
+/// Parses a catalog file. The shipped catalogs are checked by the tests, so
+/// a malformed one only loses its messages rather than stopping the program.
+fn parse(text: &str) -> HashMap<String, String> {
+    toml::from_str(text).unwrap_or_default()
+}
+
+/// Replaces each `{n}` in `template` with the nth argument
+fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut text = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        text.push_str(&rest[..start]);
+        let placeholder = rest[start + 1..]
+            .split_once('}')
+            .and_then(|(index, after)| Some((args.get(index.parse::<usize>().ok()?)?, after)));
+        match placeholder {
+            Some((arg, after)) => {
+                text.push_str(&arg.to_string());
+                rest = after;
+            }
+            None => {
+                text.push('{');
+                rest = &rest[start + 1..];
+            }
+        }
+    }
+    text.push_str(rest);
+    text
+}
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+/// Returns the chosen catalog, choosing it from `AOC_LANG` on first use if
+/// `init` wasn't called
+fn catalog() -> &'static Catalog {
+    CATALOG.get_or_init(|| {
+        let lang = std::env::var(LANG_VAR).unwrap_or_default();
+        Catalog::load(&lang).or_else(|| Catalog::load("en")).unwrap_or_else(|| Catalog {
+            messages: HashMap::new(),
+            english: HashMap::new(),
+        })
+    })
+}
+
+/// Removes `--lang <code>` from `args` and returns the code
+///
+/// # Arguments
+/// * `args` - Command-line arguments, without the program name
+///
+/// # Returns
+/// The language code, or `None` if `--lang` isn't given
+pub fn take_lang(args: &mut Vec<String>) -> Result<Option<String>, AppError> {
+    let Some(index) = args.iter().position(|arg| arg == "--lang") else {
+        return Ok(None);
+    };
+    if index + 1 == args.len() {
+        return Err(AppError::ArgError("--lang requires a language code such as en or es"));
+    }
+    let lang = args.remove(index + 1);
+    args.remove(index);
+    Ok(Some(lang))
+}
+
+/// Chooses the language for the rest of the program: `lang` if given, or
+/// else `AOC_LANG`, or else English. Only the first call has any effect.
+///
+/// # Returns
+/// An error if `lang` names a language without a catalog
+pub fn init(lang: Option<&str>) -> Result<(), AppError> {
+    let Some(lang) = lang else {
+        catalog();
+        return Ok(());
+    };
+    let catalog = Catalog::load(lang).ok_or(AppError::ArgError("--lang must be one of the languages in aoc-common/locales"))?;
+    let _ = CATALOG.set(catalog);
+    Ok(())
+}
+
+/// Returns the message for `key` in the chosen language, with `{0}`, `{1}`,
+/// ... replaced by `args`. Use the `msg!` macro rather than calling this.
+pub fn message(key: &str, args: &[&dyn Display]) -> String {
+    catalog().render(key, args)
+}
+
+/// Looks up a message in the catalog and fills in its arguments:
+/// `msg!("welcome", 6)`
+#[macro_export]
+macro_rules! msg {
+    ($key:literal $(, $arg:expr)* $(,)?) => {
+        $crate::messages::message($key, &[$(&$arg as &dyn ::std::fmt::Display),*])
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::path::Path;
+
+    use super::*;
+
+    /// Returns the placeholders a template uses
+    fn placeholders(template: &str) -> HashSet<&str> {
+        template.split('{').skip(1).filter_map(|part| part.split_once('}').map(|(index, _)| index)).collect()
+    }
+
+    /// Collects the keys of every `msg!` call in the Rust files under `dir`
+    fn used_keys(dir: &Path, keys: &mut HashSet<String>) -> std::io::Result<()> {
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.is_dir() {
+                used_keys(&path, keys)?;
+            } else if path.extension().is_some_and(|ext| ext == "rs") {
+                let source = std::fs::read_to_string(&path)?;
+                for call in source.split("msg!(\"").skip(1) {
+                    if let Some((key, _)) = call.split_once('"') {
+                        keys.insert(key.to_string());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_fill() {
+        assert_eq!(fill("Day {0} part {1}: {2}", &[&5, &2, &"123"]), "Day 5 part 2: 123");
+        assert_eq!(fill("{1} before {0}", &[&"a", &"b"]), "b before a");
+        assert_eq!(fill("Missing {3} and {x} stay", &[&1]), "Missing {3} and {x} stay");
+    }
+
+    #[test]
+    fn test_take_lang() -> Result<(), AppError> {
+        let mut args = vec!["input".to_string(), "--lang".to_string(), "es".to_string()];
+        assert_eq!(take_lang(&mut args)?.as_deref(), Some("es"));
+        assert_eq!(args, ["input"]);
+        assert_eq!(take_lang(&mut args)?, None);
+        assert!(take_lang(&mut vec!["--lang".to_string()]).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_falls_back_to_english() -> Result<(), AppError> {
+        let mut catalog = Catalog::load("es").ok_or(AppError::ArgError("no es catalog"))?;
+        assert_eq!(catalog.render("welcome", &[&6]), "¡Bienvenido al día 6!");
+        catalog.messages.remove("welcome");
+        assert_eq!(catalog.render("welcome", &[&6]), "Welcome to Day 6!");
+        assert_eq!(catalog.render("no-such-key", &[]), "no-such-key");
+        assert!(Catalog::load("xx").is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_catalogs_match_english() {
+        let english: HashMap<String, String> = toml::from_str(CATALOGS[0].1).expect("en.toml is valid TOML");
+        for (code, text) in CATALOGS {
+            let messages: HashMap<String, String> = toml::from_str(text).expect("catalog is valid TOML");
+            for (key, template) in &messages {
+                let original = english.get(key).unwrap_or_else(|| panic!("{}: {} is not in en.toml", code, key));
+                assert_eq!(placeholders(template), placeholders(original), "{}: {}", code, key);
+            }
+        }
+    }
+
+    #[test]
+    fn test_used_keys_are_in_english() -> std::io::Result<()> {
+        let english = parse(CATALOGS[0].1);
+        let root = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap_or(Path::new("."));
+        let mut keys = HashSet::new();
+        for entry in std::fs::read_dir(root)? {
+            let src = entry?.path().join("src");
+            if src.is_dir() {
+                used_keys(&src, &mut keys)?;
+            }
+        }
+        let missing: Vec<&String> = keys.iter().filter(|key| !english.contains_key(*key)).collect();
+        assert!(missing.is_empty(), "keys missing from en.toml: {:?}", missing);
+        Ok(())
+    }
+}
@@ -0,0 +1,126 @@
+//! Machine-readable results for `--format json`.
+//!
+//! Every day binary and the `aoc` runner accept `--format <text|json>`. In
+//! JSON mode a day's answers are printed as a single line such as
+//! `{"day":4,"part1":18,"part2":9,"elapsed_ms":1.2}` instead of the usual
+//! sentences, so results can be piped into other tools.
+
+use std::str::FromStr;
+use std::time::Duration;
+
+use serde_json::{json, Value};
+
+use crate::errors::AppError;
+
+/// How a binary prints its results
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// Human-readable sentences
+    #[default]
+    Text,
+    /// One JSON object per day
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(AppError::ArgError("--format must be text or json")),
+        }
+    }
+}
+
+/// Removes `--format <text|json>` from `args` and returns the chosen format
+///
+/// # Arguments
+/// * `args` - Command-line arguments, without the program name
+///
+/// # Returns
+/// The requested format, or `Format::Text` if `--format` isn't given
+pub fn take_format(args: &mut Vec<String>) -> Result<Format, AppError> {
+    let Some(index) = args.iter().position(|arg| arg == "--format") else {
+        return Ok(Format::Text);
+    };
+    if index + 1 == args.len() {
+        return Err(AppError::ArgError("--format requires text or json"));
+    }
+    let format = args[index + 1].parse()?;
+    args.drain(index..=index + 1);
+    Ok(format)
+}
+
+/// One day's answers and how long they took to compute
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    pub day: u32,
+    /// `None` if the part wasn't run or has no answer
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+    pub elapsed: Duration,
+}
+
+impl Report {
+    /// Formats the report as a single-line JSON object
+    ///
+    /// Answers that are integers are written as JSON numbers, anything else
+    /// (such as `4,6,3,5`) as a string, and missing answers as `null`.
+    pub fn to_json(&self) -> String {
+        json!({
+            "day": self.day,
+            "part1": answer_value(self.part1.as_deref()),
+            "part2": answer_value(self.part2.as_deref()),
+            "elapsed_ms": self.elapsed.as_secs_f64() * 1000.0,
+        })
+        .to_string()
+    }
+}
+
+/// Converts an answer to a JSON number if it is an integer
+pub fn answer_value(answer: Option<&str>) -> Value {
+    match answer {
+        None => Value::Null,
+        Some(answer) => answer.parse::<i64>().map_or_else(|_| Value::from(answer), Value::from),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_take_format() -> Result<(), AppError> {
+        let mut list = args(&["input", "--format", "json", "--svg", "out.svg"]);
+        assert_eq!(take_format(&mut list)?, Format::Json);
+        assert_eq!(list, args(&["input", "--svg", "out.svg"]));
+
+        let mut list = args(&["input"]);
+        assert_eq!(take_format(&mut list)?, Format::Text);
+        assert_eq!(list, args(&["input"]));
+
+        assert!(take_format(&mut args(&["--format"])).is_err());
+        assert!(take_format(&mut args(&["--format", "yaml"])).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_report_to_json() {
+        let report = Report {
+            day: 17,
+            part1: Some("4,6,3,5".to_string()),
+            part2: Some("117440".to_string()),
+            elapsed: Duration::from_micros(1500),
+        };
+        assert_eq!(report.to_json(), r#"{"day":17,"part1":"4,6,3,5","part2":117440,"elapsed_ms":1.5}"#);
+
+        let report = Report { day: 6, part1: Some("41".to_string()), part2: None, elapsed: Duration::ZERO };
+        assert_eq!(report.to_json(), r#"{"day":6,"part1":41,"part2":null,"elapsed_ms":0.0}"#);
+    }
+}
@@ -0,0 +1,162 @@
+//! The interface every day implements, so runners, benchmarks and tests can
+//! treat all days the same way.
+
+use std::time::{Duration, Instant};
+
+use crate::cancel::CancelToken;
+use crate::errors::AppError;
+
+/// A day's puzzle solution, split into parsing and the two parts.
+///
+/// The input is parsed once and the result is shared by both parts.
+pub trait Solver {
+    /// The puzzle input after parsing
+    type Parsed;
+
+    /// Parses the puzzle input text
+    fn parse(input: &str) -> Result<Self::Parsed, AppError>;
+
+    /// Computes the part 1 answer
+    fn part1(parsed: &Self::Parsed) -> Result<String, AppError>;
+
+    /// Computes the part 2 answer
+    fn part2(parsed: &Self::Parsed) -> Result<String, AppError>;
+
+    /// Describes how a part reaches its answer, one human-readable line per
+    /// step worth mentioning, for `--explain`. Days without anything to say
+    /// keep the default, which explains nothing.
+    fn explain(_parsed: &Self::Parsed, _part: u32) -> Result<Vec<String>, AppError> {
+        Ok(Vec::new())
+    }
+}
+
+/// How long each stage of a `solve_timed` call took
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timings {
+    /// Time spent in `Solver::parse`
+    pub parse: Duration,
+    /// Time spent on each requested part, in the order requested
+    pub parts: Vec<Duration>,
+}
+
+/// Parses `input` once and runs each of `parts` (1 or 2) on it
+///
+/// # Returns
+/// One answer per requested part, or an error if the input can't be parsed
+pub fn solve<S: Solver>(input: &str, parts: &[u32]) -> Result<Vec<Result<String, AppError>>, AppError> {
+    solve_timed::<S>(input, parts).map(|(answers, _)| answers)
+}
+
+/// Like `solve`, but also measures how long parsing and each part took
+///
+/// # Returns
+/// One answer per requested part along with the timings, or an error if the
+/// input can't be parsed
+pub fn solve_timed<S: Solver>(
+    input: &str,
+    parts: &[u32],
+) -> Result<(Vec<Result<String, AppError>>, Timings), AppError> {
+    solve_timed_with::<S>(input, parts, &CancelToken::new())
+}
+
+/// Like `solve_timed`, but stops early once `cancel` is cancelled. The token
+/// is checked before parsing and before each part, so a stage that has
+/// already started runs to completion.
+///
+/// # Returns
+/// One answer per requested part along with the timings, with
+/// `AppError::Cancelled` for the parts that were skipped, or an error if the
+/// input can't be parsed or cancellation came before parsing
+pub fn solve_timed_with<S: Solver>(
+    input: &str,
+    parts: &[u32],
+    cancel: &CancelToken,
+) -> Result<(Vec<Result<String, AppError>>, Timings), AppError> {
+    cancel.check()?;
+    let start = Instant::now();
+    let parsed = S::parse(input)?;
+    let parse = start.elapsed();
+
+    let mut answers = Vec::with_capacity(parts.len());
+    let mut durations = Vec::with_capacity(parts.len());
+    for &part in parts {
+        let start = Instant::now();
+        answers.push(match part {
+            _ if cancel.is_cancelled() => Err(AppError::Cancelled),
+            1 => S::part1(&parsed),
+            2 => S::part2(&parsed),
+            _ => Err(AppError::InvalidPart(part)),
+        });
+        durations.push(start.elapsed());
+    }
+
+    Ok((answers, Timings { parse, parts: durations }))
+}
+
+/// Parses `input` and explains how `part` (1 or 2) reaches its answer
+///
+/// # Returns
+/// The explanation lines, or an error if the input can't be parsed or the
+/// part doesn't exist
+pub fn explain<S: Solver>(input: &str, part: u32) -> Result<Vec<String>, AppError> {
+    if !matches!(part, 1 | 2) {
+        return Err(AppError::InvalidPart(part));
+    }
+    S::explain(&S::parse(input)?, part)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sums and multiplies a comma-separated list of numbers
+    struct Numbers;
+
+    impl Solver for Numbers {
+        type Parsed = Vec<i64>;
+
+        fn parse(input: &str) -> Result<Self::Parsed, AppError> {
+            Ok(input.trim().split(',').map(str::parse).collect::<Result<_, _>>()?)
+        }
+
+        fn part1(parsed: &Self::Parsed) -> Result<String, AppError> {
+            Ok(parsed.iter().sum::<i64>().to_string())
+        }
+
+        fn part2(parsed: &Self::Parsed) -> Result<String, AppError> {
+            Ok(parsed.iter().product::<i64>().to_string())
+        }
+    }
+
+    #[test]
+    fn test_solve() -> Result<(), AppError> {
+        let answers = solve::<Numbers>("2,3,4\n", &[2, 1, 3])?;
+        assert_eq!(answers[0].as_deref().ok(), Some("24"));
+        assert_eq!(answers[1].as_deref().ok(), Some("9"));
+        assert!(matches!(answers[2], Err(AppError::InvalidPart(3))));
+        assert!(matches!(solve::<Numbers>("2,x", &[1]), Err(AppError::ParseError(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain_defaults_to_nothing() -> Result<(), AppError> {
+        assert!(explain::<Numbers>("2,3", 1)?.is_empty());
+        assert!(matches!(explain::<Numbers>("2,3", 3), Err(AppError::InvalidPart(3))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_timed() -> Result<(), AppError> {
+        let (answers, timings) = solve_timed::<Numbers>("2,3,4\n", &[1, 2])?;
+        assert_eq!(answers.len(), 2);
+        assert_eq!(timings.parts.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_cancelled() {
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        assert!(matches!(solve_timed_with::<Numbers>("2,3", &[1], &cancel), Err(AppError::Cancelled)));
+    }
+}
@@ -0,0 +1,16 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use day_01::{parse_pairs, similarity_score, total_distance};
+
+const INPUT: &str = include_str!("../data/input.txt");
+
+fn bench_lists(c: &mut Criterion) {
+    let (list1, list2) = parse_pairs(INPUT).unwrap();
+
+    c.bench_function("parse", |b| b.iter(|| parse_pairs(black_box(INPUT))));
+    c.bench_function("total_distance", |b| b.iter(|| total_distance(black_box(&list1), black_box(&list2))));
+    c.bench_function("similarity_score", |b| b.iter(|| similarity_score(black_box(&list1), black_box(&list2))));
+}
+
+criterion_group!(benches, bench_lists);
+criterion_main!(benches);
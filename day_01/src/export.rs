@@ -0,0 +1,54 @@
+//! Parquet export of the per-pair intermediates (requires the `parquet` feature).
+
+use std::collections::HashMap;
+use std::error::Error;
+use std::fs::File;
+use std::sync::Arc;
+
+use arrow_array::{Int64Array, RecordBatch};
+use arrow_schema::{DataType, Field, Schema};
+use parquet::arrow::ArrowWriter;
+
+/// Writes one row per sorted pair to a Parquet file
+///
+/// Call this only once `total_distance` and `similarity_score` have
+/// succeeded, which guarantees that no column overflows.
+///
+/// # Columns
+/// * `left`, `right` - The pair after both lists have been sorted
+/// * `distance` - The absolute difference between `left` and `right`
+/// * `similarity` - `left` multiplied by its number of occurrences in the right list
+pub fn write_pairs_parquet(
+    path: &str,
+    list1: &[i64],
+    list2: &[i64],
+    frequency_map: &HashMap<i64, i64>,
+) -> Result<(), Box<dyn Error>> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("left", DataType::Int64, false),
+        Field::new("right", DataType::Int64, false),
+        Field::new("distance", DataType::Int64, false),
+        Field::new("similarity", DataType::Int64, false),
+    ]));
+
+    let distances: Vec<i64> = list1.iter().zip(list2).map(|(a, b)| (a - b).abs()).collect();
+    let similarities: Vec<i64> = list1
+        .iter()
+        .map(|num| num * frequency_map.get(num).copied().unwrap_or(0))
+        .collect();
+
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(Int64Array::from(list1.to_vec())),
+            Arc::new(Int64Array::from(list2.to_vec())),
+            Arc::new(Int64Array::from(distances)),
+            Arc::new(Int64Array::from(similarities)),
+        ],
+    )?;
+
+    let mut writer = ArrowWriter::try_new(File::create(path)?, schema, None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
@@ -0,0 +1,362 @@
+//! Solving inputs too large to hold in memory.
+//!
+//! The pairs are read a chunk at a time. Each chunk's two lists are sorted
+//! and spilled to their own run files in a temporary directory, so no more
+//! than `chunk_pairs` pairs are ever in memory. The runs of each list are
+//! then merged back into one sorted stream: one merge zips the two lists for
+//! the distance, and a second walks them side by side, matching equal values
+//! for the similarity score. A merge never reads more than `MAX_FAN_IN` runs at
+//! once, so if there are more, groups of them are first merged into longer
+//! intermediate runs. The temporary directory is removed afterwards.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::iter::Peekable;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use aoc_common::AppError;
+use tempfile::TempDir;
+use tracing::debug;
+
+use crate::tabular::Table;
+use crate::Limits;
+
+/// The most runs merged at once, which bounds the open files and read buffers
+const MAX_FAN_IN: usize = 64;
+
+/// Where and in how large chunks to sort
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExternalSort {
+    /// The most pairs held in memory at once
+    pub chunk_pairs: usize,
+    /// The directory the temporary run files are created in
+    pub temp_dir: PathBuf,
+}
+
+impl Default for ExternalSort {
+    /// A million pairs at a time, in the system's temporary directory
+    fn default() -> Self {
+        Self { chunk_pairs: 1 << 20, temp_dir: std::env::temp_dir() }
+    }
+}
+
+/// The sorted chunks of both lists spilled to disk
+struct Runs {
+    /// Keeps the run files until the runs are dropped
+    dir: TempDir,
+    /// How many times the runs have been merged into longer ones
+    generation: usize,
+    /// The number of values in each run, the same for both lists
+    lengths: Vec<usize>,
+}
+
+impl Runs {
+    fn path(&self, list: usize, run: usize) -> PathBuf {
+        self.run_path(self.generation, list, run)
+    }
+
+    fn run_path(&self, generation: usize, list: usize, run: usize) -> PathBuf {
+        self.dir.path().join(format!("gen{}-list{}-run{}", generation, list, run))
+    }
+
+    /// Merges the runs of one list back into a single ascending stream
+    fn merge(&self, list: usize) -> Result<MergedRuns, AppError> {
+        self.merge_range(list, 0..self.lengths.len())
+    }
+
+    /// Merges a range of the runs of one list into a single ascending stream
+    fn merge_range(&self, list: usize, runs: Range<usize>) -> Result<MergedRuns, AppError> {
+        let readers = runs
+            .map(|run| {
+                let reader = BufReader::new(File::open(self.path(list, run))?);
+                Ok(RunReader { reader, remaining: self.lengths[run] })
+            })
+            .collect::<Result<Vec<_>, AppError>>()?;
+        MergedRuns::new(readers)
+    }
+
+    /// Merges groups of `fan_in` runs into longer runs until no more than
+    /// `fan_in` remain, deleting each generation's files once it is merged
+    fn reduce(&mut self, fan_in: usize) -> Result<(), AppError> {
+        while self.lengths.len() > fan_in {
+            let run_count = self.lengths.len();
+            let mut lengths = Vec::with_capacity(run_count.div_ceil(fan_in));
+            for start in (0..run_count).step_by(fan_in) {
+                let group = start..run_count.min(start + fan_in);
+                for list in 0..2 {
+                    let path = self.run_path(self.generation + 1, list, lengths.len());
+                    write_run(&path, self.merge_range(list, group.clone())?)?;
+                }
+                lengths.push(self.lengths[group].iter().sum());
+            }
+            for run in 0..run_count {
+                for list in 0..2 {
+                    std::fs::remove_file(self.path(list, run))?;
+                }
+            }
+            debug!("Merged {} runs into {}", run_count, lengths.len());
+            self.generation += 1;
+            self.lengths = lengths;
+        }
+        Ok(())
+    }
+}
+
+/// Writes the sorted values of one run as little-endian `i64`s
+fn write_run(path: &Path, values: impl IntoIterator<Item = Result<i64, AppError>>) -> Result<(), AppError> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    for value in values {
+        writer.write_all(&value?.to_le_bytes())?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// Reads back the values of one run
+struct RunReader {
+    reader: BufReader<File>,
+    remaining: usize,
+}
+
+impl RunReader {
+    fn next_value(&mut self) -> Result<Option<i64>, AppError> {
+        if self.remaining == 0 {
+            return Ok(None);
+        }
+        let mut bytes = [0; 8];
+        self.reader.read_exact(&mut bytes)?;
+        self.remaining -= 1;
+        Ok(Some(i64::from_le_bytes(bytes)))
+    }
+}
+
+/// A k-way merge of sorted runs, holding each run's next value in a heap
+struct MergedRuns {
+    readers: Vec<RunReader>,
+    heap: BinaryHeap<Reverse<(i64, usize)>>,
+}
+
+impl MergedRuns {
+    fn new(mut readers: Vec<RunReader>) -> Result<Self, AppError> {
+        let mut heap = BinaryHeap::with_capacity(readers.len());
+        for (run, reader) in readers.iter_mut().enumerate() {
+            if let Some(value) = reader.next_value()? {
+                heap.push(Reverse((value, run)));
+            }
+        }
+        Ok(Self { readers, heap })
+    }
+}
+
+impl Iterator for MergedRuns {
+    type Item = Result<i64, AppError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((value, run)) = self.heap.pop()?;
+        match self.readers[run].next_value() {
+            Ok(Some(next)) => self.heap.push(Reverse((next, run))),
+            Ok(None) => {}
+            Err(error) => return Some(Err(error)),
+        }
+        Some(Ok(value))
+    }
+}
+
+/// Reads the pairs a chunk at a time and spills each chunk's sorted lists
+fn spill_runs(
+    input: &mut impl BufRead,
+    table: &Table,
+    limits: &Limits,
+    config: &ExternalSort,
+) -> Result<Runs, AppError> {
+    if config.chunk_pairs == 0 {
+        return Err(AppError::ArgError("--chunk-pairs must be at least 1"));
+    }
+    let mut runs = Runs { dir: TempDir::new_in(&config.temp_dir)?, generation: 0, lengths: Vec::new() };
+    let mut lists = (Vec::with_capacity(config.chunk_pairs), Vec::with_capacity(config.chunk_pairs));
+    let mut pairs = 0;
+    let mut line = String::new();
+    if table.header {
+        input.read_line(&mut line)?;
+    }
+
+    loop {
+        line.clear();
+        let done = input.read_line(&mut line)? == 0;
+        if !done {
+            let numbers = table.numbers(line.trim_end_matches(['\n', '\r']))?;
+            let &[left, right] = numbers.as_slice() else {
+                return Err(AppError::InvalidPairCount);
+            };
+            if let Some(max) = limits.max_value {
+                if left >= max || right >= max {
+                    return Err(AppError::ValueTooLarge(max));
+                }
+            }
+            if limits.max_pairs == Some(pairs) {
+                return Err(AppError::ListTooLong(pairs));
+            }
+            lists.0.push(left);
+            lists.1.push(right);
+            pairs += 1;
+        }
+
+        // Spill a full chunk, or whatever is left at the end
+        let length = lists.0.len();
+        if length == config.chunk_pairs || (done && length > 0) {
+            let run = runs.lengths.len();
+            for (list, values) in [&mut lists.0, &mut lists.1].into_iter().enumerate() {
+                values.sort_unstable();
+                write_run(&runs.path(list, run), values.iter().map(|&value| Ok(value)))?;
+                values.clear();
+            }
+            runs.lengths.push(length);
+        }
+        if done {
+            debug!("Spilled {} pairs in {} runs", pairs, runs.lengths.len());
+            return Ok(runs);
+        }
+    }
+}
+
+/// Sums the absolute differences between the merged lists
+fn merged_distance(runs: &Runs) -> Result<i64, AppError> {
+    let mut total: i64 = 0;
+    for (a, b) in runs.merge(0)?.zip(runs.merge(1)?) {
+        let (a, b) = (a?, b?);
+        total = i64::try_from(a.abs_diff(b))
+            .ok()
+            .and_then(|distance| total.checked_add(distance))
+            .ok_or(AppError::Overflow("total distance"))?;
+    }
+    Ok(total)
+}
+
+/// Skips the values of a sorted stream below `value` and counts those equal to it
+fn count_equal(stream: &mut Peekable<MergedRuns>, value: i64) -> Result<i64, AppError> {
+    let mut count = 0;
+    // Errors are taken too, so that they are returned
+    while let Some(next) = stream.next_if(|next| !matches!(next, Ok(next) if *next > value)) {
+        if next? == value {
+            count += 1;
+        }
+    }
+    Ok(count)
+}
+
+/// Sums each value of the first list times the number of times it appears
+/// in the second, walking both merged lists in step
+fn merged_similarity(runs: &Runs) -> Result<i64, AppError> {
+    let mut left = runs.merge(0)?.peekable();
+    let mut right = runs.merge(1)?.peekable();
+    let mut sum_of_products: i64 = 0;
+
+    while let Some(value) = left.next() {
+        let value = value?;
+        let left_count = 1 + count_equal(&mut left, value)?;
+        let right_count = count_equal(&mut right, value)?;
+        sum_of_products = value
+            .checked_mul(left_count)
+            .and_then(|product| product.checked_mul(right_count))
+            .and_then(|product| sum_of_products.checked_add(product))
+            .ok_or(AppError::Overflow("similarity score"))?;
+    }
+    Ok(sum_of_products)
+}
+
+/// Solves both parts without holding the lists in memory
+///
+/// # Arguments
+/// * `input` - The puzzle input, two numbers per row, read once from the start
+/// * `table` - How the rows are laid out
+/// * `limits` - The bounds the input must stay within
+/// * `config` - The chunk size and where to spill the chunks
+///
+/// # Returns
+/// * The total distance and the similarity score, or an error if:
+///   - The input or the temporary files can't be read or written
+///   - A row doesn't contain exactly 2 numbers, or breaks `limits`
+///   - Either answer overflows an `i64`
+pub fn solve_external(
+    input: &mut impl BufRead,
+    table: &Table,
+    limits: &Limits,
+    config: &ExternalSort,
+) -> Result<(i64, i64), AppError> {
+    let mut runs = spill_runs(input, table, limits, config)?;
+    runs.reduce(MAX_FAN_IN)?;
+    Ok((merged_distance(&runs)?, merged_similarity(&runs)?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solve;
+    use crate::tabular::Delimiter;
+
+    fn solve_in_chunks(input: &str, chunk_pairs: usize) -> Result<(i64, i64), AppError> {
+        let config = ExternalSort { chunk_pairs, ..ExternalSort::default() };
+        solve_external(&mut input.as_bytes(), &Table::default(), &Limits::default(), &config)
+    }
+
+    #[test]
+    fn test_matches_in_memory() -> Result<(), AppError> {
+        for path in ["data/inputtest", "data/input.txt"] {
+            let input = std::fs::read_to_string(path)?;
+            let expected = solve(&input)?;
+            for chunk_pairs in [1, 2, 37, 1000, 1 << 20] {
+                assert_eq!(solve_in_chunks(&input, chunk_pairs)?, expected, "{path} in chunks of {chunk_pairs}");
+            }
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_edge_cases() -> Result<(), AppError> {
+        assert_eq!(solve_in_chunks("", 4)?, (0, 0));
+        assert!(matches!(solve_in_chunks("1 2\n3", 4), Err(AppError::InvalidPairCount)));
+        assert!(matches!(solve_in_chunks("1 2", 0), Err(AppError::ArgError(_))));
+
+        let max = i64::MAX;
+        assert!(matches!(solve_in_chunks(&format!("{max} 0\n{max} 0\n"), 1), Err(AppError::Overflow(_))));
+        assert!(matches!(solve_in_chunks(&format!("{max} {max}\n1 {max}\n"), 1), Err(AppError::Overflow(_))));
+
+        let table = Table { delimiter: Delimiter::Char(','), header: true };
+        let limits = Limits { max_value: None, max_pairs: Some(6) };
+        let config = ExternalSort { chunk_pairs: 4, ..ExternalSort::default() };
+        let csv = "left,right\n3,4\n4,3\n2,5\n1,3\n3,9\n3,3\n";
+        assert_eq!(solve_external(&mut csv.as_bytes(), &table, &limits, &config)?, (11, 31));
+        Ok(())
+    }
+
+    #[test]
+    fn test_limited_fan_in() -> Result<(), AppError> {
+        let input = std::fs::read_to_string("data/input.txt")?;
+        let expected = solve(&input)?;
+        let config = ExternalSort { chunk_pairs: 7, ..ExternalSort::default() };
+        for fan_in in [2, 3, 64] {
+            let mut runs = spill_runs(&mut input.as_bytes(), &Table::default(), &Limits::default(), &config)?;
+            let pairs: usize = runs.lengths.iter().sum();
+            runs.reduce(fan_in)?;
+            assert!(runs.lengths.len() <= fan_in);
+            assert_eq!(runs.lengths.iter().sum::<usize>(), pairs);
+            // Only the last generation's files are left
+            assert_eq!(std::fs::read_dir(runs.dir.path())?.count(), 2 * runs.lengths.len());
+            assert_eq!((merged_distance(&runs)?, merged_similarity(&runs)?), expected, "fan-in {fan_in}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_removes_runs() -> Result<(), AppError> {
+        let temp_dir = TempDir::new()?;
+        let config = ExternalSort { chunk_pairs: 2, temp_dir: temp_dir.path().to_path_buf() };
+        let input = std::fs::read_to_string("data/inputtest")?;
+        solve_external(&mut input.as_bytes(), &Table::default(), &Limits::default(), &config)?;
+        assert_eq!(std::fs::read_dir(temp_dir.path())?.count(), 0);
+        Ok(())
+    }
+}
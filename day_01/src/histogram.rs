@@ -0,0 +1,127 @@
+//! Terminal histograms of the two lists and their pairwise differences.
+
+use std::collections::HashSet;
+
+/// Number of buckets each histogram is split into
+const BUCKETS: usize = 10;
+/// Width in characters of the longest histogram bar
+const BAR_WIDTH: usize = 40;
+
+/// Splits the value range into equal-width buckets and counts the values in each
+///
+/// # Returns
+/// * `(low, high, count)` per bucket, where `low..=high` is the bucket's value range.
+///   Empty when `values` is empty.
+fn bucket_counts(values: &[i64], buckets: usize) -> Vec<(i64, i64, usize)> {
+    let (Some(&min), Some(&max)) = (values.iter().min(), values.iter().max()) else {
+        return Vec::new();
+    };
+    // Widened so that the span of any two i64 values fits
+    let (min, max) = (i128::from(min), i128::from(max));
+    let width = ((max - min) / buckets as i128 + 1).max(1);
+    let used = ((max - min) / width + 1) as usize;
+
+    let mut counts = vec![0; used];
+    for &value in values {
+        counts[((i128::from(value) - min) / width) as usize] += 1;
+    }
+
+    counts
+        .into_iter()
+        .enumerate()
+        .map(|(i, count)| {
+            let low = min + i as i128 * width;
+            // Both bounds lie between min and max, so they fit back in an i64
+            (low as i64, (low + width - 1).min(max) as i64, count)
+        })
+        .collect()
+}
+
+/// Renders a titled histogram with one `#` bar per bucket
+///
+/// # Arguments
+/// * `title` - Heading printed above the bars
+/// * `values` - The values to bucket
+///
+/// # Returns
+/// * The histogram, one line per bucket
+pub fn render_histogram(title: &str, values: &[i64]) -> String {
+    let buckets = bucket_counts(values, BUCKETS);
+    let largest = buckets.iter().map(|&(_, _, count)| count).max().unwrap_or(0).max(1);
+    let label_width = buckets
+        .iter()
+        .map(|&(low, high, _)| format!("{low}..={high}").len())
+        .max()
+        .unwrap_or(0);
+
+    let mut output = format!("{title} ({} values)\n", values.len());
+    for (low, high, count) in buckets {
+        let label = format!("{low}..={high}");
+        let bar = "#".repeat((count * BAR_WIDTH).div_ceil(largest));
+        output.push_str(&format!("  {label:>label_width$} | {bar} {count}\n"));
+    }
+    output
+}
+
+/// Summarises how much the two lists share
+///
+/// # Returns
+/// * A short report of distinct values per list, distinct values in both lists,
+///   and how many entries of the left list appear anywhere in the right list
+pub fn overlap_statistics(list1: &[i64], list2: &[i64]) -> String {
+    let left: HashSet<i64> = list1.iter().copied().collect();
+    let right: HashSet<i64> = list2.iter().copied().collect();
+    let shared = left.intersection(&right).count();
+    let matched = list1.iter().filter(|n| right.contains(n)).count();
+    let percent = if list1.is_empty() {
+        0.0
+    } else {
+        matched as f64 * 100.0 / list1.len() as f64
+    };
+
+    format!(
+        "Overlap\n  distinct left: {}\n  distinct right: {}\n  distinct in both: {}\n  \
+         left entries found in right: {} of {} ({:.1}%)\n",
+        left.len(),
+        right.len(),
+        shared,
+        matched,
+        list1.len(),
+        percent
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bucket_counts() {
+        assert_eq!(
+            bucket_counts(&[1, 2, 3, 3, 4, 10], 3),
+            vec![(1, 4, 5), (5, 8, 0), (9, 10, 1)]
+        );
+        assert_eq!(bucket_counts(&[5, 5], 10), vec![(5, 5, 2)]);
+        assert!(bucket_counts(&[], 10).is_empty());
+        assert_eq!(bucket_counts(&[i64::MIN, i64::MAX], 1), vec![(i64::MIN, i64::MAX, 2)]);
+    }
+
+    #[test]
+    fn test_render_histogram() {
+        let histogram = render_histogram("Differences", &[0, 0, 1, 2]);
+        assert_eq!(
+            histogram,
+            "Differences (4 values)\n  0..=0 | ######################################## 2\n  \
+             1..=1 | #################### 1\n  2..=2 | #################### 1\n"
+        );
+    }
+
+    #[test]
+    fn test_overlap_statistics() {
+        let stats = overlap_statistics(&[3, 4, 2, 1, 3, 3], &[4, 3, 5, 3, 9, 3]);
+        assert!(stats.contains("distinct left: 4\n"));
+        assert!(stats.contains("distinct right: 4\n"));
+        assert!(stats.contains("distinct in both: 2\n"));
+        assert!(stats.contains("left entries found in right: 4 of 6 (66.7%)\n"));
+    }
+}
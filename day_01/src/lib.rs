@@ -0,0 +1,265 @@
+//! Day 1: Historian Hysteria
+//!
+//! Library entry points shared by the binary and the `aoc` runner.
+
+#[cfg(feature = "parquet")]
+pub mod export;
+pub mod external;
+pub mod histogram;
+pub mod tabular;
+
+use std::collections::HashMap;
+
+use aoc_common::{AppError, Solver};
+use tracing::{trace, Level};
+
+use crate::tabular::Table;
+
+/// Optional bounds on the input, none by default
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// Numbers must be below this, if set
+    pub max_value: Option<i64>,
+    /// The input must not have more lines, each a pair or row of columns,
+    /// than this, if set
+    pub max_pairs: Option<usize>,
+}
+
+/// Counts how many times each number appears in `list`
+pub fn frequency_map(list: &[i64]) -> HashMap<i64, i64> {
+    let mut frequency_map = HashMap::new();
+    for &number in list {
+        *frequency_map.entry(number).or_insert(0) += 1;
+    }
+    frequency_map
+}
+
+/// Reads one pair of numbers per line into two lists and sorts them,
+/// accepting any number of pairs of any `i64` values
+///
+/// # Arguments
+/// * `input` - The puzzle input, two whitespace-separated numbers per line
+///
+/// # Returns
+/// * Both lists, each sorted ascending, or an error if:
+///   - Any number cannot be parsed
+///   - A line doesn't contain exactly 2 numbers
+pub fn parse_pairs(input: &str) -> Result<(Vec<i64>, Vec<i64>), AppError> {
+    parse_pairs_with(input, &Limits::default())
+}
+
+/// Reads one pair of numbers per line into two lists and sorts them,
+/// enforcing `limits`
+///
+/// # Arguments
+/// * `input` - The puzzle input, two whitespace-separated numbers per line
+/// * `limits` - The bounds the input must stay within
+///
+/// # Returns
+/// * Both lists, each sorted ascending, or an error if:
+///   - Any number cannot be parsed
+///   - A line doesn't contain exactly 2 numbers
+///   - Any number is >= `limits.max_value`
+///   - Input exceeds `limits.max_pairs` pairs
+pub fn parse_pairs_with(input: &str, limits: &Limits) -> Result<(Vec<i64>, Vec<i64>), AppError> {
+    Table::default().pairs(input, limits)
+}
+
+/// Reads the same number of whitespace-separated numbers from every line,
+/// one list per column, and sorts each list
+///
+/// # Arguments
+/// * `input` - The input, with as many numbers on each line as on the first
+/// * `limits` - The bounds the input must stay within
+///
+/// # Returns
+/// * One list per column, each sorted ascending, or an error if:
+///   - Any number cannot be parsed
+///   - A line has a different number of columns than the first
+///   - Any number is >= `limits.max_value`
+///   - Input exceeds `limits.max_pairs` lines
+pub fn parse_columns_with(input: &str, limits: &Limits) -> Result<Vec<Vec<i64>>, AppError> {
+    Table::default().columns(input, limits)
+}
+
+/// Compares two of the columns read by `parse_columns_with`
+///
+/// # Arguments
+/// * `columns` - The sorted lists, one per column
+/// * `(left, right)` - The 0-based indices of the columns to compare
+///
+/// # Returns
+/// * The total distance and the similarity score of the two columns, taking
+///   `left` as the first list, or an error if either index is out of range
+///   or either answer overflows
+pub fn compare_columns(columns: &[Vec<i64>], (left, right): (usize, usize)) -> Result<(i64, i64), AppError> {
+    let (Some(list1), Some(list2)) = (columns.get(left), columns.get(right)) else {
+        return Err(AppError::ArgError("--columns refers to a column the input doesn't have"));
+    };
+    Ok((total_distance(list1, list2)?, similarity_score(list1, list2)?))
+}
+
+/// Sums the absolute differences between corresponding elements
+///
+/// # Arguments
+/// * `list1`, `list2` - The two lists, each sorted ascending as `parse_pairs` returns them
+///
+/// # Returns
+/// * The total distance between the lists, or `AppError::Overflow` if it
+///   doesn't fit in an `i64`
+pub fn total_distance(list1: &[i64], list2: &[i64]) -> Result<i64, AppError> {
+    list1.iter()
+        .zip(list2.iter())
+        .try_fold(0i64, |total, (a, b)| {
+            i64::try_from(a.abs_diff(*b)).ok().and_then(|distance| total.checked_add(distance))
+        })
+        .ok_or(AppError::Overflow("total distance"))
+}
+
+/// Sums each element of the first list multiplied by the number of times
+/// it appears in the second list
+///
+/// # Arguments
+/// * `list1`, `list2` - The two lists, in any order
+///
+/// # Returns
+/// * The similarity score of the lists, or `AppError::Overflow` if it
+///   doesn't fit in an `i64`
+pub fn similarity_score(list1: &[i64], list2: &[i64]) -> Result<i64, AppError> {
+    let frequency_map = frequency_map(list2);
+
+    // Per-number counts, shown with -vv
+    if tracing::enabled!(Level::TRACE) {
+        for (number, count) in &frequency_map {
+            trace!("Number {} appears {} times in list2", number, count);
+        }
+    }
+
+    let mut sum_of_products: i64 = 0;
+    for &num in list1 {
+        if let Some(&count) = frequency_map.get(&num) {
+            sum_of_products = num
+                .checked_mul(count)
+                .and_then(|product| sum_of_products.checked_add(product))
+                .ok_or(AppError::Overflow("similarity score"))?;
+        }
+    }
+    Ok(sum_of_products)
+}
+
+/// Solves both parts
+///
+/// # Arguments
+/// * `input` - The puzzle input, two whitespace-separated numbers per line
+///
+/// # Returns
+/// * The total distance and the similarity score, or an error if the input
+///   can't be parsed by `parse_pairs` or either answer overflows
+pub fn solve(input: &str) -> Result<(i64, i64), AppError> {
+    let (list1, list2) = parse_pairs(input)?;
+    Ok((total_distance(&list1, &list2)?, similarity_score(&list1, &list2)?))
+}
+
+/// Solver for the two location-ID lists
+pub struct Day01;
+
+impl Solver for Day01 {
+    /// Both lists, each sorted ascending
+    type Parsed = (Vec<i64>, Vec<i64>);
+
+    /// Reads both lists with `parse_pairs`
+    fn parse(input: &str) -> Result<Self::Parsed, AppError> {
+        parse_pairs(input)
+    }
+
+    /// Sums the absolute differences between corresponding elements
+    fn part1((list1, list2): &Self::Parsed) -> Result<String, AppError> {
+        Ok(total_distance(list1, list2)?.to_string())
+    }
+
+    /// Sums each element of the first list multiplied by the number of times
+    /// it appears in the second list
+    fn part2((list1, list2): &Self::Parsed) -> Result<String, AppError> {
+        Ok(similarity_score(list1, list2)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<(), AppError> {
+        let lists = Day01::parse(&std::fs::read_to_string("data/inputtest")?)?;
+        assert_eq!(Day01::part1(&lists)?, "11");
+        assert_eq!(Day01::part2(&lists)?, "31");
+        Ok(())
+    }
+
+    #[test]
+    fn test_library_api() -> Result<(), AppError> {
+        let input = std::fs::read_to_string("data/inputtest")?;
+        let (list1, list2) = parse_pairs(&input)?;
+        assert_eq!(list1, [1, 2, 3, 3, 3, 4]);
+        assert_eq!(list2, [3, 3, 3, 4, 5, 9]);
+        assert_eq!(total_distance(&list1, &list2)?, 11);
+        assert_eq!(similarity_score(&list1, &list2)?, 31);
+        assert_eq!(solve(&input)?, (11, 31));
+        Ok(())
+    }
+
+    #[test]
+    fn test_similarity_ignores_order() -> Result<(), AppError> {
+        assert_eq!(similarity_score(&[3, 4, 2, 1, 3, 3], &[4, 3, 5, 3, 9, 3])?, 31);
+        assert_eq!(similarity_score(&[], &[1, 2])?, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_large_input() -> Result<(), AppError> {
+        let input: String = (0..5000i64).map(|i| format!("{} {}\n", 3_000_000_000 + i, i)).collect();
+        let (distance, similarity) = solve(&input)?;
+        assert_eq!(distance, 5000 * 3_000_000_000);
+        assert_eq!(similarity, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_overflow() {
+        let max = i64::MAX.to_string();
+        let input = format!("{max} 0\n{max} 0\n");
+        assert!(matches!(solve(&input), Err(AppError::Overflow("total distance"))));
+        assert!(matches!(total_distance(&[i64::MIN], &[1]), Err(AppError::Overflow(_))));
+        let input = format!("{max} {max}\n1 {max}\n");
+        let (list1, list2) = parse_pairs(&input).unwrap();
+        assert!(matches!(similarity_score(&list1, &list2), Err(AppError::Overflow("similarity score"))));
+    }
+
+    #[test]
+    fn test_columns() -> Result<(), AppError> {
+        let columns = parse_columns_with("3 4 1\n4 3 1\n2 5 2\n1 3 3\n3 9 3\n3 3 9", &Limits::default())?;
+        assert_eq!(columns.len(), 3);
+        assert_eq!(compare_columns(&columns, (0, 1))?, (11, 31));
+        assert_eq!(compare_columns(&columns, (1, 2))?, (8, 27));
+        assert_eq!(compare_columns(&columns, (2, 0))?, (7, 22));
+        assert!(matches!(compare_columns(&columns, (0, 3)), Err(AppError::ArgError(_))));
+
+        assert!(matches!(
+            parse_columns_with("1 2 3\n4 5", &Limits::default()),
+            Err(AppError::ColumnCount { line: 2, expected: 3, got: 2 })
+        ));
+        assert!(parse_columns_with("", &Limits::default())?.is_empty());
+        assert_eq!(parse_pairs("")?, (Vec::new(), Vec::new()));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(matches!(Day01::parse("1 2 3"), Err(AppError::InvalidPairCount)));
+        assert!(matches!(Day01::parse("1 2\n3"), Err(AppError::InvalidPairCount)));
+        let limits = Limits { max_value: Some(100_000), max_pairs: Some(2) };
+        assert!(matches!(parse_pairs_with("1 100000", &limits), Err(AppError::ValueTooLarge(100_000))));
+        assert!(matches!(parse_pairs_with("1 2\n3 4\n5 6", &limits), Err(AppError::ListTooLong(2))));
+        assert!(parse_pairs_with("1 2\n3 4", &limits).is_ok());
+    }
+}
@@ -1,4 +1,4 @@
-//! A program that processes pairs of numbers from stdin, sorts them, and calculates
+//! A program that processes pairs of numbers from a file or stdin, sorts them, and calculates
 //! 1) the sum of absolute differences between corresponding elements
 //! 2) the total similiarity score by taking each element in the first list and multiplying
 //! the value times the number of times the the number appears in the second list.
@@ -6,8 +6,9 @@
 //! # Running the Program
 //! From the project root directory, you can run the program in several ways:
 //!
-//! 1. Using input.txt:
+//! 1. Using input.txt, given as a path or on stdin:
 //! ```bash
+//! cargo run -- data/input.txt
 //! cargo run < data/input.txt
 //! ```
 //!
@@ -19,49 +20,96 @@
 //! 10 15
 //! <Ctrl+D>
 //! ```
+//!
+//! 3. Printing histograms of both lists and the pairwise differences, plus overlap statistics:
+//! ```bash
+//! cargo run -- --histogram < data/input.txt
+//! ```
+//!
+//! 4. Writing the sorted pairs to a Parquet file (requires the `parquet` feature):
+//! ```bash
+//! cargo run --features parquet -- --emit-parquet pairs.parquet < data/input.txt
+//! ```
+//!
+//! 5. Printing the answers as a JSON object instead:
+//! ```bash
+//! cargo run -- --format json < data/input.txt
+//! ```
+//!
+//! 6. Rejecting inputs outside the puzzle's bounds, which are otherwise any
+//!    number of pairs of any 64-bit numbers:
+//! ```bash
+//! cargo run -- --max-value 100000 --max-pairs 1000 < data/input.txt
+//! ```
+//!
+//! 7. Reading any number of columns, the same on every line, and comparing
+//!    chosen pairs of them by their 0-based indices; histograms and the
+//!    Parquet export use the first pair:
+//! ```bash
+//! cargo run -- --columns 0,2 --columns 1,2 < data/columns.txt
+//! ```
+//!
+//! 8. Reading a spreadsheet export, split on a delimiter (a single character,
+//!    `tab` or the default `whitespace`) and skipping its header row:
+//! ```bash
+//! cargo run -- data/inputtest.csv --delimiter , --header
+//! ```
+//!
+//! 9. Solving an input too large for memory, by sorting chunks of pairs on
+//!    disk and merging them (`--chunk-pairs` sets the chunk size and implies
+//!    `--external`):
+//! ```bash
+//! cargo run --release -- huge.txt --external --chunk-pairs 1000000
+//! ```
 
-use std::io::{self, BufRead};
-use std::error::Error;
-use std::collections::HashMap;
+// The numbered list above wraps its second item without indenting it
+#![allow(clippy::doc_lazy_continuation)]
 
-/// Maximum allowed value for any input number
-const MAX_VALUE: i32 = 100_000;
-/// Maximum allowed size for the input lists
-const MAX_LIST_SIZE: usize = 1000;
+use std::io;
+use std::error::Error;
+use std::time::{Duration, Instant};
 
-/// Custom error type for the application
-#[derive(Debug)]
-enum AppError {
-    IoError(io::Error),
-    ParseError(std::num::ParseIntError),
-    InvalidPairCount,
-    ValueTooLarge(i32),
-    ListTooLong(usize),
-}
+use aoc_common::input::InputSource;
+use aoc_common::logging;
+use aoc_common::messages;
+use aoc_common::msg;
+use aoc_common::output::{take_format, Format, Report};
+use aoc_common::AppError;
+#[cfg(feature = "parquet")]
+use day_01::{export, frequency_map};
+use day_01::external::{solve_external, ExternalSort};
+use day_01::tabular::Table;
+use day_01::{compare_columns, histogram, Limits};
 
-impl std::error::Error for AppError {}
-impl std::fmt::Display for AppError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::IoError(e) => write!(f, "IO error: {}", e),
-            Self::ParseError(e) => write!(f, "Parse error: {}", e),
-            Self::InvalidPairCount => write!(f, "Each line must contain exactly 2 numbers"),
-            Self::ValueTooLarge(max) => write!(f, "Input contains numbers >= {}", max),
-            Self::ListTooLong(max) => write!(f, "Lists must not exceed {} elements", max),
+/// Prints the answers for one pair of lists
+fn print_answers(format: Format, total: i64, products: i64, elapsed: Duration) {
+    match format {
+        Format::Text => {
+            println!("{}", msg!("day01-total", total));
+            println!("{}", msg!("day01-products", products));
+        }
+        Format::Json => {
+            let report = Report { day: 1, part1: Some(total.to_string()), part2: Some(products.to_string()), elapsed };
+            println!("{}", report.to_json());
         }
     }
 }
 
-/// Main function that reads number pairs from stdin, validates them,
+/// Main function that reads number pairs from a file or stdin, validates them,
 /// sorts both lists, and calculates the sum of absolute differences and
 /// the total similiarity score.
 ///
 /// # Error Handling
 /// Returns an error if:
 /// - Input cannot be read or parsed
-/// - A line doesn't contain exactly 2 numbers
-/// - Any number is >= MAX_VALUE
-/// - Input exceeds MAX_LIST_SIZE pairs
+/// - A line doesn't contain exactly 2 numbers, or with `--columns` as many
+///   numbers as the first line
+/// - A `--columns` index is past the last column
+/// - Any number is >= `--max-value`, or input exceeds `--max-pairs` pairs
+/// - Either answer overflows an `i64`
+/// - An unknown argument is given, or `--emit-parquet` is used without the `parquet` feature
+/// - `--external` is combined with an option that needs the lists in memory,
+///   or its temporary files can't be written
 ///
 /// # Example Input Format
 /// ```text
@@ -70,68 +118,123 @@ impl std::fmt::Display for AppError {
 /// 10 15
 /// ```
 fn main() -> Result<(), Box<dyn Error>> {
-    let mut list1 = Vec::with_capacity(MAX_LIST_SIZE);
-    let mut list2 = Vec::with_capacity(MAX_LIST_SIZE);
-
-    // Read and validate input line by line
-    for line in io::stdin().lock().lines() {
-        let line = line.map_err(AppError::IoError)?;
-        let numbers: Vec<i32> = line
-            .split_whitespace()
-            .map(|s| s.parse().map_err(AppError::ParseError))
-            .collect::<Result<_, _>>()?;
-        
-        if numbers.len() != 2 {
-            return Err(Box::new(AppError::InvalidPairCount));
-        }
-        
-        if numbers[0] >= MAX_VALUE || numbers[1] >= MAX_VALUE {
-            return Err(Box::new(AppError::ValueTooLarge(MAX_VALUE)));
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let format = take_format(&mut args)?;
+    logging::init(logging::take_verbosity(&mut args));
+    messages::init(messages::take_lang(&mut args)?.as_deref())?;
+    let mut args = args.into_iter().peekable();
+    let source = InputSource::from_arg(args.next_if(|arg| !arg.starts_with("--")).as_deref());
+    let mut parquet_path = None;
+    let mut show_histogram = false;
+    let mut limits = Limits::default();
+    let mut column_pairs = Vec::new();
+    let mut table = Table::default();
+    let mut external = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--emit-parquet" => {
+                parquet_path = Some(
+                    args.next()
+                        .ok_or(AppError::ArgError("--emit-parquet requires a path"))?,
+                )
+            }
+            "--histogram" => show_histogram = true,
+            "--columns" => {
+                let value = args.next().ok_or(AppError::ArgError("--columns requires two indices"))?;
+                let (left, right) = value
+                    .split_once(',')
+                    .ok_or(AppError::ArgError("--columns must be two indices like 0,1"))?;
+                column_pairs.push((left.parse()?, right.parse()?));
+            }
+            "--delimiter" => {
+                let value = args.next().ok_or(AppError::ArgError("--delimiter requires a character"))?;
+                table.delimiter = value.parse()?;
+            }
+            "--header" => table.header = true,
+            "--external" => {
+                external.get_or_insert_with(ExternalSort::default);
+            }
+            "--chunk-pairs" => {
+                let value = args.next().ok_or(AppError::ArgError("--chunk-pairs requires a number"))?;
+                external.get_or_insert_with(ExternalSort::default).chunk_pairs = value.parse()?;
+            }
+            "--max-value" => {
+                let value = args.next().ok_or(AppError::ArgError("--max-value requires a number"))?;
+                limits.max_value = Some(value.parse()?);
+            }
+            "--max-pairs" => {
+                let value = args.next().ok_or(AppError::ArgError("--max-pairs requires a number"))?;
+                limits.max_pairs = Some(value.parse()?);
+            }
+            _ => {
+                return Err(Box::new(AppError::ArgError(
+                    "Usage: day_01 [<input>] [--columns <a,b>]... [--delimiter <c>] [--header] [--external] [--chunk-pairs <n>] [--histogram] [--max-value <n>] [--max-pairs <n>] [--emit-parquet <path>] [--format <text|json>] [-v|-vv]",
+                )))
+            }
         }
+    }
+    if column_pairs.len() > 1 && format == Format::Json {
+        return Err(Box::new(AppError::ArgError("--format json allows a single --columns pair")));
+    }
+    if parquet_path.is_some() && cfg!(not(feature = "parquet")) {
+        return Err(Box::new(AppError::ArgError(
+            "--emit-parquet requires building with --features parquet",
+        )));
+    }
 
-        if list1.len() == MAX_LIST_SIZE {
-            return Err(Box::new(AppError::ListTooLong(MAX_LIST_SIZE)));
+    if let Some(config) = external {
+        if !column_pairs.is_empty() || show_histogram || parquet_path.is_some() {
+            return Err(Box::new(AppError::ArgError(
+                "--external can't be combined with --columns, --histogram or --emit-parquet",
+            )));
         }
-        
-        list1.push(numbers[0]);
-        list2.push(numbers[1]);
+        let start = Instant::now();
+        let (total, products) = solve_external(&mut source.open()?, &table, &limits, &config)?;
+        print_answers(format, total, products, start.elapsed());
+        return Ok(());
     }
 
-    // Sort both lists using sort_unstable (faster than stable sort when ordering of equal elements doesn't matter)
-    list1.sort_unstable();
-    list2.sort_unstable();
-
-    // Combine frequency map calculation and sum calculation
-    let mut frequency_map = HashMap::new();
-    let mut sum_of_products = 0;
+    let input = io::read_to_string(source.open()?)?;
+    let start = Instant::now();
+    let columns = if column_pairs.is_empty() {
+        column_pairs.push((0, 1));
+        let (list1, list2) = table.pairs(&input, &limits)?;
+        vec![list1, list2]
+    } else {
+        table.columns(&input, &limits)?
+    };
+    let answers = column_pairs
+        .iter()
+        .map(|&pair| compare_columns(&columns, pair))
+        .collect::<Result<Vec<_>, _>>()?;
+    let elapsed = start.elapsed();
 
-    // Build frequency map
-    for &number in &list2 {
-        *frequency_map.entry(number).or_insert(0) += 1;
-    }
-
-    // Optional debug output
-    #[cfg(debug_assertions)]
-    for (number, count) in &frequency_map {
-        println!("Number {} appears {} times in list2", number, count);
+    // Output the result
+    if let [(total, products)] = answers[..] {
+        print_answers(format, total, products, elapsed);
+    } else {
+        for ((left, right), (total, products)) in column_pairs.iter().zip(&answers) {
+            println!("{}", msg!("day01-columns", left, right, total, products));
+        }
     }
 
-    // Calculate the sum of absolute differences between corresponding elements
-    let total: i32 = list1.iter()
-        .zip(list2.iter())
-        .map(|(a, b)| (*a - *b).abs())
-        .sum();
+    let (list1, list2) = (&columns[column_pairs[0].0], &columns[column_pairs[0].1]);
 
-    // Output the result
-    println!("Total: {total}");
+    if show_histogram && format == Format::Text {
+        // total_distance succeeded, so no single difference overflows
+        let differences: Vec<i64> = list1.iter().zip(list2).map(|(a, b)| (a - b).abs()).collect();
+        println!();
+        println!("{}", histogram::render_histogram("Left list", list1));
+        println!("{}", histogram::render_histogram("Right list", list2));
+        println!("{}", histogram::render_histogram("Pair differences", &differences));
+        print!("{}", histogram::overlap_statistics(list1, list2));
+    }
 
-    // Calculate sum using the frequency map
-    for &num in &list1 {
-        if let Some(&count) = frequency_map.get(&num) {
-            sum_of_products += num * count;
-        }
+    #[cfg(feature = "parquet")]
+    if let Some(path) = parquet_path {
+        export::write_pairs_parquet(&path, list1, list2, &frequency_map(list2))?;
+        eprintln!("{}", msg!("day01-wrote-parquet", list1.len(), path));
     }
 
-    println!("Sum of products: {}", sum_of_products);
     Ok(())
 }
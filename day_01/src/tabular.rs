@@ -0,0 +1,186 @@
+//! Splitting the input into rows of numbers.
+//!
+//! The puzzle separates its columns with whitespace. A `Table` can instead
+//! split on a single character, so CSV and TSV files exported from a
+//! spreadsheet can be read directly, and can skip a header row. Fields are
+//! trimmed and may be wrapped in double quotes.
+
+use std::str::FromStr;
+
+use aoc_common::AppError;
+
+use crate::Limits;
+
+/// What separates the columns of a row
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Delimiter {
+    /// Any run of whitespace, as in the puzzle
+    #[default]
+    Whitespace,
+    /// Exactly this character, such as `,` or a tab
+    Char(char),
+}
+
+impl FromStr for Delimiter {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "whitespace" => Ok(Self::Whitespace),
+            "tab" | "\\t" => Ok(Self::Char('\t')),
+            _ => {
+                let mut chars = s.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => Ok(Self::Char(c)),
+                    _ => Err(AppError::ArgError("--delimiter must be a single character, tab or whitespace")),
+                }
+            }
+        }
+    }
+}
+
+/// How the rows of the input are laid out
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Table {
+    /// What separates the columns
+    pub delimiter: Delimiter,
+    /// Whether the first line holds column names rather than numbers
+    pub header: bool,
+}
+
+impl Table {
+    /// Splits one line into its fields
+    fn fields<'a>(&self, line: &'a str) -> Box<dyn Iterator<Item = &'a str> + 'a> {
+        match self.delimiter {
+            Delimiter::Whitespace => Box::new(line.split_whitespace()),
+            Delimiter::Char(c) => Box::new(line.split(c).map(|field| field.trim().trim_matches('"'))),
+        }
+    }
+
+    /// Parses every field of one row as a number
+    pub(crate) fn numbers(&self, line: &str) -> Result<Vec<i64>, AppError> {
+        self.fields(line).map(|s| s.parse().map_err(AppError::ParseError)).collect()
+    }
+
+    /// Reads the same number of numbers from every row, one list per
+    /// column, and sorts each list
+    ///
+    /// # Arguments
+    /// * `input` - The input, with as many fields on each row as on the first
+    /// * `limits` - The bounds the input must stay within
+    ///
+    /// # Returns
+    /// * One list per column, each sorted ascending, or an error if:
+    ///   - Any field isn't a number
+    ///   - A row has a different number of columns than the first
+    ///   - Any number is >= `limits.max_value`
+    ///   - Input exceeds `limits.max_pairs` rows, not counting the header
+    pub fn columns(&self, input: &str, limits: &Limits) -> Result<Vec<Vec<i64>>, AppError> {
+        let mut columns: Vec<Vec<i64>> = Vec::new();
+        let skip = usize::from(self.header);
+
+        // Read and validate input line by line
+        for (index, line) in input.lines().enumerate().skip(skip) {
+            let numbers = self.numbers(line)?;
+
+            let rows = index - skip;
+            if rows == 0 {
+                columns = vec![Vec::new(); numbers.len()];
+            } else if numbers.len() != columns.len() {
+                return Err(AppError::ColumnCount { line: index + 1, expected: columns.len(), got: numbers.len() });
+            }
+
+            if let Some(max) = limits.max_value {
+                if numbers.iter().any(|&number| number >= max) {
+                    return Err(AppError::ValueTooLarge(max));
+                }
+            }
+
+            if limits.max_pairs == Some(rows) {
+                return Err(AppError::ListTooLong(rows));
+            }
+
+            for (column, number) in columns.iter_mut().zip(numbers) {
+                column.push(number);
+            }
+        }
+
+        // Sort every list using sort_unstable (faster than stable sort when ordering of equal elements doesn't matter)
+        for column in &mut columns {
+            column.sort_unstable();
+        }
+
+        Ok(columns)
+    }
+
+    /// Reads exactly two numbers from every row into two lists and sorts them
+    ///
+    /// # Arguments
+    /// * `input` - The input, two fields per row
+    /// * `limits` - The bounds the input must stay within
+    ///
+    /// # Returns
+    /// * Both lists, each sorted ascending, or the errors of `columns`, with
+    ///   `AppError::InvalidPairCount` for any row without exactly 2 numbers
+    pub fn pairs(&self, input: &str, limits: &Limits) -> Result<(Vec<i64>, Vec<i64>), AppError> {
+        let columns = self.columns(input, limits).map_err(|error| match error {
+            AppError::ColumnCount { .. } => AppError::InvalidPairCount,
+            error => error,
+        })?;
+
+        match <[Vec<i64>; 2]>::try_from(columns) {
+            Ok([list1, list2]) => Ok((list1, list2)),
+            // No rows at all gives no columns
+            Err(columns) if columns.is_empty() => Ok((Vec::new(), Vec::new())),
+            Err(_) => Err(AppError::InvalidPairCount),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_delimiter_from_str() -> Result<(), AppError> {
+        assert_eq!("whitespace".parse::<Delimiter>()?, Delimiter::Whitespace);
+        assert_eq!(",".parse::<Delimiter>()?, Delimiter::Char(','));
+        assert_eq!("tab".parse::<Delimiter>()?, Delimiter::Char('\t'));
+        assert_eq!("\\t".parse::<Delimiter>()?, Delimiter::Char('\t'));
+        assert!(matches!(";;".parse::<Delimiter>(), Err(AppError::ArgError(_))));
+        assert!(matches!("".parse::<Delimiter>(), Err(AppError::ArgError(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_csv_with_header() -> Result<(), AppError> {
+        let table = Table { delimiter: Delimiter::Char(','), header: true };
+        let input = "left,right\r\n3,4\r\n4, 3\r\n\"2\",5\r\n1,3\r\n3,9\r\n3,3\r\n";
+        let (list1, list2) = table.pairs(input, &Limits::default())?;
+        assert_eq!(list1, [1, 2, 3, 3, 3, 4]);
+        assert_eq!(list2, [3, 3, 3, 4, 5, 9]);
+
+        let limits = Limits { max_value: None, max_pairs: Some(6) };
+        assert!(table.pairs(input, &limits).is_ok());
+
+        assert!(matches!(
+            Table { header: false, ..table }.pairs(input, &Limits::default()),
+            Err(AppError::ParseError(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tsv_columns() -> Result<(), AppError> {
+        let table = Table { delimiter: Delimiter::Char('\t'), header: true };
+        let columns = table.columns("a\tb\tc\n1\t2\t3\n4\t5\t6\n", &Limits::default())?;
+        assert_eq!(columns, [vec![1, 4], vec![2, 5], vec![3, 6]]);
+        assert!(matches!(
+            table.columns("a\tb\n1\t2\n3\n", &Limits::default()),
+            Err(AppError::ColumnCount { line: 3, expected: 2, got: 1 })
+        ));
+        // A field left empty isn't a number
+        assert!(matches!(table.columns("a\tb\n1\t\n", &Limits::default()), Err(AppError::ParseError(_))));
+        Ok(())
+    }
+}
@@ -0,0 +1,28 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use day_02::{classify_report, classify_report_brute_force, is_safe_report, is_safe_with_dampener_k};
+
+const INPUT: &str = include_str!("../data/input.txt");
+
+fn bench_reports(c: &mut Criterion) {
+    let reports: Vec<Vec<i32>> = INPUT
+        .lines()
+        .map(|line| line.split_whitespace().map(|level| level.parse().unwrap()).collect())
+        .collect();
+
+    c.bench_function("is_safe_report", |b| {
+        b.iter(|| reports.iter().filter(|levels| is_safe_report(black_box(levels))).count())
+    });
+    c.bench_function("classify_report", |b| {
+        b.iter(|| reports.iter().map(|levels| classify_report(black_box(levels))).collect::<Vec<_>>())
+    });
+    c.bench_function("classify_report_brute_force", |b| {
+        b.iter(|| reports.iter().map(|levels| classify_report_brute_force(black_box(levels))).collect::<Vec<_>>())
+    });
+    c.bench_function("is_safe_with_dampener_k/3", |b| {
+        b.iter(|| reports.iter().filter(|levels| is_safe_with_dampener_k(black_box(levels), 3)).count())
+    });
+}
+
+criterion_group!(benches, bench_reports);
+criterion_main!(benches);
@@ -0,0 +1,85 @@
+//! One-line explanations of each report's classification, for `--explain`.
+//!
+//! Levels are numbered from 0 within their report, as in `Verdict`.
+
+use crate::{Classification, SafetyRules};
+
+/// Explains whether a report is safe without the dampener
+///
+/// # Arguments
+/// * `rules` - The rules the report is judged by
+/// * `line` - The 1-based line number of the report
+/// * `levels` - A slice of integers representing the levels in a report
+///
+/// # Returns
+/// * The line number and levels, followed by the first rule broken, if any
+pub fn explain_verdict(rules: &SafetyRules, line: usize, levels: &[i32]) -> String {
+    let numbers: Vec<String> = levels.iter().map(i32::to_string).collect();
+    format!("Line {}: {} - {}", line, numbers.join(" "), rules.check(levels))
+}
+
+/// Explains why a report got its classification
+///
+/// # Arguments
+/// * `rules` - The rules the report was judged by
+/// * `line` - The 1-based line number of the report
+/// * `levels` - A slice of integers representing the levels in a report
+/// * `classification` - How the report was judged
+/// * `removed_index` - The level the dampener removed, if it removed exactly one
+///
+/// # Returns
+/// * The line number and levels, followed by the verdict, the rule broken
+///   and the level removed where that applies
+pub fn explain_report(
+    rules: &SafetyRules,
+    line: usize,
+    levels: &[i32],
+    classification: Classification,
+    removed_index: Option<usize>,
+) -> String {
+    let numbers: Vec<String> = levels.iter().map(i32::to_string).collect();
+    let verdict = rules.check(levels);
+    let reason = match (classification, removed_index) {
+        (Classification::Safe, _) => "safe".to_string(),
+        (Classification::Dampened, Some(index)) => format!(
+            "safe with the dampener: {}; removing level {} ({}) fixes it",
+            verdict, index, levels[index]
+        ),
+        (Classification::Dampened, None) => format!("safe with the dampener: {}", verdict),
+        (Classification::Unsafe, _) => format!("unsafe: {}; the dampener can't fix it", verdict),
+    };
+    format!("Line {}: {} - {}", line, numbers.join(" "), reason)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classify_report;
+
+    #[test]
+    fn test_explain_report() {
+        let explain = |line, levels: &[i32]| {
+            let (classification, removed_index) = classify_report(levels);
+            explain_report(&SafetyRules::default(), line, levels, classification, removed_index)
+        };
+        assert_eq!(explain(1, &[7, 6, 4, 2, 1]), "Line 1: 7 6 4 2 1 - safe");
+        assert_eq!(
+            explain_verdict(&SafetyRules::default(), 4, &[1, 3, 2, 4, 5]),
+            "Line 4: 1 3 2 4 5 - levels 1 and 2 change direction (by -1)"
+        );
+        assert_eq!(
+            explain(2, &[1, 2, 7, 8, 9]),
+            "Line 2: 1 2 7 8 9 - unsafe: levels 1 and 2 differ by 5, outside the allowed range; the dampener can't fix it"
+        );
+        assert_eq!(
+            explain(4, &[1, 3, 2, 4, 5]),
+            "Line 4: 1 3 2 4 5 - safe with the dampener: levels 1 and 2 change direction (by -1); \
+             removing level 1 (3) fixes it"
+        );
+        assert_eq!(
+            explain(5, &[8, 6, 4, 4, 1]),
+            "Line 5: 8 6 4 4 1 - safe with the dampener: levels 2 and 3 differ by 0, outside the allowed range; \
+             removing level 2 (4) fixes it"
+        );
+    }
+}
@@ -0,0 +1,56 @@
+//! Parquet export of the per-report classifications (requires the `parquet` feature).
+
+use std::error::Error;
+use std::fs::File;
+use std::sync::Arc;
+
+use arrow_array::types::Int32Type;
+use arrow_array::{ListArray, RecordBatch, StringArray, UInt64Array};
+use arrow_schema::{DataType, Field, Schema};
+use parquet::arrow::ArrowWriter;
+
+use crate::Classification;
+
+/// A single report together with its classification
+pub struct ReportRecord {
+    pub line: usize,
+    pub levels: Vec<i32>,
+    pub classification: Classification,
+    pub removed_index: Option<usize>,
+}
+
+/// Writes one row per report to a Parquet file
+///
+/// # Columns
+/// * `line` - 1-based line number of the report in the input
+/// * `levels` - The levels of the report
+/// * `classification` - `safe`, `dampened` or `unsafe`
+/// * `removed_index` - Index of the level the dampener removed, null otherwise
+pub fn write_reports_parquet(path: &str, records: &[ReportRecord]) -> Result<(), Box<dyn Error>> {
+    let levels_field = Field::new_list_field(DataType::Int32, true);
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("line", DataType::UInt64, false),
+        Field::new("levels", DataType::List(Arc::new(levels_field)), false),
+        Field::new("classification", DataType::Utf8, false),
+        Field::new("removed_index", DataType::UInt64, true),
+    ]));
+
+    let lines = UInt64Array::from_iter_values(records.iter().map(|r| r.line as u64));
+    let levels = ListArray::from_iter_primitive::<Int32Type, _, _>(
+        records
+            .iter()
+            .map(|r| Some(r.levels.iter().copied().map(Some).collect::<Vec<_>>())),
+    );
+    let classifications = StringArray::from_iter_values(records.iter().map(|r| r.classification.as_str()));
+    let removed = UInt64Array::from_iter(records.iter().map(|r| r.removed_index.map(|i| i as u64)));
+
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![Arc::new(lines), Arc::new(levels), Arc::new(classifications), Arc::new(removed)],
+    )?;
+
+    let mut writer = ArrowWriter::try_new(File::create(path)?, schema, None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
@@ -0,0 +1,537 @@
+//! Day 2: Red-Nosed Reports
+//!
+//! Library entry points shared by the binary and the `aoc` runner.
+
+#[cfg(feature = "parquet")]
+pub mod export;
+pub mod explain;
+pub mod sparkline;
+pub mod stats;
+
+use std::fmt;
+
+use aoc_common::{AppError, Solver};
+use rayon::prelude::*;
+use tracing::trace;
+
+/// Which safety rule a report breaks first, if any
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    /// Every rule holds
+    Safe,
+    /// Levels `index` and `index + 1` differ by `diff`, outside the allowed range
+    DiffOutOfRange { index: usize, diff: i32 },
+    /// Levels `index` and `index + 1` move against the direction set by the
+    /// first two levels that differ
+    DirectionChange { index: usize, diff: i32 },
+}
+
+impl Verdict {
+    pub fn is_safe(&self) -> bool {
+        *self == Self::Safe
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Safe => write!(f, "safe"),
+            Self::DiffOutOfRange { index, diff } => {
+                write!(f, "levels {} and {} differ by {}, outside the allowed range", index, index + 1, diff.abs())
+            }
+            Self::DirectionChange { index, diff } => {
+                write!(f, "levels {} and {} change direction (by {:+})", index, index + 1, diff)
+            }
+        }
+    }
+}
+
+/// How a report was judged by the safety rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Classification {
+    /// Safe as-is
+    Safe,
+    /// Safe only after the dampener removed one level
+    Dampened,
+    /// Unsafe even with the dampener
+    Unsafe,
+}
+
+impl Classification {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Safe => "safe",
+            Self::Dampened => "dampened",
+            Self::Unsafe => "unsafe",
+        }
+    }
+}
+
+/// The rules a report must follow to be safe.
+///
+/// The default is the puzzle's: adjacent levels differ by 1 to 3 and never
+/// repeat.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SafetyRules {
+    /// Smallest allowed difference between adjacent levels that differ
+    pub min_diff: i32,
+    /// Largest allowed difference between adjacent levels
+    pub max_diff: i32,
+    /// Whether adjacent levels must differ, making the report strictly
+    /// monotonic; if not, equal neighbours are allowed whatever `min_diff` is
+    pub strict: bool,
+}
+
+impl Default for SafetyRules {
+    fn default() -> Self {
+        Self { min_diff: 1, max_diff: 3, strict: true }
+    }
+}
+
+impl SafetyRules {
+    /// Whether a step of `diff` is allowed, measured along the report's direction
+    fn allows(&self, diff: i32) -> bool {
+        (self.min_diff..=self.max_diff).contains(&diff) || (diff == 0 && !self.strict)
+    }
+
+    /// Checks a report against the rules
+    ///
+    /// # Arguments
+    /// * `levels` - A slice of integers representing the levels in a report
+    ///
+    /// # Returns
+    /// * `Verdict::Safe` if:
+    ///   - All numbers are increasing or all decreasing, strictly if `strict` is set
+    ///   - Each adjacent pair differs by `min_diff` to `max_diff`
+    /// * Otherwise the first rule broken, and where
+    pub fn check(&self, levels: &[i32]) -> Verdict {
+        // The first pair that differs sets the direction
+        let is_increasing = levels
+            .windows(2)
+            .map(|pair| pair[1] - pair[0])
+            .find(|&diff| diff != 0)
+            .is_some_and(|diff| diff > 0);
+
+        for (index, pair) in levels.windows(2).enumerate() {
+            let diff = pair[1] - pair[0];
+            if diff == 0 && !self.strict {
+                continue;
+            }
+
+            let diff_abs = diff.abs();
+
+            // if two adjacent levels are the same or
+            // differ by too much or too little, report is unsafe
+            if diff == 0 || diff_abs < self.min_diff || diff_abs > self.max_diff {
+                return Verdict::DiffOutOfRange { index, diff };
+            }
+
+            // If direction changes, report is unsafe
+            if (diff > 0) != is_increasing {
+                return Verdict::DirectionChange { index, diff };
+            }
+        }
+
+        Verdict::Safe
+    }
+
+    /// Whether `levels`, leaving out the level at `skip`, follow the rules at
+    /// every step in the given direction
+    fn is_monotonic_without(&self, levels: &[i32], increasing: bool, skip: Option<usize>) -> bool {
+        let mut kept = levels.iter().enumerate().filter(|&(i, _)| Some(i) != skip).map(|(_, &level)| level);
+        let Some(mut prev) = kept.next() else {
+            return true;
+        };
+        for current in kept {
+            let diff = if increasing { current - prev } else { prev - current };
+            if !self.allows(diff) {
+                return false;
+            }
+            prev = current;
+        }
+        true
+    }
+
+    /// Classifies a report, trying the dampener if it isn't safe on its own
+    ///
+    /// Runs in O(n): within one direction, a removal can only help if it takes
+    /// out one of the two levels of the first pair that breaks the rules, so at
+    /// most four removals are checked rather than every level.
+    ///
+    /// # Returns
+    /// * The classification, and the index of the removed level if the dampener was needed
+    pub fn classify(&self, levels: &[i32]) -> (Classification, Option<usize>) {
+        if self.check(levels).is_safe() {
+            trace!(?levels, "safe without dampener");
+            return (Classification::Safe, None);
+        }
+
+        if levels.len() > 2 {
+            let mut removed = None;
+            for increasing in [true, false] {
+                let Some(i) = levels
+                    .windows(2)
+                    .position(|pair| !self.is_monotonic_without(pair, increasing, None))
+                else {
+                    continue;
+                };
+                for candidate in [i, i + 1] {
+                    if removed.is_none_or(|removed| candidate < removed)
+                        && self.is_monotonic_without(levels, increasing, Some(candidate))
+                    {
+                        removed = Some(candidate);
+                    }
+                }
+            }
+            if let Some(i) = removed {
+                trace!(?levels, removed = i, "safe with dampener");
+                return (Classification::Dampened, Some(i));
+            }
+        }
+
+        (Classification::Unsafe, None)
+    }
+
+    /// Finds the fewest levels to remove so the rest follow the rules in one
+    /// direction, if that takes at most `k` removals
+    ///
+    /// `kept[i]` is the fewest removals among levels `0..=i` that keep level `i`
+    /// as the last one; only the `k + 1` levels before `i` can precede it, as
+    /// anything further back would remove more than `k` in between. That makes
+    /// the search O(n * k) rather than trying every set of removals.
+    fn removals_in_direction(&self, levels: &[i32], k: usize, increasing: bool) -> Option<Vec<usize>> {
+        let n = levels.len();
+        let mut kept = vec![0; n];
+        let mut previous = vec![None; n];
+
+        for i in 0..n {
+            kept[i] = i;
+            for j in i.saturating_sub(k + 1)..i {
+                let diff = if increasing { levels[i] - levels[j] } else { levels[j] - levels[i] };
+                if self.allows(diff) && kept[j] + (i - j - 1) <= kept[i] {
+                    kept[i] = kept[j] + (i - j - 1);
+                    previous[i] = Some(j);
+                }
+            }
+        }
+
+        let last = (0..n).min_by_key(|&i| kept[i] + (n - 1 - i))?;
+        if kept[last] + (n - 1 - last) > k {
+            return None;
+        }
+
+        let mut is_kept = vec![false; n];
+        let mut current = Some(last);
+        while let Some(i) = current {
+            is_kept[i] = true;
+            current = previous[i];
+        }
+        Some((0..n).filter(|&i| !is_kept[i]).collect())
+    }
+
+    /// Finds the fewest levels to remove to make a report safe
+    ///
+    /// # Arguments
+    /// * `levels` - A slice of integers representing the levels in a report
+    /// * `k` - The most levels the dampener may remove
+    ///
+    /// # Returns
+    /// * The indices of the removed levels, in order, or `None` if more than `k`
+    ///   would have to go
+    pub fn dampener_removals(&self, levels: &[i32], k: usize) -> Option<Vec<usize>> {
+        if levels.is_empty() {
+            return Some(Vec::new());
+        }
+
+        [true, false]
+            .into_iter()
+            .filter_map(|increasing| self.removals_in_direction(levels, k, increasing))
+            .min_by_key(Vec::len)
+    }
+
+    /// Classifies a report with a dampener that may remove up to `k` levels
+    ///
+    /// # Returns
+    /// * The classification, and the index of the removed level if the dampener
+    ///   removed exactly one
+    pub fn classify_k(&self, levels: &[i32], k: usize) -> (Classification, Option<usize>) {
+        match self.dampener_removals(levels, k) {
+            Some(removed) if removed.is_empty() => (Classification::Safe, None),
+            Some(removed) => (Classification::Dampened, (removed.len() == 1).then(|| removed[0])),
+            None => (Classification::Unsafe, None),
+        }
+    }
+}
+
+/// Checks a report against the puzzle's safety rules, see `SafetyRules::check`
+pub fn check_report(levels: &[i32]) -> Verdict {
+    SafetyRules::default().check(levels)
+}
+
+/// Validates if a sequence of levels forms a safe report
+/// 
+/// # Arguments
+/// * `levels` - A slice of integers representing the levels in a report
+///
+/// # Returns
+/// * `true` if `check_report` finds no broken rule
+/// * `false` otherwise
+pub fn is_safe_report(levels: &[i32]) -> bool {
+    check_report(levels).is_safe()
+}
+
+/// Classifies a report under the puzzle's rules, see `SafetyRules::classify`
+///
+/// # Returns
+/// * The classification, and the index of the removed level if the dampener was needed
+pub fn classify_report(levels: &[i32]) -> (Classification, Option<usize>) {
+    SafetyRules::default().classify(levels)
+}
+
+/// Classifies a report like `classify_report`, trying the removal of every
+/// level in turn. O(n^2), kept to test and benchmark the linear version against
+///
+/// # Returns
+/// * The classification, and the index of the removed level if the dampener was needed
+pub fn classify_report_brute_force(levels: &[i32]) -> (Classification, Option<usize>) {
+    if is_safe_report(levels) {
+        trace!(?levels, "safe without dampener");
+        return (Classification::Safe, None);
+    }
+
+    // if not safe, see if removing one level can make it safe
+    if levels.len() > 2 {
+        // Preallocate vector with capacity
+        let mut modified_levels = Vec::with_capacity(levels.len() - 1);
+        for i in 0..levels.len() {
+            modified_levels.clear();
+            modified_levels.extend(levels[..i].iter().chain(levels[i + 1..].iter()));
+
+            if is_safe_report(&modified_levels) {
+                trace!(?levels, removed = i, "safe with dampener");
+                return (Classification::Dampened, Some(i));
+            }
+        }
+    }
+
+    (Classification::Unsafe, None)
+}
+
+/// Finds the fewest levels to remove to make a report safe under the
+/// puzzle's rules, see `SafetyRules::dampener_removals`
+pub fn dampener_removals(levels: &[i32], k: usize) -> Option<Vec<usize>> {
+    SafetyRules::default().dampener_removals(levels, k)
+}
+
+/// Validates if a report is safe once the dampener removes up to `k` levels
+///
+/// # Arguments
+/// * `levels` - A slice of integers representing the levels in a report
+/// * `k` - The most levels the dampener may remove
+///
+/// # Returns
+/// * `true` if removing at most `k` levels leaves a safe report
+pub fn is_safe_with_dampener_k(levels: &[i32], k: usize) -> bool {
+    dampener_removals(levels, k).is_some()
+}
+
+/// Classifies a report with a dampener that may remove up to `k` levels,
+/// under the puzzle's rules, see `SafetyRules::classify_k`
+pub fn classify_report_k(levels: &[i32], k: usize) -> (Classification, Option<usize>) {
+    SafetyRules::default().classify_k(levels, k)
+}
+
+/// Classifies many reports at once, spread over all cores
+///
+/// # Arguments
+/// * `reports` - The levels of each report
+/// * `rules` - The rules the reports are judged by
+/// * `k` - The most levels the dampener may remove
+///
+/// # Returns
+/// * The classification of each report, in order, with the index of the
+///   removed level where the dampener removed exactly one
+pub fn classify_reports(
+    reports: &[Vec<i32>],
+    rules: &SafetyRules,
+    k: usize,
+) -> Vec<(Classification, Option<usize>)> {
+    reports
+        .par_iter()
+        .map(|levels| match k {
+            1 => rules.classify(levels),
+            k => rules.classify_k(levels, k),
+        })
+        .collect()
+}
+
+/// Solver for the reactor safety reports
+pub struct Day02;
+
+impl Solver for Day02 {
+    /// The reports, each a list of levels
+    type Parsed = Vec<Vec<i32>>;
+
+    /// Reads one report per line, each a list of space-separated levels
+    fn parse(input: &str) -> Result<Self::Parsed, AppError> {
+        input
+            .lines()
+            .map(|line| {
+                line.split_whitespace()
+                    .map(|level| level.parse().map_err(AppError::from))
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Counts the reports that are safe without the dampener
+    fn part1(reports: &Self::Parsed) -> Result<String, AppError> {
+        let count = reports.par_iter().filter(|levels| is_safe_report(levels)).count();
+        Ok(count.to_string())
+    }
+
+    /// Counts the reports that are safe, with the dampener removing at most one level
+    fn part2(reports: &Self::Parsed) -> Result<String, AppError> {
+        let count = reports
+            .par_iter()
+            .filter(|levels| classify_report(levels).0 != Classification::Unsafe)
+            .count();
+        Ok(count.to_string())
+    }
+
+    /// One line per report saying whether it passed, and for part 2 which
+    /// level the dampener removed
+    fn explain(reports: &Self::Parsed, part: u32) -> Result<Vec<String>, AppError> {
+        let rules = SafetyRules::default();
+        Ok((1..)
+            .zip(reports)
+            .map(|(line, levels)| match part {
+                1 => explain::explain_verdict(&rules, line, levels),
+                _ => {
+                    let (classification, removed_index) = rules.classify(levels);
+                    explain::explain_report(&rules, line, levels, classification, removed_index)
+                }
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<(), AppError> {
+        let reports = Day02::parse(&std::fs::read_to_string("data/inputtest.txt")?)?;
+        assert_eq!(Day02::part1(&reports)?, "2");
+        assert_eq!(Day02::part2(&reports)?, "4");
+
+        let explanation = Day02::explain(&reports, 2)?;
+        assert_eq!(explanation.len(), 6);
+        assert_eq!(explanation[0], "Line 1: 7 6 4 2 1 - safe");
+        assert!(explanation[3].ends_with("removing level 1 (3) fixes it"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_classify_report() {
+        assert_eq!(classify_report(&[7, 6, 4, 2, 1]), (Classification::Safe, None));
+        assert_eq!(classify_report(&[1, 3, 2, 4, 5]), (Classification::Dampened, Some(1)));
+        assert_eq!(classify_report(&[1, 2, 7, 8, 9]), (Classification::Unsafe, None));
+    }
+
+    /// Tries every way of removing up to `k` levels
+    fn brute_force_k(levels: &[i32], k: usize) -> bool {
+        is_safe_report(levels)
+            || (k > 0
+                && (0..levels.len()).any(|i| {
+                    let rest: Vec<i32> = levels[..i].iter().chain(&levels[i + 1..]).copied().collect();
+                    brute_force_k(&rest, k - 1)
+                }))
+    }
+
+    #[test]
+    fn test_dampener_k_matches_brute_force() {
+        // Small pseudo-random reports from a linear congruential generator
+        let mut seed: u32 = 12345;
+        let mut next = || {
+            seed = seed.wrapping_mul(1_103_515_245).wrapping_add(12_345);
+            (seed >> 16) as i32 % 7
+        };
+        for _ in 0..500 {
+            let len = next() as usize + 1;
+            let mut levels = vec![10];
+            for _ in 1..len {
+                let last = levels[levels.len() - 1];
+                levels.push(last + next() - 2);
+            }
+            for k in 0..=3 {
+                assert_eq!(is_safe_with_dampener_k(&levels, k), brute_force_k(&levels, k), "{:?}, k = {}", levels, k);
+                if let Some(removed) = dampener_removals(&levels, k) {
+                    let rest: Vec<i32> = (0..levels.len()).filter(|i| !removed.contains(i)).map(|i| levels[i]).collect();
+                    assert!(removed.len() <= k && is_safe_report(&rest));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_classify_report_matches_brute_force() -> Result<(), AppError> {
+        let reports = Day02::parse(&std::fs::read_to_string("data/input.txt")?)?;
+        let extra = [
+            vec![5, 1, 2, 3],
+            vec![1, 5, 4, 3],
+            vec![3, 1, 2, 3, 4],
+            vec![1, 1],
+            vec![1, 1, 1],
+            vec![4, 4, 5, 6],
+        ];
+        for levels in reports.iter().chain(&extra) {
+            assert_eq!(classify_report(levels), classify_report_brute_force(levels), "{:?}", levels);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_report() {
+        assert_eq!(check_report(&[7, 6, 4, 2, 1]), Verdict::Safe);
+        assert_eq!(check_report(&[1, 2, 7, 8, 9]), Verdict::DiffOutOfRange { index: 1, diff: 5 });
+        assert_eq!(check_report(&[1, 3, 2, 4, 5]), Verdict::DirectionChange { index: 1, diff: -1 });
+        assert_eq!(check_report(&[8, 6, 4, 4, 1]), Verdict::DiffOutOfRange { index: 2, diff: 0 });
+    }
+
+    #[test]
+    fn test_custom_rules() {
+        let rules = SafetyRules { min_diff: 1, max_diff: 5, strict: false };
+        assert_eq!(rules.check(&[1, 2, 7, 8, 9]), Verdict::Safe);
+        assert_eq!(rules.check(&[8, 6, 4, 4, 1]), Verdict::Safe);
+        assert_eq!(rules.check(&[4, 4, 5, 4]), Verdict::DirectionChange { index: 2, diff: -1 });
+        assert_eq!(rules.check(&[1, 2, 9]), Verdict::DiffOutOfRange { index: 1, diff: 7 });
+        assert_eq!(rules.classify(&[4, 4, 5, 4]), (Classification::Dampened, Some(2)));
+        assert_eq!(rules.classify_k(&[1, 9, 2, 9, 3], 2), (Classification::Dampened, None));
+
+        let wide_steps = SafetyRules { min_diff: 2, max_diff: 3, strict: true };
+        assert_eq!(wide_steps.check(&[1, 3, 4]), Verdict::DiffOutOfRange { index: 1, diff: 1 });
+        assert_eq!(wide_steps.classify(&[1, 3, 4, 6]), (Classification::Dampened, Some(1)));
+    }
+
+    #[test]
+    fn test_classify_reports() -> Result<(), AppError> {
+        let reports = Day02::parse(&std::fs::read_to_string("data/input.txt")?)?;
+        let rules = SafetyRules::default();
+        for k in [1, 2] {
+            let expected: Vec<_> = reports.iter().map(|levels| rules.classify_k(levels, k).0).collect();
+            let classified = classify_reports(&reports, &rules, k);
+            assert_eq!(classified.iter().map(|&(classification, _)| classification).collect::<Vec<_>>(), expected);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_classify_report_k() {
+        assert_eq!(classify_report_k(&[1, 3, 2, 4, 5], 1), (Classification::Dampened, Some(1)));
+        assert_eq!(classify_report_k(&[1, 2, 7, 8, 9], 1), (Classification::Unsafe, None));
+        assert_eq!(classify_report_k(&[1, 2, 7, 8, 3], 2), (Classification::Dampened, None));
+        assert!(is_safe_with_dampener_k(&[1, 2, 7, 8, 9], 2));
+    }
+}
@@ -1,16 +1,18 @@
-//! A program that processes a set of numbers from stdin, each line is a report.
+//! A program that processes a set of numbers from a file or stdin, each line is a report.
 //! Each report contains a list of space-separated numbers called "levels".
 //! 
 //! A report is considered "safe" if:
 //! 1. The levels are strictly monotonic (either all increasing or all decreasing)
 //! 2. The difference between any two adjacent levels is between 1 and 3 (inclusive)
-//! 3. OR if removing exactly one level makes the report satisfy conditions 1 and 2
+//! 3. OR if removing exactly one level (or up to K, with `--dampener-k`) makes the report
+//!    satisfy conditions 1 and 2
 //!
 //! # Running the Program
 //! From the project root directory, you can run the program in several ways:
 //!
-//! 1. Using input.txt:
+//! 1. Using input.txt, given as a path or on stdin:
 //! ```bash
+//! cargo run -- data/input.txt
 //! cargo run < data/input.txt
 //! ```
 //!
@@ -25,90 +27,110 @@
 //! 1 3 6 7 9
 //! <Ctrl+D>
 //! ```
+//!
+//! 3. Plotting each report as a sparkline coloured by classification, in the terminal
+//!    or as an SVG file:
+//! ```bash
+//! cargo run -- --sparklines < data/input.txt
+//! cargo run -- --sparklines-svg reports.svg < data/input.txt
+//! ```
+//!
+//! 4. Writing the report classifications to a Parquet file (requires the `parquet` feature):
+//! ```bash
+//! cargo run --features parquet -- --emit-parquet reports.parquet < data/input.txt
+//! ```
+//!
+//! 5. Printing the answers as a JSON object instead:
+//! ```bash
+//! cargo run -- --format json < data/input.txt
+//! ```
+//!
+//! 6. Printing only one part's answer, the strictly safe count (1) or the
+//!    count with the dampener (2):
+//! ```bash
+//! cargo run -- --part 1 < data/input.txt
+//! ```
+//!
+//! 7. Letting the dampener remove up to K levels instead of one:
+//! ```bash
+//! cargo run -- --dampener-k 2 < data/input.txt
+//! ```
+//!
+//! 8. Explaining each report's classification: the first rule it breaks and
+//!    the level the dampener removed:
+//! ```bash
+//! cargo run -- --explain < data/input.txt
+//! ```
+//!
+//! 9. Judging the reports by other rules: a different range of allowed
+//!    differences between adjacent levels, and allowing equal neighbours:
+//! ```bash
+//! cargo run -- --min-diff 1 --max-diff 5 --allow-equal < data/input.txt
+//! ```
+//!
+//! 10. Listing the line numbers and levels of the reports that stay unsafe
+//!     even with the dampener:
+//! ```bash
+//! cargo run -- --list-unsafe < data/input.txt
+//! ```
+//!
+//! 11. Summarising the reports: how many are safe with and without the
+//!     dampener, how often each difference between adjacent levels occurs
+//!     and which report is longest:
+//! ```bash
+//! cargo run -- --stats < data/input.txt
+//! ```
 
-use std::io::{self};
+use std::io::BufRead;
 use std::error::Error;
+use std::time::{Duration, Instant};
 
-/// Custom error type for the application
-#[derive(Debug)]
-enum AppError {
-    IoError(io::Error),
-    ParseError(std::num::ParseIntError),
-}
-
-impl From<io::Error> for AppError {
-    fn from(error: io::Error) -> Self {
-        Self::IoError(error)
-    }
-}
-
-impl From<std::num::ParseIntError> for AppError {
-    fn from(error: std::num::ParseIntError) -> Self {
-        Self::ParseError(error)
-    }
-}
+use aoc_common::input::InputSource;
+use aoc_common::logging;
+use aoc_common::messages;
+use aoc_common::msg;
+use aoc_common::output::{take_format, Format, Report};
+use aoc_common::AppError;
+use tracing::trace;
+#[cfg(feature = "parquet")]
+use day_02::export;
+use day_02::stats::ReportStats;
+use day_02::{classify_reports, explain, sparkline, Classification, SafetyRules};
 
-impl std::error::Error for AppError {}
+/// How many reports are read and validated together
+const CHUNK_LINES: usize = 4096;
 
-impl std::fmt::Display for AppError {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Self::IoError(e) => write!(f, "IO error: {}", e),
-            Self::ParseError(e) => write!(f, "Parse error: {}", e),
-        }
-    }
-}
-
-/// Validates if a sequence of levels forms a safe report
-/// 
-/// # Arguments
-/// * `levels` - A slice of integers representing the levels in a report
+/// Reads up to `CHUNK_LINES` reports
 ///
 /// # Returns
-/// * `true` if:
-///   - All numbers are strictly increasing or strictly decreasing
-///   - Each adjacent pair differs by 1, 2, or 3
-/// * `false` otherwise
-fn is_safe_report(levels: &[i32]) -> bool {
-    if levels.len() < 2 {
-        return true;
-    }
-
-    let mut prev = levels[0];
-    let first_diff = levels[1] - prev;
-    let is_increasing = first_diff > 0;
-
-    for &current in &levels[1..] {
-        let diff = current - prev;
-        let diff_abs = diff.abs();
-        
-        // if two adjacent levels are the same or
-        // differ more than 3, report is unsafe
-        if diff_abs < 1 || diff_abs > 3 {
-            return false;
-        }
-        
-        // If direction changes, report is unsafe
-        if (diff > 0) != is_increasing {
-            return false;
-        }
-        
-        prev = current;
+/// * The levels of each report read, empty once the input is exhausted, or
+///   an error if the input can't be read or a level isn't a number
+fn read_chunk(input: &mut impl BufRead) -> Result<Vec<Vec<i32>>, Box<dyn Error>> {
+    let mut chunk = Vec::with_capacity(CHUNK_LINES);
+    let mut buffer = String::new();
+    while chunk.len() < CHUNK_LINES && input.read_line(&mut buffer)? > 0 {
+        let levels: Vec<i32> = buffer
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<_, _>>()?;
+        trace!("Read levels: {:?}", levels);
+        chunk.push(levels);
+        buffer.clear();
     }
-
-    true
+    Ok(chunk)
 }
 
-/// Processes reports from standard input and counts how many are "safe"
+/// Processes reports from the input file, or standard input without one, and counts how many are "safe"
 ///
 /// A report is considered safe if:
-/// - It's safe according to `is_safe_report`, or
-/// - Removing exactly one level makes it safe
+/// - It's safe according to the `SafetyRules`, the puzzle's unless overridden, or
+/// - Removing exactly one level, or up to `--dampener-k` levels, makes it safe
 ///
 /// # Error Handling
 /// Returns an error if:
 /// - There's an IO error while reading input
 /// - Any number in the input cannot be parsed as an integer
+/// - An unknown argument is given, or `--emit-parquet` is used without the `parquet` feature
 ///
 /// # Example Input Format
 /// ```text
@@ -120,45 +142,174 @@ fn is_safe_report(levels: &[i32]) -> bool {
 /// 1 3 6 7 9    # Safe: strictly increasing, differences ≤ 3
 /// ```
 fn main() -> Result<(), Box<dyn Error>> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let format = take_format(&mut args)?;
+    logging::init(logging::take_verbosity(&mut args));
+    messages::init(messages::take_lang(&mut args)?.as_deref())?;
+    let mut args = args.into_iter().peekable();
+    let source = InputSource::from_arg(args.next_if(|arg| !arg.starts_with("--")).as_deref());
+    let mut parquet_path = None;
+    let mut sparklines = false;
+    let mut explain = false;
+    let mut list_unsafe = false;
+    let mut show_stats = false;
+    let mut svg_path = None;
+    let (mut part1, mut part2) = (true, true);
+    let mut dampener_k = 1;
+    let mut rules = SafetyRules::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--emit-parquet" => {
+                parquet_path = Some(
+                    args.next()
+                        .ok_or(AppError::ArgError("--emit-parquet requires a path"))?,
+                )
+            }
+            "--sparklines" => sparklines = true,
+            "--explain" => explain = true,
+            "--list-unsafe" => list_unsafe = true,
+            "--stats" => show_stats = true,
+            "--min-diff" => {
+                let value = args.next().ok_or(AppError::ArgError("--min-diff requires a number"))?;
+                rules.min_diff = value.parse()?;
+            }
+            "--max-diff" => {
+                let value = args.next().ok_or(AppError::ArgError("--max-diff requires a number"))?;
+                rules.max_diff = value.parse()?;
+            }
+            "--allow-equal" => rules.strict = false,
+            "--dampener-k" => {
+                let value = args.next().ok_or(AppError::ArgError("--dampener-k requires a number"))?;
+                dampener_k = value.parse()?;
+            }
+            "--part" => {
+                let value = args.next().ok_or(AppError::ArgError("--part requires 1, 2 or both"))?;
+                (part1, part2) = match value.as_str() {
+                    "1" => (true, false),
+                    "2" => (false, true),
+                    "both" => (true, true),
+                    _ => return Err(Box::new(AppError::ArgError("--part must be 1, 2 or both"))),
+                };
+            }
+            "--sparklines-svg" => {
+                svg_path = Some(
+                    args.next()
+                        .ok_or(AppError::ArgError("--sparklines-svg requires a path"))?,
+                )
+            }
+            _ => {
+                return Err(Box::new(AppError::ArgError(
+                    "Usage: day_02 [<input>] [--part <1|2|both>] [--dampener-k <k>] [--explain] [--list-unsafe] [--stats] [--min-diff <n>] [--max-diff <n>] [--allow-equal] [--sparklines] [--sparklines-svg <path>] [--emit-parquet <path>] [--format <text|json>] [-v|-vv]",
+                )))
+            }
+        }
+    }
+    if rules.min_diff < 1 || rules.min_diff > rules.max_diff {
+        return Err(Box::new(AppError::ArgError("--min-diff must be at least 1 and at most --max-diff")));
+    }
+    if show_stats && format == Format::Json {
+        return Err(Box::new(AppError::ArgError("--stats can't be combined with --format json")));
+    }
+    if parquet_path.is_some() && cfg!(not(feature = "parquet")) {
+        return Err(Box::new(AppError::ArgError(
+            "--emit-parquet requires building with --features parquet",
+        )));
+    }
+
     let mut safe_count = 0;
-    let stdin = io::stdin();
-    let mut buffer = String::new();
+    let mut strictly_safe_count = 0;
+    let mut elapsed = Duration::ZERO;
+    let mut input = source.open()?;
+    #[cfg(feature = "parquet")]
+    let mut records = Vec::new();
+    let mut plotted_reports = Vec::new();
+    let mut line = 0;
+    let mut stats = ReportStats::default();
 
-    // Read and validate reports line by line, each report has one or more levels
-    while stdin.read_line(&mut buffer)? > 0 {
-        let levels: Vec<i32> = buffer
-            .split_whitespace()
-            .map(str::parse)
-            .collect::<Result<_, _>>()?;
-        
-        #[cfg(debug_assertions)]
-        println!("Read levels: {:?}", levels);
-
-        if is_safe_report(&levels) {
-            #[cfg(debug_assertions)]
-            println!("safe without dampener");
-            safe_count += 1;
-        // if not safe, see if removing one level can make it safe
-        } else if levels.len() > 2 {
-            // Preallocate vector with capacity
-            let mut modified_levels = Vec::with_capacity(levels.len() - 1);
-            for i in 0..levels.len() {
-                modified_levels.clear();
-                modified_levels.extend(levels[..i].iter().chain(levels[i + 1..].iter()));
-                
-                if is_safe_report(&modified_levels) {
-                    #[cfg(debug_assertions)]
-                    println!("safe with dampener");
-                    safe_count += 1;
-                    break;
-                }
+    // Read reports a chunk of lines at a time, each report has one or more
+    // levels, and validate each chunk in parallel
+    loop {
+        let chunk = read_chunk(&mut input)?;
+        if chunk.is_empty() {
+            break;
+        }
+
+        let start = Instant::now();
+        let classified = classify_reports(&chunk, &rules, dampener_k);
+        elapsed += start.elapsed();
+
+        for (levels, (classification, removed_index)) in chunk.into_iter().zip(classified) {
+            line += 1;
+            if classification != Classification::Unsafe {
+                safe_count += 1;
+            }
+            if classification == Classification::Safe {
+                strictly_safe_count += 1;
+            }
+
+            if show_stats {
+                stats.add(line, &levels, classification);
+            }
+            if explain && format == Format::Text {
+                println!("{}", explain::explain_report(&rules, line, &levels, classification, removed_index));
+            }
+            if list_unsafe && classification == Classification::Unsafe && format == Format::Text {
+                let numbers: Vec<String> = levels.iter().map(i32::to_string).collect();
+                println!("{}", msg!("day02-unsafe-line", line, numbers.join(" ")));
+            }
+            if sparklines && format == Format::Text {
+                println!("{}", sparkline::terminal_line(&levels, classification));
+            }
+            if svg_path.is_some() {
+                plotted_reports.push((levels.clone(), classification));
+            }
+
+            #[cfg(feature = "parquet")]
+            if parquet_path.is_some() {
+                records.push(export::ReportRecord {
+                    line,
+                    levels,
+                    classification,
+                    removed_index,
+                });
             }
         }
-        
-        buffer.clear();
     }
 
-    println!("Number of safe reports: {}", safe_count);
+    match format {
+        Format::Text => {
+            if part1 {
+                println!("{}", msg!("day02-part1", strictly_safe_count));
+            }
+            if part2 {
+                println!("{}", msg!("day02-part2", safe_count));
+            }
+        }
+        Format::Json => {
+            let report = Report {
+                day: 2,
+                part1: part1.then(|| strictly_safe_count.to_string()),
+                part2: part2.then(|| safe_count.to_string()),
+                elapsed,
+            };
+            println!("{}", report.to_json());
+        }
+    }
+
+    if show_stats {
+        print!("{}", stats.render());
+    }
+
+    if let Some(path) = svg_path {
+        std::fs::write(&path, sparkline::render_svg(&plotted_reports))?;
+        eprintln!("{}", msg!("day02-wrote-svg", plotted_reports.len(), path));
+    }
+
+    #[cfg(feature = "parquet")]
+    if let Some(path) = parquet_path {
+        export::write_reports_parquet(&path, &records)?;
+        eprintln!("{}", msg!("day02-wrote-parquet", records.len(), path));
+    }
 
     Ok(())
 }
@@ -0,0 +1,146 @@
+//! Tiny per-report plots of the levels, coloured by classification.
+
+use std::fmt::Write;
+
+use crate::Classification;
+
+/// Unicode block characters from lowest to highest
+const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+/// Horizontal distance between two levels in the SVG plot
+const SVG_STEP: usize = 8;
+/// Height of one report row in the SVG plot
+const SVG_ROW_HEIGHT: usize = 24;
+
+/// ANSI colour code used for a classification in the terminal
+fn ansi_color(classification: Classification) -> &'static str {
+    match classification {
+        Classification::Safe => "32",
+        Classification::Dampened => "33",
+        Classification::Unsafe => "31",
+    }
+}
+
+/// Stroke colour used for a classification in the SVG plot
+fn svg_color(classification: Classification) -> &'static str {
+    match classification {
+        Classification::Safe => "#2ca02c",
+        Classification::Dampened => "#ff7f0e",
+        Classification::Unsafe => "#d62728",
+    }
+}
+
+/// Renders the levels as unicode blocks scaled between the report's own minimum and maximum
+///
+/// # Arguments
+/// * `levels` - A slice of integers representing the levels in a report
+///
+/// # Returns
+/// * One block character per level
+pub fn sparkline(levels: &[i32]) -> String {
+    let min = levels.iter().copied().min().unwrap_or(0);
+    let max = levels.iter().copied().max().unwrap_or(0);
+    let range = (max - min).max(1) as usize;
+
+    levels
+        .iter()
+        .map(|&level| BLOCKS[(level - min) as usize * (BLOCKS.len() - 1) / range])
+        .collect()
+}
+
+/// Renders a sparkline line for the terminal, coloured by classification
+///
+/// # Arguments
+/// * `levels` - A slice of integers representing the levels in a report
+/// * `classification` - How the report was judged
+///
+/// # Returns
+/// * The coloured sparkline followed by the classification and the raw levels
+pub fn terminal_line(levels: &[i32], classification: Classification) -> String {
+    let numbers: Vec<String> = levels.iter().map(i32::to_string).collect();
+    format!(
+        "\x1b[{}m{:<8}\x1b[0m {:<8} {}",
+        ansi_color(classification),
+        sparkline(levels),
+        classification.as_str(),
+        numbers.join(" ")
+    )
+}
+
+/// Renders every report as a polyline in an SVG document, one row per report
+///
+/// # Arguments
+/// * `reports` - The levels of each report together with its classification
+///
+/// # Returns
+/// * The SVG document
+pub fn render_svg(reports: &[(Vec<i32>, Classification)]) -> String {
+    let longest = reports.iter().map(|(levels, _)| levels.len()).max().unwrap_or(0);
+    let width = longest.max(1) * SVG_STEP;
+    let height = reports.len().max(1) * SVG_ROW_HEIGHT;
+    let mut svg = String::new();
+
+    // Writing to a String cannot fail, so the fmt::Results below are ignored
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+
+    for (row, (levels, classification)) in reports.iter().enumerate() {
+        let min = levels.iter().copied().min().unwrap_or(0);
+        let max = levels.iter().copied().max().unwrap_or(0);
+        let range = (max - min).max(1) as usize;
+        let top = row * SVG_ROW_HEIGHT + 2;
+        let plot_height = SVG_ROW_HEIGHT - 4;
+
+        let points: Vec<String> = levels
+            .iter()
+            .enumerate()
+            .map(|(i, &level)| {
+                let x = i * SVG_STEP + SVG_STEP / 2;
+                let y = top + plot_height - (level - min) as usize * plot_height / range;
+                format!("{x},{y}")
+            })
+            .collect();
+        let _ = writeln!(
+            svg,
+            r#"<polyline points="{}" fill="none" stroke="{}" stroke-width="1.5"/>"#,
+            points.join(" "),
+            svg_color(*classification)
+        );
+    }
+
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sparkline() {
+        assert_eq!(sparkline(&[1, 3, 6, 7, 9]), "▁▂▅▆█");
+        assert_eq!(sparkline(&[4, 4, 4]), "▁▁▁");
+        assert_eq!(sparkline(&[]), "");
+    }
+
+    #[test]
+    fn test_terminal_line() {
+        let line = terminal_line(&[7, 6, 4, 2, 1], Classification::Safe);
+        assert_eq!(line, "\x1b[32m█▆▄▂▁   \x1b[0m safe     7 6 4 2 1");
+    }
+
+    #[test]
+    fn test_render_svg() {
+        let reports = vec![
+            (vec![7, 6, 4, 2, 1], Classification::Safe),
+            (vec![1, 3, 2, 4, 5], Classification::Dampened),
+            (vec![1, 2, 7, 8, 9], Classification::Unsafe),
+        ];
+        let svg = render_svg(&reports);
+        assert_eq!(svg.matches("<polyline").count(), 3);
+        assert!(svg.contains(r##"points="4,2 12,6 20,12 28,19 36,22" fill="none" stroke="#2ca02c""##));
+        assert!(svg.contains("#ff7f0e"));
+        assert!(svg.contains("#d62728"));
+    }
+}
@@ -0,0 +1,100 @@
+//! Summary statistics over all the reports, for `--stats`.
+
+use std::collections::BTreeMap;
+use std::fmt::Write;
+
+use crate::Classification;
+
+/// Totals gathered one report at a time
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReportStats {
+    /// The number of reports
+    pub reports: usize,
+    /// Reports safe without the dampener
+    pub strictly_safe: usize,
+    /// Reports safe only with the dampener
+    pub dampened: usize,
+    /// How often each absolute difference between adjacent levels occurs
+    pub differences: BTreeMap<u32, usize>,
+    /// The 1-based line and level count of the longest report, the first
+    /// one if several are equally long
+    pub longest: Option<(usize, usize)>,
+}
+
+impl ReportStats {
+    /// Adds one report to the totals
+    ///
+    /// # Arguments
+    /// * `line` - The 1-based line number of the report
+    /// * `levels` - A slice of integers representing the levels in a report
+    /// * `classification` - How the report was judged
+    pub fn add(&mut self, line: usize, levels: &[i32], classification: Classification) {
+        self.reports += 1;
+        match classification {
+            Classification::Safe => self.strictly_safe += 1,
+            Classification::Dampened => self.dampened += 1,
+            Classification::Unsafe => {}
+        }
+        for pair in levels.windows(2) {
+            *self.differences.entry(pair[0].abs_diff(pair[1])).or_default() += 1;
+        }
+        if self.longest.is_none_or(|(_, len)| levels.len() > len) {
+            self.longest = Some((line, levels.len()));
+        }
+    }
+
+    /// Renders the totals as lines of text
+    ///
+    /// # Returns
+    /// * The report counts with their percentages, one line per difference
+    ///   seen and the longest report
+    pub fn render(&self) -> String {
+        let percent = |count: usize| 100.0 * count as f64 / self.reports.max(1) as f64;
+        let mut text = String::new();
+
+        // Writing to a String cannot fail, so the fmt::Results below are ignored
+        let _ = writeln!(text, "Reports: {}", self.reports);
+        let _ = writeln!(text, "Safe without the dampener: {} ({:.1}%)", self.strictly_safe, percent(self.strictly_safe));
+        let _ = writeln!(text, "Safe only with the dampener: {} ({:.1}%)", self.dampened, percent(self.dampened));
+        let _ = writeln!(text, "Adjacent differences:");
+        for (difference, count) in &self.differences {
+            let _ = writeln!(text, "  {:>4}: {}", difference, count);
+        }
+        if let Some((line, len)) = self.longest {
+            let _ = writeln!(text, "Longest report: line {} with {} levels", line, len);
+        }
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::classify_report;
+
+    #[test]
+    fn test_report_stats() {
+        let reports = [
+            vec![7, 6, 4, 2, 1],
+            vec![1, 2, 7, 8, 9],
+            vec![9, 7, 6, 2, 1],
+            vec![1, 3, 2, 4, 5],
+            vec![8, 6, 4, 4, 1],
+            vec![1, 3, 6, 7, 9, 10],
+        ];
+        let mut stats = ReportStats::default();
+        for (i, levels) in reports.iter().enumerate() {
+            stats.add(i + 1, levels, classify_report(levels).0);
+        }
+
+        assert_eq!(stats.reports, 6);
+        assert_eq!(stats.strictly_safe, 2);
+        assert_eq!(stats.dampened, 2);
+        assert_eq!(stats.differences, BTreeMap::from([(0, 1), (1, 11), (2, 9), (3, 2), (4, 1), (5, 1)]));
+        assert_eq!(stats.longest, Some((6, 6)));
+
+        let text = stats.render();
+        assert!(text.contains("Safe without the dampener: 2 (33.3%)"));
+        assert!(text.contains("Longest report: line 6 with 6 levels"));
+    }
+}
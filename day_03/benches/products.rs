@@ -0,0 +1,28 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use day_03::calculations::{
+    calculate_products, calculate_products_do_dont, calculate_products_do_dont_fast, calculate_products_do_dont_with,
+    calculate_products_fast, calculate_products_with, Algorithm,
+};
+
+const INPUT: &str = include_str!("../data/input");
+
+fn bench_products(c: &mut Criterion) {
+    c.bench_function("calculate_products", |b| b.iter(|| calculate_products(black_box(INPUT))));
+    c.bench_function("calculate_products_do_dont", |b| {
+        b.iter(|| calculate_products_do_dont(black_box(INPUT)))
+    });
+    c.bench_function("calculate_products_fast", |b| b.iter(|| calculate_products_fast(black_box(INPUT))));
+    c.bench_function("calculate_products_do_dont_fast", |b| {
+        b.iter(|| calculate_products_do_dont_fast(black_box(INPUT)))
+    });
+    c.bench_function("calculate_products_memchr", |b| {
+        b.iter(|| calculate_products_with(black_box(INPUT), Algorithm::Memchr))
+    });
+    c.bench_function("calculate_products_do_dont_memchr", |b| {
+        b.iter(|| calculate_products_do_dont_with(black_box(INPUT), Algorithm::Memchr))
+    });
+}
+
+criterion_group!(benches, bench_products);
+criterion_main!(benches);
@@ -1,4 +1,8 @@
-use crate::errors::AppError;
+use aoc_common::AppError;
+use crate::scanner::{find_instructions, scan_instructions};
+use crate::vm::VmState;
+use std::fmt;
+use std::str::FromStr;
 use std::sync::LazyLock;
 
 // Regular expression to match multiplication expressions like mul(123,456)
@@ -17,17 +21,96 @@ static DO_DONT_RE: LazyLock<regex::Regex> =
 ///
 /// # Returns
 ///
-/// * `Result<i32, AppError>` - The total product or an error
-pub fn calculate_products(input: &str) -> Result<i32, AppError> {
-    let mut total = 0;
+/// * `Result<i64, AppError>` - The total product or an error
+pub fn calculate_products(input: &str) -> Result<i64, AppError> {
+    let mut state = VmState::default();
 
     for cap in PRODUCT_RE.captures_iter(input) {
-        let num1: i32 = cap[1].parse()?;
-        let num2: i32 = cap[2].parse()?;
-        total += num1 * num2;
+        state.apply(Instruction::Mul(cap[1].parse()?, cap[2].parse()?).into());
     }
 
-    Ok(total)
+    Ok(state.total)
+}
+
+/// A single instruction found in the corrupted memory
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    /// `do()` - enables future multiplications
+    Do,
+    /// `don't()` - disables future multiplications
+    Dont,
+    /// `mul(a,b)` - multiplies two numbers of 1-3 digits
+    Mul(i32, i32),
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Do => write!(f, "do()"),
+            Self::Dont => write!(f, "don't()"),
+            Self::Mul(num1, num2) => write!(f, "mul({},{})", num1, num2),
+        }
+    }
+}
+
+/// Lazily scans the input string for do(), don't() and mul(a,b) instructions
+///
+/// # Arguments
+///
+/// * `input` - A string slice that holds the input data
+///
+/// # Returns
+///
+/// * An iterator yielding the byte offset of each instruction together with the
+///   instruction, in input order, or an error if an operand cannot be parsed
+pub fn iter_instructions(
+    input: &str,
+) -> impl Iterator<Item = Result<(usize, Instruction), AppError>> + '_ {
+    DO_DONT_RE
+        .captures_iter(input)
+        .map(|cap| parse_capture(&cap).map(|(span, instruction)| (span.start, instruction)))
+}
+
+/// The byte range of an instruction in the input
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span {
+    /// Offset of the first byte
+    pub start: usize,
+    /// Offset just past the last byte
+    pub end: usize,
+}
+
+impl Span {
+    /// The text of the instruction within `input`
+    pub fn text<'a>(&self, input: &'a str) -> &'a str {
+        &input[self.start..self.end]
+    }
+}
+
+/// Converts a `DO_DONT_RE` match into the instruction and where it was found
+fn parse_capture(cap: &regex::Captures<'_>) -> Result<(Span, Instruction), AppError> {
+    let instruction = match &cap[1] {
+        "do()" => Instruction::Do,
+        "don't()" => Instruction::Dont,
+        _ => Instruction::Mul(cap[2].parse()?, cap[3].parse()?),
+    };
+    let whole = cap.get(0).map_or(0..0, |m| m.range());
+    Ok((Span { start: whole.start, end: whole.end }, instruction))
+}
+
+/// Scans the input string for do(), don't() and mul(a,b) instructions
+///
+/// # Arguments
+///
+/// * `input` - A string slice that holds the input data
+///
+/// # Returns
+///
+/// * `Result<Vec<(Span, Instruction)>, AppError>` - Each instruction with the
+///   bytes it was read from, in input order, or an error if an operand cannot
+///   be parsed
+pub fn parse_instructions(input: &str) -> Result<Vec<(Span, Instruction)>, AppError> {
+    DO_DONT_RE.captures_iter(input).map(|cap| parse_capture(&cap)).collect()
 }
 
 /// Calculates the total product of all multiplication expressions in the input string
@@ -39,26 +122,141 @@ pub fn calculate_products(input: &str) -> Result<i32, AppError> {
 ///
 /// # Returns
 ///
-/// * `Result<i32, AppError>` - The total product or an error
-pub fn calculate_products_do_dont(input: &str) -> Result<i32, AppError> {
-    let mut total = 0;
-    let mut should_add = true;
-
-    for cap in DO_DONT_RE.captures_iter(input) {
-        match &cap[1] {
-            "do()" => should_add = true,
-            "don't()" => should_add = false,
-            _ => {
-                if should_add {
-                    let num1: i32 = cap[2].parse()?;
-                    let num2: i32 = cap[3].parse()?;
-                    total += num1 * num2;
-                }
+/// * `Result<i64, AppError>` - The total product or an error
+pub fn calculate_products_do_dont(input: &str) -> Result<i64, AppError> {
+    calculate_products_do_dont_resuming(input, Algorithm::Regex, true).map(|(total, _)| total)
+}
+
+/// Sums the products of the `mul` instructions, skipping those disabled by
+/// a `don't()` if `respect_do` is set
+fn total_products(instructions: impl Iterator<Item = (usize, Instruction)>, respect_do: bool) -> i64 {
+    let instructions = instructions
+        .map(|(_, instruction)| instruction)
+        .filter(|instruction| respect_do || matches!(instruction, Instruction::Mul(..)))
+        .map(Into::into);
+    crate::vm::execute(instructions).total
+}
+
+/// Calculates the same total as `calculate_products` with the hand-rolled
+/// scanner instead of the regex engine
+///
+/// # Arguments
+///
+/// * `input` - A string slice that holds the input data
+///
+/// # Returns
+///
+/// * `i64` - The total product
+pub fn calculate_products_fast(input: &str) -> i64 {
+    total_products(scan_instructions(input.as_bytes()), false)
+}
+
+/// Calculates the same total as `calculate_products_do_dont` with the
+/// hand-rolled scanner instead of the regex engine
+///
+/// # Arguments
+///
+/// * `input` - A string slice that holds the input data
+///
+/// # Returns
+///
+/// * `i64` - The total product
+pub fn calculate_products_do_dont_fast(input: &str) -> i64 {
+    total_products(scan_instructions(input.as_bytes()), true)
+}
+
+/// How `calculate_products_with` and `calculate_products_do_dont_with` find
+/// the instructions
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Match the whole pattern with the regex engine
+    #[default]
+    Regex,
+    /// Feed every byte to the hand-rolled state machine
+    Scanner,
+    /// Jump to each `m` or `d` with `memchr`, then run the state machine
+    Memchr,
+}
+
+impl FromStr for Algorithm {
+    type Err = AppError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "regex" => Ok(Self::Regex),
+            "scanner" => Ok(Self::Scanner),
+            "memchr" => Ok(Self::Memchr),
+            _ => Err(AppError::ArgError("--algo must be regex, scanner or memchr")),
+        }
+    }
+}
+
+/// Calculates the total product of all multiplication expressions in the
+/// input string using the chosen algorithm
+///
+/// # Arguments
+///
+/// * `input` - A string slice that holds the input data
+/// * `algorithm` - How the instructions are found
+///
+/// # Returns
+///
+/// * `Result<i64, AppError>` - The total product or an error
+pub fn calculate_products_with(input: &str, algorithm: Algorithm) -> Result<i64, AppError> {
+    match algorithm {
+        Algorithm::Regex => calculate_products(input),
+        Algorithm::Scanner => Ok(calculate_products_fast(input)),
+        Algorithm::Memchr => Ok(total_products(find_instructions(input.as_bytes()), false)),
+    }
+}
+
+/// Calculates the total product of the enabled multiplication expressions in
+/// the input string using the chosen algorithm
+///
+/// # Arguments
+///
+/// * `input` - A string slice that holds the input data
+/// * `algorithm` - How the instructions are found
+///
+/// # Returns
+///
+/// * `Result<i64, AppError>` - The total product or an error
+pub fn calculate_products_do_dont_with(input: &str, algorithm: Algorithm) -> Result<i64, AppError> {
+    calculate_products_do_dont_resuming(input, algorithm, true).map(|(total, _)| total)
+}
+
+/// Calculates the total product of the enabled multiplication expressions in
+/// the input string, starting from the enabled state left by an earlier input
+///
+/// # Arguments
+///
+/// * `input` - A string slice that holds the input data
+/// * `algorithm` - How the instructions are found
+/// * `enabled` - Whether multiplications are enabled at the start of the input
+///
+/// # Returns
+///
+/// * `Result<(i64, bool), AppError>` - The total product and whether
+///   multiplications are still enabled at the end of the input, or an error
+pub fn calculate_products_do_dont_resuming(
+    input: &str,
+    algorithm: Algorithm,
+    enabled: bool,
+) -> Result<(i64, bool), AppError> {
+    let mut state = VmState { enabled, ..VmState::default() };
+
+    match algorithm {
+        Algorithm::Regex => {
+            for item in iter_instructions(input) {
+                let (_, instruction) = item?;
+                state.apply(instruction.into());
             }
         }
+        Algorithm::Scanner => scan_instructions(input.as_bytes()).for_each(|(_, i)| state.apply(i.into())),
+        Algorithm::Memchr => find_instructions(input.as_bytes()).for_each(|(_, i)| state.apply(i.into())),
     }
 
-    Ok(total)
+    Ok((state.total, state.enabled))
 }
 
 #[cfg(test)]
@@ -76,6 +274,25 @@ mod tests {
         Ok(())
     }
 
+    /// Tests the iter_instructions function
+    #[test]
+    fn test_iter_instructions() -> Result<(), Box<dyn Error>> {
+        let input = read_file_to_string("data/inputtest")?;
+        let instructions = iter_instructions(&input).collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            instructions,
+            vec![
+                (1, Instruction::Mul(2, 4)),
+                (20, Instruction::Dont),
+                (28, Instruction::Mul(5, 5)),
+                (48, Instruction::Mul(11, 8)),
+                (59, Instruction::Do),
+                (64, Instruction::Mul(8, 5)),
+            ]
+        );
+        Ok(())
+    }
+
     /// Tests the calculate_products_do_dont function
     #[test]
     fn test_calculate_products_do_dont() -> Result<(), Box<dyn Error>> {
@@ -84,4 +301,72 @@ mod tests {
         assert_eq!(total, 48, "Expected total to be 48, got {}", total);
         Ok(())
     }
+
+    /// Tests that the hand-rolled scanner gives the same totals as the regex
+    #[test]
+    fn test_fast_matches_regex() -> Result<(), Box<dyn Error>> {
+        let input = read_file_to_string("data/inputtest")?;
+        assert_eq!(calculate_products_fast(&input), calculate_products(&input)?);
+        assert_eq!(calculate_products_do_dont_fast(&input), calculate_products_do_dont(&input)?);
+        Ok(())
+    }
+
+    /// Tests that every algorithm gives the example's totals
+    #[test]
+    fn test_algorithms() -> Result<(), Box<dyn Error>> {
+        let input = read_file_to_string("data/inputtest")?;
+        for algorithm in ["regex", "scanner", "memchr"] {
+            let algorithm = algorithm.parse()?;
+            assert_eq!(calculate_products_with(&input, algorithm)?, 161);
+            assert_eq!(calculate_products_do_dont_with(&input, algorithm)?, 48);
+        }
+        assert!("simd".parse::<Algorithm>().is_err());
+        Ok(())
+    }
+
+    /// Tests the parse_instructions function
+    #[test]
+    fn test_parse_instructions() -> Result<(), Box<dyn Error>> {
+        let input = read_file_to_string("data/inputtest")?;
+        let instructions = parse_instructions(&input)?;
+        let offsets: Vec<_> = iter_instructions(&input)
+            .map(|item| item.map(|(offset, _)| offset))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(instructions.iter().map(|(span, _)| span.start).collect::<Vec<_>>(), offsets);
+        for (span, instruction) in &instructions {
+            assert_eq!(span.text(&input), instruction.to_string());
+        }
+        assert_eq!(instructions[1], (Span { start: 20, end: 27 }, Instruction::Dont));
+        Ok(())
+    }
+
+    /// Tests that the enabled state carries from one input into the next
+    #[test]
+    fn test_resuming() -> Result<(), Box<dyn Error>> {
+        let input = read_file_to_string("data/inputtest")?;
+        for algorithm in [Algorithm::Regex, Algorithm::Scanner, Algorithm::Memchr] {
+            assert_eq!(calculate_products_do_dont_resuming(&input, algorithm, false)?, (40, true));
+            assert_eq!(calculate_products_do_dont_resuming("mul(2,3)don't()", algorithm, true)?, (6, false));
+            assert_eq!(calculate_products_do_dont_resuming("mul(2,3)", algorithm, false)?, (0, false));
+        }
+        Ok(())
+    }
+
+    /// Tests totals just past `i32::MAX`, where 2152 products of 999 * 999
+    /// land
+    #[test]
+    fn test_totals_past_i32() -> Result<(), Box<dyn Error>> {
+        let expected = 2152 * 998_001;
+        assert!(expected > i64::from(i32::MAX));
+
+        let input = "mul(999,999)".repeat(2152);
+        assert_eq!(calculate_products(&input)?, expected);
+        assert_eq!(calculate_products_do_dont(&input)?, expected);
+        for algorithm in [Algorithm::Regex, Algorithm::Scanner, Algorithm::Memchr] {
+            assert_eq!(calculate_products_with(&input, algorithm)?, expected);
+            assert_eq!(calculate_products_do_dont_with(&input, algorithm)?, expected);
+        }
+        assert_eq!(crate::stream::calculate_products_from_reader(input.as_bytes())?, expected);
+        Ok(())
+    }
 }
@@ -0,0 +1,85 @@
+//! Parquet export of the scanned instructions (requires the `parquet` feature).
+
+use std::error::Error;
+use std::fs::File;
+use std::sync::Arc;
+
+use arrow_array::{BooleanArray, Int32Array, RecordBatch, StringArray, UInt64Array};
+use arrow_schema::{DataType, Field, Schema};
+use parquet::arrow::ArrowWriter;
+
+use crate::calculations::Instruction;
+
+/// Writes one row per scanned instruction to a Parquet file
+///
+/// # Columns
+/// * `offset` - Byte offset of the instruction in the input
+/// * `instruction` - `do`, `don't` or `mul`
+/// * `a`, `b` - The operands of a `mul`, null for `do`/`don't`
+/// * `enabled` - Whether multiplications were enabled after this instruction
+/// * `product` - `a * b` for an enabled `mul`, 0 for a disabled one, null otherwise
+pub fn write_instructions_parquet(
+    path: &str,
+    instructions: &[(usize, Instruction)],
+) -> Result<(), Box<dyn Error>> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("offset", DataType::UInt64, false),
+        Field::new("instruction", DataType::Utf8, false),
+        Field::new("a", DataType::Int32, true),
+        Field::new("b", DataType::Int32, true),
+        Field::new("enabled", DataType::Boolean, false),
+        Field::new("product", DataType::Int32, true),
+    ]));
+
+    let mut offsets = Vec::with_capacity(instructions.len());
+    let mut names = Vec::with_capacity(instructions.len());
+    let mut a = Vec::with_capacity(instructions.len());
+    let mut b = Vec::with_capacity(instructions.len());
+    let mut enabled = Vec::with_capacity(instructions.len());
+    let mut products = Vec::with_capacity(instructions.len());
+    let mut should_add = true;
+
+    for &(offset, instruction) in instructions {
+        offsets.push(offset as u64);
+        match instruction {
+            Instruction::Do => {
+                should_add = true;
+                names.push("do");
+                a.push(None);
+                b.push(None);
+                products.push(None);
+            }
+            Instruction::Dont => {
+                should_add = false;
+                names.push("don't");
+                a.push(None);
+                b.push(None);
+                products.push(None);
+            }
+            Instruction::Mul(num1, num2) => {
+                names.push("mul");
+                a.push(Some(num1));
+                b.push(Some(num2));
+                products.push(Some(if should_add { num1 * num2 } else { 0 }));
+            }
+        }
+        enabled.push(should_add);
+    }
+
+    let batch = RecordBatch::try_new(
+        schema.clone(),
+        vec![
+            Arc::new(UInt64Array::from(offsets)),
+            Arc::new(StringArray::from(names)),
+            Arc::new(Int32Array::from(a)),
+            Arc::new(Int32Array::from(b)),
+            Arc::new(BooleanArray::from(enabled)),
+            Arc::new(Int32Array::from(products)),
+        ],
+    )?;
+
+    let mut writer = ArrowWriter::try_new(File::create(path)?, schema, None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
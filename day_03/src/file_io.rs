@@ -1,5 +1,7 @@
 use std::error::Error;
 
+use tracing::debug;
+
 /// Reads the content of a file into a string
 ///
 /// # Arguments
@@ -11,6 +13,6 @@ use std::error::Error;
 /// * `Result<String, Box<dyn Error>>` - The file content or an error
 pub fn read_file_to_string(path: &str) -> Result<String, Box<dyn Error>> {
     let content = std::fs::read_to_string(path)?;
-    println!("Read {} bytes", content.len());
+    debug!("Read {} bytes", content.len());
     Ok(content)
 }
@@ -0,0 +1,52 @@
+//! Day 3: Mull It Over
+//!
+//! Library entry points shared by the binary and the `aoc` runner.
+
+pub mod calculations;
+#[cfg(feature = "parquet")]
+pub mod export;
+pub mod file_io;
+pub mod scanner;
+pub mod stepper;
+pub mod stream;
+pub mod syntax;
+pub mod vm;
+
+use aoc_common::{AppError, Solver};
+
+use calculations::{calculate_products, calculate_products_do_dont};
+
+/// Solver for the corrupted memory
+pub struct Day03;
+
+impl Solver for Day03 {
+    /// The corrupted memory, scanned as-is by both parts
+    type Parsed = String;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AppError> {
+        Ok(input.to_string())
+    }
+
+    /// Sums the products of every `mul` instruction
+    fn part1(memory: &Self::Parsed) -> Result<String, AppError> {
+        Ok(calculate_products(memory)?.to_string())
+    }
+
+    /// Sums the products of the `mul` instructions enabled by `do()`/`don't()`
+    fn part2(memory: &Self::Parsed) -> Result<String, AppError> {
+        Ok(calculate_products_do_dont(memory)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<(), AppError> {
+        let parsed = Day03::parse(&std::fs::read_to_string("data/inputtest")?)?;
+        assert_eq!(Day03::part1(&parsed)?, "161");
+        assert_eq!(Day03::part2(&parsed)?, "48");
+        Ok(())
+    }
+}
@@ -1,29 +1,156 @@
 use std::error::Error;
+use std::time::Instant;
 
-// Internal imports
-mod calculations;
-mod errors;
-mod file_io;
-
-use calculations::{calculate_products, calculate_products_do_dont};
-use errors::AppError;
-use file_io::read_file_to_string;
+use aoc_common::input::resolve_input;
+use aoc_common::logging;
+use aoc_common::messages;
+use aoc_common::msg;
+use aoc_common::output::{take_format, Format, Report};
+use aoc_common::AppError;
+use day_03::calculations::{calculate_products_do_dont_resuming, calculate_products_with, Algorithm};
+use day_03::file_io::read_file_to_string;
+use day_03::stepper::run_stepper;
+use day_03::stream::{calculate_products_do_dont_from_reader_resuming, calculate_products_from_reader};
 
 /// Main function to execute the program
 ///
+/// # Usage
+///
+/// ```bash
+/// cargo run -- path/to/input/file
+/// cargo run -- path/to/input/file --step
+/// cargo run -- path/to/input/file --algo memchr
+/// cargo run -- part1 part2 part3 --carry-state
+/// cargo run --features parquet -- path/to/input/file --emit-parquet instructions.parquet
+/// cargo run -- path/to/input/file --format json
+/// ```
+///
+/// Without an input path, the input cached by `aoc fetch` is used. The input
+/// is streamed rather than loaded, so files larger than memory work too.
+///
+/// Given several input paths, the totals cover all of them. Each file starts
+/// with multiplications enabled, unless `--carry-state` is given, in which
+/// case each starts in the state the previous file ended in.
+///
+/// `--step` walks through the instructions one at a time, showing the
+/// enabled state, running total and surrounding text for each.
+///
+/// `--algo <regex|scanner|memchr>` loads the input and finds the instructions
+/// with the chosen algorithm, so their timings can be compared.
+///
 /// # Returns
 ///
 /// * `Result<(), Box<dyn Error>>` - Success or an error
 fn main() -> Result<(), Box<dyn Error>> {
-    let path = std::env::args()
-        .nth(1)
-        .ok_or(AppError::ArgError("No input file provided"))?;
-    let input = read_file_to_string(&path)?;
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let format = take_format(&mut args)?;
+    logging::init(logging::take_verbosity(&mut args));
+    messages::init(messages::take_lang(&mut args)?.as_deref())?;
+    let mut args = args.into_iter();
+    let mut paths = Vec::new();
+    let mut parquet_path = None;
+    let mut step = false;
+    let mut carry_state = false;
+    let mut algorithm = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--emit-parquet" => {
+                parquet_path = Some(
+                    args.next()
+                        .ok_or(AppError::ArgError("--emit-parquet requires a path"))?,
+                )
+            }
+            "--step" => step = true,
+            "--carry-state" => carry_state = true,
+            "--algo" => {
+                algorithm = Some(
+                    args.next()
+                        .ok_or(AppError::ArgError("--algo requires regex, scanner or memchr"))?
+                        .parse::<Algorithm>()?,
+                )
+            }
+            _ if !arg.starts_with("--") => paths.push(arg),
+            _ => {
+                return Err(Box::new(AppError::ArgError(
+                    "Usage: day_03 [<input>...] [--carry-state] [--step] [--algo <regex|scanner|memchr>] [--emit-parquet <path>] [--format <text|json>] [-v|-vv]",
+                )))
+            }
+        }
+    }
+    if parquet_path.is_some() && cfg!(not(feature = "parquet")) {
+        return Err(Box::new(AppError::ArgError(
+            "--emit-parquet requires building with --features parquet",
+        )));
+    }
+    if paths.is_empty() {
+        paths.push(resolve_input(None, 3)?);
+    }
+    if (step || parquet_path.is_some()) && paths.len() > 1 {
+        return Err(Box::new(AppError::ArgError("--step and --emit-parquet take a single input")));
+    }
+    if step {
+        if format == Format::Json {
+            return Err(Box::new(AppError::ArgError("--step can't be combined with --format json")));
+        }
+        let input = read_file_to_string(&paths[0])?;
+        run_stepper(&input, std::io::stdin().lock(), std::io::stdout())?;
+        return Ok(());
+    }
+
+    let loaded = match algorithm {
+        Some(_) => paths.iter().map(|path| read_file_to_string(path)).collect::<Result<Vec<_>, _>>()?,
+        None => Vec::new(),
+    };
+
+    let start = Instant::now();
+    let (mut total, mut do_total, mut enabled) = (0, 0, true);
+    for (i, path) in paths.iter().enumerate() {
+        if !carry_state {
+            enabled = true;
+        }
+        let (file_total, (file_do_total, end_enabled)) = match algorithm {
+            Some(algorithm) => {
+                let (do_total, enabled) = calculate_products_do_dont_resuming(&loaded[i], algorithm, enabled)?;
+                (calculate_products_with(&loaded[i], algorithm)?, (do_total, enabled))
+            }
+            None => {
+                let open = || std::fs::File::open(path).map(std::io::BufReader::new);
+                (
+                    calculate_products_from_reader(open()?)?,
+                    calculate_products_do_dont_from_reader_resuming(open()?, enabled)?,
+                )
+            }
+        };
+        total += file_total;
+        do_total += file_do_total;
+        enabled = end_enabled;
+    }
+    let elapsed = start.elapsed();
+
+    match format {
+        Format::Text => {
+            println!("{}", msg!("day03-part1", total));
+            println!("{}", msg!("day03-part2", do_total));
+        }
+        Format::Json => {
+            let report = Report {
+                day: 3,
+                part1: Some(total.to_string()),
+                part2: Some(do_total.to_string()),
+                elapsed,
+            };
+            println!("{}", report.to_json());
+        }
+    }
 
-    let total = calculate_products(&input)?;
-    println!("Total sum of all products: {}", total);
+    #[cfg(feature = "parquet")]
+    if let Some(out) = parquet_path {
+        let input = read_file_to_string(&paths[0])?;
+        let instructions =
+            day_03::calculations::iter_instructions(&input).collect::<Result<Vec<_>, _>>()?;
+        day_03::export::write_instructions_parquet(&out, &instructions)?;
+        eprintln!("{}", msg!("day03-wrote-parquet", instructions.len(), out));
+    }
 
-    let total = calculate_products_do_dont(&input)?;
-    println!("Total sum of all 'do' products: {}", total);
     Ok(())
 }
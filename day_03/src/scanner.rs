@@ -0,0 +1,209 @@
+//! Byte-level state machine finding instructions without the regex engine.
+//!
+//! Each byte is looked at once. No instruction contains an `m` or `d` after
+//! its first byte, so when a byte doesn't continue the instruction being
+//! read, it can only start a new one and is fed to the initial state again.
+//!
+//! `find_instructions` runs the same state machine, but uses `memchr` to jump
+//! straight to the next `m` or `d` instead of feeding it every byte.
+
+use crate::calculations::Instruction;
+
+/// How much of an instruction has been read so far
+#[derive(Debug, Clone, Copy)]
+enum State {
+    Start,
+    M,
+    Mu,
+    Mul,
+    /// Inside `mul(`, reading the first operand
+    Lhs { value: i32, digits: u8 },
+    /// After the comma, reading the second operand
+    Rhs { lhs: i32, value: i32, digits: u8 },
+    D,
+    Do,
+    /// `do(`
+    DoOpen,
+    Don,
+    /// `don'`
+    DonQuote,
+    /// `don't`
+    Dont,
+    /// `don't(`
+    DontOpen,
+}
+
+/// Outcome of feeding one byte to the state machine
+enum Step {
+    /// The byte continues the current instruction
+    Next(State),
+    /// The byte completes an instruction
+    Done(Instruction),
+    /// The byte can't continue the current instruction
+    Fail,
+}
+
+/// Feeds `byte` to the state machine in `state`
+fn step(state: State, byte: u8) -> Step {
+    use State::*;
+
+    let digit = byte.is_ascii_digit().then(|| i32::from(byte - b'0'));
+    match (state, byte, digit) {
+        (Start, b'm', _) => Step::Next(M),
+        (Start, b'd', _) => Step::Next(D),
+        (M, b'u', _) => Step::Next(Mu),
+        (Mu, b'l', _) => Step::Next(Mul),
+        (Mul, b'(', _) => Step::Next(Lhs { value: 0, digits: 0 }),
+        (Lhs { value, digits }, _, Some(d)) if digits < 3 => {
+            Step::Next(Lhs { value: value * 10 + d, digits: digits + 1 })
+        }
+        (Lhs { value, digits }, b',', _) if digits > 0 => Step::Next(Rhs { lhs: value, value: 0, digits: 0 }),
+        (Rhs { lhs, value, digits }, _, Some(d)) if digits < 3 => {
+            Step::Next(Rhs { lhs, value: value * 10 + d, digits: digits + 1 })
+        }
+        (Rhs { lhs, value, digits }, b')', _) if digits > 0 => Step::Done(Instruction::Mul(lhs, value)),
+        (D, b'o', _) => Step::Next(Do),
+        (Do, b'(', _) => Step::Next(DoOpen),
+        (DoOpen, b')', _) => Step::Done(Instruction::Do),
+        (Do, b'n', _) => Step::Next(Don),
+        (Don, b'\'', _) => Step::Next(DonQuote),
+        (DonQuote, b't', _) => Step::Next(Dont),
+        (Dont, b'(', _) => Step::Next(DontOpen),
+        (DontOpen, b')', _) => Step::Done(Instruction::Dont),
+        _ => Step::Fail,
+    }
+}
+
+/// Iterator over the instructions in a byte slice, see `scan_instructions`
+#[derive(Debug, Clone)]
+pub struct Scanner<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Iterator for Scanner<'_> {
+    type Item = (usize, Instruction);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut state = State::Start;
+        let mut start = self.pos;
+
+        while let Some(&byte) = self.input.get(self.pos) {
+            let outcome = match step(state, byte) {
+                Step::Fail if !matches!(state, State::Start) => {
+                    start = self.pos;
+                    step(State::Start, byte)
+                }
+                outcome => outcome,
+            };
+            self.pos += 1;
+            match outcome {
+                Step::Next(next) => state = next,
+                Step::Done(instruction) => return Some((start, instruction)),
+                Step::Fail => {
+                    state = State::Start;
+                    start = self.pos;
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Iterator over the instructions in a byte slice, see `find_instructions`
+#[derive(Debug, Clone)]
+pub struct Finder<'a> {
+    input: &'a [u8],
+    pos: usize,
+}
+
+impl Iterator for Finder<'_> {
+    type Item = (usize, Instruction);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(found) = memchr::memchr2(b'm', b'd', &self.input[self.pos..]) {
+            let start = self.pos + found;
+            let mut state = State::Start;
+            self.pos = start + 1;
+            for (end, &byte) in self.input.iter().enumerate().skip(start) {
+                match step(state, byte) {
+                    Step::Next(next) => state = next,
+                    Step::Done(instruction) => {
+                        self.pos = end + 1;
+                        return Some((start, instruction));
+                    }
+                    Step::Fail => break,
+                }
+            }
+        }
+        self.pos = self.input.len();
+        None
+    }
+}
+
+/// Scans the input for do(), don't() and mul(a,b) instructions, finding the
+/// same instructions as `iter_instructions`
+///
+/// # Arguments
+///
+/// * `input` - The corrupted memory, which need not be valid UTF-8
+///
+/// # Returns
+///
+/// * An iterator yielding the byte offset of each instruction together with the
+///   instruction, in input order
+pub fn scan_instructions(input: &[u8]) -> Scanner<'_> {
+    Scanner { input, pos: 0 }
+}
+
+/// Finds the same instructions as `scan_instructions`, skipping with `memchr`
+/// over the bytes that can't start an instruction
+///
+/// # Arguments
+///
+/// * `input` - The corrupted memory, which need not be valid UTF-8
+///
+/// # Returns
+///
+/// * An iterator yielding the byte offset of each instruction together with the
+///   instruction, in input order
+pub fn find_instructions(input: &[u8]) -> Finder<'_> {
+    Finder { input, pos: 0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculations::iter_instructions;
+    use crate::file_io::read_file_to_string;
+    use std::error::Error;
+
+    /// Tests that the scanner agrees with the regex on the example
+    #[test]
+    fn test_matches_regex() -> Result<(), Box<dyn Error>> {
+        let input = read_file_to_string("data/inputtest")?;
+        let expected = iter_instructions(&input).collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(scan_instructions(input.as_bytes()).collect::<Vec<_>>(), expected);
+        assert_eq!(find_instructions(input.as_bytes()).collect::<Vec<_>>(), expected);
+        Ok(())
+    }
+
+    /// Tests the near misses the regex rejects, and tokens right after them
+    #[test]
+    fn test_near_misses() -> Result<(), Box<dyn Error>> {
+        let input = "mul(1234,5)mmul(1,2)mul(,3)mul(4,)mul (5,6)dodo()don'don't()mul(999,999)dmul(7,8";
+        let expected = iter_instructions(input).collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(
+            expected,
+            vec![
+                (12, Instruction::Mul(1, 2)),
+                (45, Instruction::Do),
+                (53, Instruction::Dont),
+                (60, Instruction::Mul(999, 999)),
+            ]
+        );
+        assert_eq!(scan_instructions(input.as_bytes()).collect::<Vec<_>>(), expected);
+        assert_eq!(find_instructions(input.as_bytes()).collect::<Vec<_>>(), expected);
+        Ok(())
+    }
+}
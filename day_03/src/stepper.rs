@@ -0,0 +1,125 @@
+//! Interactive step-by-step walk through the scanned instruction stream.
+
+use std::io::{BufRead, Write};
+
+use crate::calculations::{iter_instructions, Instruction};
+use crate::vm::VmState;
+use aoc_common::AppError;
+
+/// Number of bytes of raw input shown on each side of the current instruction
+const CONTEXT_BYTES: usize = 20;
+
+/// Returns the raw text around `offset` and the column of `offset` within it.
+/// Newlines are shown as spaces so the context stays on one line.
+fn context_window(input: &str, offset: usize) -> (String, usize) {
+    let mut start = offset.saturating_sub(CONTEXT_BYTES);
+    while !input.is_char_boundary(start) {
+        start -= 1;
+    }
+    let mut end = (offset + CONTEXT_BYTES * 2).min(input.len());
+    while !input.is_char_boundary(end) {
+        end += 1;
+    }
+
+    let text = input[start..end].replace(['\n', '\r'], " ");
+    (text, input[start..offset].chars().count())
+}
+
+/// Steps through every instruction in the input one at a time.
+///
+/// For each instruction the enabled/disabled state, the running total of
+/// enabled multiplications and the surrounding raw text are written to
+/// `output`, then a line is read from `commands`: an empty line (or end of
+/// input) moves to the next instruction, `q` stops early.
+///
+/// # Arguments
+///
+/// * `input` - The corrupted memory to scan
+/// * `commands` - Where the step commands are read from, normally stdin
+/// * `output` - Where the steps are written to, normally stdout
+///
+/// # Returns
+///
+/// * `Result<i64, AppError>` - The running total when stepping stopped, or an error
+pub fn run_stepper(
+    input: &str,
+    mut commands: impl BufRead,
+    mut output: impl Write,
+) -> Result<i64, AppError> {
+    let mut state = VmState::default();
+    let mut command = String::new();
+
+    for (step, item) in iter_instructions(input).enumerate() {
+        let (offset, instruction) = item?;
+        let (was_enabled, total_before) = (state.enabled, state.total);
+        state.apply(instruction.into());
+        let note = match instruction {
+            Instruction::Do => "enables multiplications".to_string(),
+            Instruction::Dont => "disables multiplications".to_string(),
+            Instruction::Mul(..) if was_enabled => format!("adds {}", state.total - total_before),
+            Instruction::Mul(..) => "skipped".to_string(),
+        };
+
+        let (context, column) = context_window(input, offset);
+        writeln!(output, "#{} @{}: {} ({})", step + 1, offset, instruction, note)?;
+        writeln!(
+            output,
+            "  state: {}, running total: {}",
+            if state.enabled { "enabled" } else { "disabled" },
+            state.total
+        )?;
+        writeln!(output, "  {}", context)?;
+        writeln!(output, "  {}^", " ".repeat(column))?;
+        write!(output, "[Enter] next, [q] quit > ")?;
+        output.flush()?;
+
+        command.clear();
+        if commands.read_line(&mut command)? == 0 {
+            writeln!(output)?;
+        } else if command.trim() == "q" {
+            break;
+        }
+    }
+
+    writeln!(output, "Total: {}", state.total)?;
+    Ok(state.total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::read_file_to_string;
+    use std::error::Error;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_run_stepper_to_end() -> Result<(), Box<dyn Error>> {
+        let input = read_file_to_string("data/inputtest")?;
+        let mut output = Vec::new();
+        let total = run_stepper(&input, Cursor::new(""), &mut output)?;
+        assert_eq!(total, 48);
+
+        let output = String::from_utf8(output)?;
+        assert!(output.contains("#2 @20: don't() (disables multiplications)"));
+        assert!(output.contains("#3 @28: mul(5,5) (skipped)"));
+        assert!(output.contains("  state: enabled, running total: 48"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_run_stepper_quit() -> Result<(), Box<dyn Error>> {
+        let input = read_file_to_string("data/inputtest")?;
+        let mut output = Vec::new();
+        let total = run_stepper(&input, Cursor::new("\nq\n"), &mut output)?;
+        assert_eq!(total, 8);
+        assert!(!String::from_utf8(output)?.contains("#3 "));
+        Ok(())
+    }
+
+    #[test]
+    fn test_context_window() {
+        let (context, column) = context_window("ab\ncdmul(1,2)", 5);
+        assert_eq!(context, "ab cdmul(1,2)");
+        assert_eq!(column, 5);
+    }
+}
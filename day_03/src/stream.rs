@@ -0,0 +1,222 @@
+//! Scanning corrupted memory from a reader, for inputs too large to load.
+//!
+//! The reader is consumed one buffer at a time. Any bytes at the end of a
+//! buffer that could still be the start of an instruction are carried over
+//! and scanned again together with the next buffer, so instructions split
+//! across buffers are found exactly once.
+
+use std::collections::VecDeque;
+use std::io::BufRead;
+use std::sync::LazyLock;
+
+use aoc_common::AppError;
+
+use crate::calculations::Instruction;
+use crate::vm::VmState;
+
+/// The longest instruction, `mul(123,456)`, in bytes
+const MAX_INSTRUCTION_LEN: usize = 12;
+
+// Byte-level version of the do, don't and multiplication pattern, so buffers
+// can end in the middle of a UTF-8 character
+static DO_DONT_BYTES_RE: LazyLock<regex::bytes::Regex> = LazyLock::new(|| {
+    regex::bytes::Regex::new(r"(do\(\)|don't\(\)|mul\(([0-9]{1,3}),([0-9]{1,3})\))").unwrap()
+});
+
+/// Iterator over the instructions read from a `BufRead`, see `stream_instructions`
+#[derive(Debug)]
+pub struct InstructionStream<R> {
+    reader: R,
+    /// Bytes read but not yet known to hold no further instruction
+    carry: Vec<u8>,
+    /// Offset of the first carried byte in the whole input
+    offset: u64,
+    found: VecDeque<(u64, Instruction)>,
+    done: bool,
+}
+
+impl<R: BufRead> InstructionStream<R> {
+    /// Scans the carried bytes, keeping the tail that may hold the start of
+    /// an instruction unless the input has ended
+    fn scan(&mut self, at_end: bool) {
+        let mut keep_from = if at_end {
+            self.carry.len()
+        } else {
+            self.carry.len().saturating_sub(MAX_INSTRUCTION_LEN - 1)
+        };
+
+        for cap in DO_DONT_BYTES_RE.captures_iter(&self.carry) {
+            let instruction = match &cap[1] {
+                b"do()" => Instruction::Do,
+                b"don't()" => Instruction::Dont,
+                _ => Instruction::Mul(parse_operand(&cap[2]), parse_operand(&cap[3])),
+            };
+            let whole = cap.get(0).map_or(0..0, |m| m.range());
+            self.found.push_back((self.offset + whole.start as u64, instruction));
+            keep_from = keep_from.max(whole.end);
+        }
+
+        self.carry.drain(..keep_from);
+        self.offset += keep_from as u64;
+    }
+}
+
+impl<R: BufRead> Iterator for InstructionStream<R> {
+    type Item = Result<(u64, Instruction), AppError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(found) = self.found.pop_front() {
+                return Some(Ok(found));
+            }
+            if self.done {
+                return None;
+            }
+
+            let chunk = match self.reader.fill_buf() {
+                Ok(chunk) => chunk,
+                Err(error) if error.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(error) => {
+                    self.done = true;
+                    return Some(Err(error.into()));
+                }
+            };
+            let read = chunk.len();
+            self.carry.extend_from_slice(chunk);
+            self.reader.consume(read);
+            self.done = read == 0;
+            self.scan(self.done);
+        }
+    }
+}
+
+/// Parses 1-3 ASCII digits already checked by the pattern
+fn parse_operand(digits: &[u8]) -> i32 {
+    digits.iter().fold(0, |n, &digit| n * 10 + i32::from(digit - b'0'))
+}
+
+/// Lazily scans a reader for do(), don't() and mul(a,b) instructions
+///
+/// # Arguments
+///
+/// * `reader` - The corrupted memory, read one buffer at a time
+///
+/// # Returns
+///
+/// * An iterator yielding the byte offset of each instruction together with the
+///   instruction, in input order, or an error if the reader fails
+pub fn stream_instructions<R: BufRead>(reader: R) -> InstructionStream<R> {
+    InstructionStream { reader, carry: Vec::new(), offset: 0, found: VecDeque::new(), done: false }
+}
+
+/// Calculates the total product of all multiplication expressions read from `reader`
+///
+/// # Arguments
+///
+/// * `reader` - The corrupted memory, read one buffer at a time
+///
+/// # Returns
+///
+/// * `Result<i64, AppError>` - The total product or an error
+pub fn calculate_products_from_reader(reader: impl BufRead) -> Result<i64, AppError> {
+    let mut state = VmState::default();
+
+    for item in stream_instructions(reader) {
+        if let (_, instruction @ Instruction::Mul(..)) = item? {
+            state.apply(instruction.into());
+        }
+    }
+
+    Ok(state.total)
+}
+
+/// Calculates the total product of the multiplication expressions read from
+/// `reader` that are preceded by a "do()" and not by a "don't()"
+///
+/// # Arguments
+///
+/// * `reader` - The corrupted memory, read one buffer at a time
+///
+/// # Returns
+///
+/// * `Result<i64, AppError>` - The total product or an error
+pub fn calculate_products_do_dont_from_reader(reader: impl BufRead) -> Result<i64, AppError> {
+    calculate_products_do_dont_from_reader_resuming(reader, true).map(|(total, _)| total)
+}
+
+/// Calculates the total product of the enabled multiplication expressions
+/// read from `reader`, starting from the enabled state left by an earlier input
+///
+/// # Arguments
+///
+/// * `reader` - The corrupted memory, read one buffer at a time
+/// * `enabled` - Whether multiplications are enabled at the start of the input
+///
+/// # Returns
+///
+/// * `Result<(i64, bool), AppError>` - The total product and whether
+///   multiplications are still enabled at the end of the input, or an error
+pub fn calculate_products_do_dont_from_reader_resuming(
+    reader: impl BufRead,
+    enabled: bool,
+) -> Result<(i64, bool), AppError> {
+    let mut state = VmState { enabled, ..VmState::default() };
+
+    for item in stream_instructions(reader) {
+        let (_, instruction) = item?;
+        state.apply(instruction.into());
+    }
+
+    Ok((state.total, state.enabled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculations::iter_instructions;
+    use crate::file_io::read_file_to_string;
+    use std::error::Error;
+    use std::io::BufReader;
+
+    /// Every buffer size splits some instruction of the example somewhere
+    #[test]
+    fn test_matches_whole_input() -> Result<(), Box<dyn Error>> {
+        let input = read_file_to_string("data/inputtest")?;
+        let expected = iter_instructions(&input)
+            .map(|item| item.map(|(offset, instruction)| (offset as u64, instruction)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        for capacity in 1..=input.len() {
+            let reader = BufReader::with_capacity(capacity, input.as_bytes());
+            let found = stream_instructions(reader).collect::<Result<Vec<_>, _>>()?;
+            assert_eq!(found, expected, "buffer of {} bytes", capacity);
+
+            let reader = BufReader::with_capacity(capacity, input.as_bytes());
+            assert_eq!(calculate_products_from_reader(reader)?, 161);
+            let reader = BufReader::with_capacity(capacity, input.as_bytes());
+            assert_eq!(calculate_products_do_dont_from_reader(reader)?, 48);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_split_utf8_and_near_misses() -> Result<(), Box<dyn Error>> {
+        let input = "é mul(1,2 mul(3,4)don'tmul(5,6)ü do_not()mul(1234,1)don't()mul(7,8)";
+        for capacity in 1..=input.len() {
+            let reader = BufReader::with_capacity(capacity, input.as_bytes());
+            let found: Vec<_> = stream_instructions(reader)
+                .map(|item| item.map(|(_, instruction)| instruction))
+                .collect::<Result<_, _>>()?;
+            assert_eq!(
+                found,
+                vec![
+                    Instruction::Mul(3, 4),
+                    Instruction::Mul(5, 6),
+                    Instruction::Dont,
+                    Instruction::Mul(7, 8),
+                ]
+            );
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,195 @@
+//! Scanning corrupted memory whose instructions are spelled differently.
+//!
+//! A `ScannerConfig` describes the instruction names, how many digits an
+//! operand may have and whether whitespace may appear inside the brackets,
+//! as in `mul( 2 , 4 )`. `compile` turns it into a `ConfiguredScanner`, which
+//! finds the same `Instruction`s as the fixed puzzle syntax does.
+
+use aoc_common::AppError;
+
+use crate::calculations::Instruction;
+use crate::vm::VmState;
+
+/// The spelling of the instructions to scan for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScannerConfig {
+    /// Name of the multiplication, `mul` in the puzzle
+    pub mul: String,
+    /// Name of the instruction enabling multiplications, `do` in the puzzle
+    pub enable: String,
+    /// Name of the instruction disabling multiplications, `don't` in the puzzle
+    pub disable: String,
+    /// Fewest digits an operand may have
+    pub min_digits: usize,
+    /// Most digits an operand may have
+    pub max_digits: usize,
+    /// Whether whitespace is allowed next to the brackets and the comma
+    pub allow_whitespace: bool,
+}
+
+impl Default for ScannerConfig {
+    /// The puzzle's own syntax
+    fn default() -> Self {
+        Self {
+            mul: "mul".to_string(),
+            enable: "do".to_string(),
+            disable: "don't".to_string(),
+            min_digits: 1,
+            max_digits: 3,
+            allow_whitespace: false,
+        }
+    }
+}
+
+impl ScannerConfig {
+    /// Builds the matcher for this syntax
+    ///
+    /// # Returns
+    ///
+    /// * `Result<ConfiguredScanner, AppError>` - The scanner, or an error if a
+    ///   name is empty or the digit limits are out of order or above 9, which
+    ///   could overflow an operand
+    pub fn compile(&self) -> Result<ConfiguredScanner, AppError> {
+        if self.mul.is_empty() || self.enable.is_empty() || self.disable.is_empty() {
+            return Err(AppError::ArgError("instruction names can't be empty"));
+        }
+        if self.min_digits == 0 || self.min_digits > self.max_digits || self.max_digits > 9 {
+            return Err(AppError::ArgError("operands need between 1 and 9 digits"));
+        }
+
+        let space = if self.allow_whitespace { r"\s*" } else { "" };
+        let digits = format!("[0-9]{{{},{}}}", self.min_digits, self.max_digits);
+        let pattern = format!(
+            r"(?P<enable>{enable}\({space}\))|(?P<disable>{disable}\({space}\))|{mul}\({space}(?P<lhs>{digits}){space},{space}(?P<rhs>{digits}){space}\)",
+            enable = regex::escape(&self.enable),
+            disable = regex::escape(&self.disable),
+            mul = regex::escape(&self.mul),
+        );
+        let re = regex::Regex::new(&pattern).map_err(|_| AppError::ArgError("scanner syntax is too large"))?;
+        Ok(ConfiguredScanner { re })
+    }
+}
+
+/// A matcher compiled from a `ScannerConfig`
+#[derive(Debug, Clone)]
+pub struct ConfiguredScanner {
+    re: regex::Regex,
+}
+
+impl ConfiguredScanner {
+    /// Lazily scans the input for instructions in this syntax
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - A string slice that holds the input data
+    ///
+    /// # Returns
+    ///
+    /// * An iterator yielding the byte offset of each instruction together with the
+    ///   instruction, in input order, or an error if an operand cannot be parsed
+    pub fn instructions<'a>(
+        &'a self,
+        input: &'a str,
+    ) -> impl Iterator<Item = Result<(usize, Instruction), AppError>> + 'a {
+        self.re.captures_iter(input).map(|cap| {
+            let instruction = if cap.name("enable").is_some() {
+                Instruction::Do
+            } else if cap.name("disable").is_some() {
+                Instruction::Dont
+            } else {
+                Instruction::Mul(cap["lhs"].parse()?, cap["rhs"].parse()?)
+            };
+            Ok((cap.get(0).map_or(0, |m| m.start()), instruction))
+        })
+    }
+
+    /// Calculates the total product of all multiplications in the input string
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - A string slice that holds the input data
+    ///
+    /// # Returns
+    ///
+    /// * `Result<i64, AppError>` - The total product or an error
+    pub fn calculate_products(&self, input: &str) -> Result<i64, AppError> {
+        let mut state = VmState::default();
+
+        for item in self.instructions(input) {
+            if let (_, instruction @ Instruction::Mul(..)) = item? {
+                state.apply(instruction.into());
+            }
+        }
+
+        Ok(state.total)
+    }
+
+    /// Calculates the total product of the multiplications in the input string
+    /// that are enabled
+    ///
+    /// # Arguments
+    ///
+    /// * `input` - A string slice that holds the input data
+    ///
+    /// # Returns
+    ///
+    /// * `Result<i64, AppError>` - The total product or an error
+    pub fn calculate_products_do_dont(&self, input: &str) -> Result<i64, AppError> {
+        let mut state = VmState::default();
+
+        for item in self.instructions(input) {
+            let (_, instruction) = item?;
+            state.apply(instruction.into());
+        }
+
+        Ok(state.total)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculations::iter_instructions;
+    use crate::file_io::read_file_to_string;
+    use std::error::Error;
+
+    /// Tests that the default syntax is the puzzle's
+    #[test]
+    fn test_default_matches_puzzle() -> Result<(), Box<dyn Error>> {
+        let input = read_file_to_string("data/inputtest")?;
+        let scanner = ScannerConfig::default().compile()?;
+        let expected = iter_instructions(&input).collect::<Result<Vec<_>, _>>()?;
+        assert_eq!(scanner.instructions(&input).collect::<Result<Vec<_>, _>>()?, expected);
+        assert_eq!(scanner.calculate_products(&input)?, 161);
+        assert_eq!(scanner.calculate_products_do_dont(&input)?, 48);
+        Ok(())
+    }
+
+    #[test]
+    fn test_custom_syntax() -> Result<(), Box<dyn Error>> {
+        let config = ScannerConfig {
+            mul: "times".to_string(),
+            enable: "on".to_string(),
+            disable: "off".to_string(),
+            min_digits: 2,
+            max_digits: 4,
+            allow_whitespace: true,
+        };
+        let scanner = config.compile()?;
+        let input = "times( 10 , 1000 )mul(2,4)times(1,10)off( )times(11,11)on()times(12,\n12)";
+        assert_eq!(scanner.calculate_products(input)?, 10_000 + 121 + 144);
+        assert_eq!(scanner.calculate_products_do_dont(input)?, 10_000 + 144);
+
+        let strict = ScannerConfig { allow_whitespace: false, ..config };
+        assert_eq!(strict.compile()?.calculate_products(input)?, 121);
+        Ok(())
+    }
+
+    #[test]
+    fn test_invalid_config() {
+        let config = ScannerConfig { min_digits: 4, max_digits: 3, ..ScannerConfig::default() };
+        assert!(matches!(config.compile(), Err(AppError::ArgError(_))));
+        let config = ScannerConfig { mul: String::new(), ..ScannerConfig::default() };
+        assert!(matches!(config.compile(), Err(AppError::ArgError(_))));
+    }
+}
@@ -0,0 +1,204 @@
+//! A tiny interpreter for an extended corrupted-memory language.
+//!
+//! Besides the puzzle's `do()`, `don't()` and `mul(a,b)` it understands:
+//!
+//! * `add(a,b)` - adds `a + b` to the total while enabled
+//! * `set(r,v)` - stores `v` in register `r`, one of `a` to `z`
+//! * `begin()` / `end()` - a nested scope: `end()` restores the enabled state
+//!   from before the matching `begin()`
+//!
+//! The operands of `mul` and `add` are either 1-3 digit numbers or register
+//! names. Registers start at 0. The puzzle's own functions in `calculations`,
+//! `stream` and `stepper` run their instructions through the same `VmState`.
+
+use std::sync::LazyLock;
+
+use aoc_common::AppError;
+
+use crate::calculations;
+
+// Regular expression to match every instruction of the extended language
+static PROGRAM_RE: LazyLock<regex::Regex> = LazyLock::new(|| {
+    regex::Regex::new(
+        r"do\(\)|don't\(\)|begin\(\)|end\(\)|(mul|add)\(([0-9]{1,3}|[a-z]),([0-9]{1,3}|[a-z])\)|set\(([a-z]),([0-9]{1,3})\)",
+    )
+    .unwrap()
+});
+
+/// An operand of `mul` or `add`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operand {
+    /// A literal number
+    Value(i32),
+    /// The register with this index, 0 for `a` up to 25 for `z`
+    Register(u8),
+}
+
+/// A single instruction of the extended language
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Instruction {
+    /// `do()` - enables future arithmetic
+    Do,
+    /// `don't()` - disables future arithmetic
+    Dont,
+    /// `mul(a,b)` - adds `a * b` to the total
+    Mul(Operand, Operand),
+    /// `add(a,b)` - adds `a + b` to the total
+    Add(Operand, Operand),
+    /// `set(r,v)` - stores `v` in register `r`
+    Set(u8, i32),
+    /// `begin()` - opens a scope
+    Begin,
+    /// `end()` - closes the innermost scope
+    End,
+}
+
+impl From<calculations::Instruction> for Instruction {
+    fn from(instruction: calculations::Instruction) -> Self {
+        match instruction {
+            calculations::Instruction::Do => Self::Do,
+            calculations::Instruction::Dont => Self::Dont,
+            calculations::Instruction::Mul(num1, num2) => Self::Mul(Operand::Value(num1), Operand::Value(num2)),
+        }
+    }
+}
+
+/// The state of the interpreter between instructions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VmState {
+    /// Sum of every `mul` and `add` run while enabled
+    pub total: i64,
+    /// Whether `mul` and `add` currently count
+    pub enabled: bool,
+    /// The enabled state saved by each open `begin()`, innermost last
+    pub scopes: Vec<bool>,
+    /// The values of registers `a` to `z`, changed only by `set`
+    pub registers: [i32; 26],
+}
+
+impl Default for VmState {
+    fn default() -> Self {
+        Self { total: 0, enabled: true, scopes: Vec::new(), registers: [0; 26] }
+    }
+}
+
+impl VmState {
+    /// Runs one instruction.
+    ///
+    /// `set` takes effect even while disabled. An `end()` without an open
+    /// scope is ignored, like any other corruption.
+    pub fn apply(&mut self, instruction: Instruction) {
+        match instruction {
+            Instruction::Do => self.enabled = true,
+            Instruction::Dont => self.enabled = false,
+            Instruction::Mul(a, b) => {
+                if self.enabled {
+                    self.total += i64::from(self.value(a)) * i64::from(self.value(b));
+                }
+            }
+            Instruction::Add(a, b) => {
+                if self.enabled {
+                    self.total += i64::from(self.value(a)) + i64::from(self.value(b));
+                }
+            }
+            Instruction::Set(register, value) => self.registers[usize::from(register)] = value,
+            Instruction::Begin => self.scopes.push(self.enabled),
+            Instruction::End => {
+                if let Some(enabled) = self.scopes.pop() {
+                    self.enabled = enabled;
+                }
+            }
+        }
+    }
+
+    fn value(&self, operand: Operand) -> i32 {
+        match operand {
+            Operand::Value(value) => value,
+            Operand::Register(register) => self.registers[usize::from(register)],
+        }
+    }
+}
+
+/// Runs a program from the initial state
+///
+/// # Arguments
+///
+/// * `program` - The instructions to run, in order
+///
+/// # Returns
+///
+/// * `VmState` - The state after the last instruction
+pub fn execute(program: impl IntoIterator<Item = Instruction>) -> VmState {
+    let mut state = VmState::default();
+    for instruction in program {
+        state.apply(instruction);
+    }
+    state
+}
+
+/// Parses an operand already checked by `PROGRAM_RE`
+fn parse_operand(text: &str) -> Result<Operand, AppError> {
+    match text.as_bytes() {
+        &[letter] if letter.is_ascii_lowercase() => Ok(Operand::Register(letter - b'a')),
+        _ => Ok(Operand::Value(text.parse()?)),
+    }
+}
+
+/// Scans corrupted memory for the instructions of the extended language
+///
+/// # Arguments
+///
+/// * `input` - A string slice that holds the program
+///
+/// # Returns
+///
+/// * `Result<Vec<Instruction>, AppError>` - The instructions in input order,
+///   or an error if a number cannot be parsed
+pub fn parse_program(input: &str) -> Result<Vec<Instruction>, AppError> {
+    PROGRAM_RE
+        .captures_iter(input)
+        .map(|cap| {
+            let instruction = match &cap[0] {
+                "do()" => Instruction::Do,
+                "don't()" => Instruction::Dont,
+                "begin()" => Instruction::Begin,
+                "end()" => Instruction::End,
+                _ => match cap.get(1).map(|m| m.as_str()) {
+                    Some("mul") => Instruction::Mul(parse_operand(&cap[2])?, parse_operand(&cap[3])?),
+                    Some(_) => Instruction::Add(parse_operand(&cap[2])?, parse_operand(&cap[3])?),
+                    None => Instruction::Set(cap[4].as_bytes()[0] - b'a', cap[5].parse()?),
+                },
+            };
+            Ok(instruction)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::read_file_to_string;
+    use std::error::Error;
+
+    /// Tests that the puzzle's instructions give the part 2 answer
+    #[test]
+    fn test_example() -> Result<(), Box<dyn Error>> {
+        let input = read_file_to_string("data/inputtest")?;
+        assert_eq!(execute(parse_program(&input)?).total, 48);
+        Ok(())
+    }
+
+    #[test]
+    fn test_extended_program() -> Result<(), Box<dyn Error>> {
+        let program = parse_program("set(x,7)xadd(x,3)mul(x,2)begin()don't()mul(5,5)begin()do()add(1,1)end()mul(9,9)end()end()mul(1,1)set(y,1000)")?;
+        assert_eq!(program.len(), 14);
+        let state = execute(program);
+        // 10 + 14, then 2 in the inner scope, then 1 once the outer one closes
+        assert_eq!(state.total, 27);
+        assert!(state.enabled);
+        assert!(state.scopes.is_empty());
+        assert_eq!(state.registers[23], 7);
+        assert_eq!(state.registers[24], 0, "set(y,1000) has a 4 digit value");
+        Ok(())
+    }
+}
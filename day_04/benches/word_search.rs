@@ -0,0 +1,16 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use day_04::calculations::{count_instances, count_x_instances};
+use day_04::file_io::parse_grid;
+
+const INPUT: &str = include_str!("../data/input");
+
+fn bench_word_search(c: &mut Criterion) {
+    let grid = parse_grid(INPUT).unwrap();
+
+    c.bench_function("count_instances", |b| b.iter(|| count_instances(black_box(&grid), "XMAS")));
+    c.bench_function("count_x_instances", |b| b.iter(|| count_x_instances(black_box(&grid), "MAS")));
+}
+
+criterion_group!(benches, bench_word_search);
+criterion_main!(benches);
@@ -1,10 +1,204 @@
-use crate::errors::AppError;
+use aho_corasick::AhoCorasick;
+use aoc_common::AppError;
 use ndarray::Array2;
+use rayon::prelude::*;
+
+use crate::search::Search;
+
+/// A line through the grid that a pattern can lie along
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    /// Left to right along a row
+    Horizontal,
+    /// Top to bottom along a column
+    Vertical,
+    /// Top left to bottom right
+    DiagonalDownRight,
+    /// Top right to bottom left
+    DiagonalDownLeft,
+}
+
+impl Direction {
+    /// Every direction, covering every line through the grid once
+    pub const ALL: [Direction; 4] = [
+        Direction::Horizontal,
+        Direction::Vertical,
+        Direction::DiagonalDownRight,
+        Direction::DiagonalDownLeft,
+    ];
+
+    /// The (row, col) step from one character of a match to the next
+    pub fn delta(self) -> (isize, isize) {
+        match self {
+            Direction::Horizontal => (0, 1),
+            Direction::Vertical => (1, 0),
+            Direction::DiagonalDownRight => (1, 1),
+            Direction::DiagonalDownLeft => (1, -1),
+        }
+    }
+}
+
+/// A set of directions to search along, combined with `|`.
+///
+/// Patterns are only read forwards along the chosen directions unless
+/// `REVERSED` is included too.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Directions(u8);
+
+impl Directions {
+    pub const HORIZONTAL: Directions = Directions(1);
+    pub const VERTICAL: Directions = Directions(2);
+    pub const DIAG_DOWN_RIGHT: Directions = Directions(4);
+    pub const DIAG_DOWN_LEFT: Directions = Directions(8);
+    /// Also read each chosen direction backwards
+    pub const REVERSED: Directions = Directions(16);
+    /// Every direction, both ways
+    pub const ALL: Directions = Directions(31);
+
+    /// Whether every direction in `other` is also in `self`
+    pub fn contains(self, other: Directions) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    /// Whether a pattern may be read along `direction` in `orientation`
+    fn allows(self, direction: Direction, orientation: Orientation) -> bool {
+        let axis = match direction {
+            Direction::Horizontal => Directions::HORIZONTAL,
+            Direction::Vertical => Directions::VERTICAL,
+            Direction::DiagonalDownRight => Directions::DIAG_DOWN_RIGHT,
+            Direction::DiagonalDownLeft => Directions::DIAG_DOWN_LEFT,
+        };
+        self.contains(axis) && (orientation == Orientation::Forward || self.contains(Directions::REVERSED))
+    }
+}
+
+impl Default for Directions {
+    fn default() -> Self {
+        Directions::ALL
+    }
+}
+
+impl std::ops::BitOr for Directions {
+    type Output = Directions;
+
+    fn bitor(self, other: Directions) -> Directions {
+        Directions(self.0 | other.0)
+    }
+}
+
+/// How lines behave at the edges of the grid
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Topology {
+    /// Lines stop at the edges
+    #[default]
+    Flat,
+    /// Lines wrap around the edges, so the grid is a torus. A match may not
+    /// use a cell twice, so it is at most as long as the line it lies on.
+    Torus,
+}
+
+/// Whether a match reads as the pattern or as the pattern reversed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Orientation {
+    Forward,
+    Backward,
+}
+
+/// One instance of a pattern in the grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Match {
+    /// The (row, col) of the match's first cell along `direction`
+    pub start: (usize, usize),
+    pub direction: Direction,
+    /// `Forward` if the pattern reads from `start` along `direction`,
+    /// `Backward` if it reads towards `start`
+    pub orientation: Orientation,
+}
+
+impl Match {
+    /// The cells covered by a match of a pattern `len` characters long, from `start`
+    pub fn cells(&self, len: usize) -> impl Iterator<Item = (usize, usize)> {
+        let (dr, dc) = self.direction.delta();
+        let (row, col) = self.start;
+        (0..len as isize).map(move |k| ((row as isize + dr * k) as usize, (col as isize + dc * k) as usize))
+    }
+
+    /// Like `cells`, for a match found with `Topology::Torus` on a grid of
+    /// `rows` x `cols`
+    pub fn cells_wrapping(&self, len: usize, (rows, cols): (usize, usize)) -> impl Iterator<Item = (usize, usize)> {
+        let (dr, dc) = self.direction.delta();
+        let (row, col) = self.start;
+        (0..len as isize).map(move |k| {
+            (
+                (row as isize + dr * k).rem_euclid(rows as isize) as usize,
+                (col as isize + dc * k).rem_euclid(cols as isize) as usize,
+            )
+        })
+    }
+}
+
+/// Prepares a search of the grid for `search` along the chosen directions
+fn search_2d<'a>(input: &Array2<char>, search: &'a str, topology: Topology, directions: Directions) -> Search<'a, 2> {
+    let (rows, cols) = input.dim();
+    let axes = Direction::ALL.map(|direction| {
+        let (dr, dc) = direction.delta();
+        [dr, dc]
+    });
+    Search::new(search, [rows, cols], &axes, topology, |axis, orientation| {
+        directions.allows(Direction::ALL[axis], orientation)
+    })
+}
+
+/// Calls `visit` with every instance found by `search` whose first character
+/// is in row `i`
+fn for_each_instance_in_row(input: &Array2<char>, search: &Search<2>, i: usize, mut visit: impl FnMut(Match)) {
+    for j in 0..input.ncols() {
+        search.starting_at(|position| input[position], [i, j], |axis, orientation, [row, col]| {
+            visit(Match { start: (row, col), direction: Direction::ALL[axis], orientation })
+        });
+    }
+}
+
+/// Finds every instance of a string pattern in an Array2 of characters.
+/// The search is performed in all directions: horizontal, vertical, and diagonal.
+/// The pattern can be found forwards or backwards; a palindrome is only
+/// reported once per position, as `Forward`.
+///
+/// # Arguments
+///
+/// * `input` - A 2D array of characters to search through
+/// * `search` - The pattern to search for
+///
+/// # Returns
+///
+/// * `Vec<Match>` - Every match, ordered by the cell holding the pattern's
+///   first character
+pub fn find_instances(input: &Array2<char>, search: &str) -> Vec<Match> {
+    find_instances_with(input, search, Topology::default(), Directions::ALL)
+}
+
+/// Like `find_instances`, with lines that behave at the edges as `topology`
+/// says, only searching along `directions`
+pub fn find_instances_with(
+    input: &Array2<char>,
+    search: &str,
+    topology: Topology,
+    directions: Directions,
+) -> Vec<Match> {
+    let search = search_2d(input, search, topology, directions);
+    let mut matches = Vec::new();
+    for i in 0..input.nrows() {
+        for_each_instance_in_row(input, &search, i, |found| matches.push(found));
+    }
+    matches
+}
 
 /// Searches for instances of a string pattern in an Array2 of characters.
 /// The search is performed in all directions: horizontal, vertical, and diagonal.
 /// The pattern can be found forwards or backwards.
 ///
+/// Rows are searched in parallel and their counts summed.
+///
 /// # Arguments
 ///
 /// * `input` - A 2D array of characters to search through
@@ -14,63 +208,116 @@ use ndarray::Array2;
 ///
 /// * `Result<i32, AppError>` - The number of pattern instances found, or an error
 pub fn count_instances(input: &Array2<char>, search: &str) -> Result<i32, AppError> {
-    let mut num_instances = 0;
+    count_instances_with(input, search, Topology::default(), Directions::ALL)
+}
+
+/// Like `count_instances`, with lines that behave at the edges as `topology`
+/// says, only searching along `directions`
+pub fn count_instances_with(
+    input: &Array2<char>,
+    search: &str,
+    topology: Topology,
+    directions: Directions,
+) -> Result<i32, AppError> {
+    let search = search_2d(input, search, topology, directions);
+    let num_instances = (0..input.nrows())
+        .into_par_iter()
+        .map(|i| {
+            let mut row_instances = 0;
+            for_each_instance_in_row(input, &search, i, |_| row_instances += 1);
+            row_instances
+        })
+        .sum();
+    Ok(num_instances)
+}
+
+/// Every line through the grid once: rows, columns and both diagonals
+fn grid_lines(input: &Array2<char>) -> Vec<String> {
     let (rows, cols) = input.dim();
-    let search_len = search.len();
-    let search_chars: Vec<char> = search.chars().collect();
-    let search_reverse: Vec<char> = search_chars.iter().rev().cloned().collect();
-
-    // Check rows
-    for row in input.rows() {
-        row.windows(search_len)
-            .into_iter().filter(|window| {
-                window.to_vec() == search_chars || window.to_vec() == search_reverse
-            })
-            .for_each(|_| num_instances += 1);
-    }
-
-    // Check columns
-    for col in input.columns() {
-        col.windows(search_len)
-            .into_iter().filter(|window| {
-                window.to_vec() == search_chars || window.to_vec() == search_reverse
-            })
-            .for_each(|_| num_instances += 1);
-    }
-
-    // Check diagonals
-    for i in 0..rows {
-        for j in 0..cols {
-            // Down-right diagonal
-            if i + search_len <= rows && j + search_len <= cols {
-                let diag_chars: Vec<char> = (0..search_len).map(|k| input[[i + k, j + k]]).collect();
-                if diag_chars == search_chars || diag_chars == search_reverse {
-                    num_instances += 1;
+    let mut lines: Vec<String> = input.rows().into_iter().map(|row| row.iter().collect()).collect();
+    lines.extend(input.columns().into_iter().map(|col| col.iter().collect::<String>()));
+    if rows == 0 || cols == 0 {
+        return lines;
+    }
+
+    // Down-right diagonals start on the top row or the left column, down-left
+    // diagonals on the top row or the right column
+    for (start_row, start_col) in (0..cols).map(|j| (0, j)).chain((1..rows).map(|i| (i, 0))) {
+        let len = (rows - start_row).min(cols - start_col);
+        lines.push((0..len).map(|k| input[[start_row + k, start_col + k]]).collect());
+    }
+    for (start_row, start_col) in (0..cols).map(|j| (0, j)).chain((1..rows).map(|i| (i, cols - 1))) {
+        let len = (rows - start_row).min(start_col + 1);
+        lines.push((0..len).map(|k| input[[start_row + k, start_col - k]]).collect());
+    }
+
+    lines
+}
+
+/// Counts the instances of several patterns at once.
+///
+/// Each pattern is counted exactly as `count_instances` would count it, but
+/// every line of the grid is scanned only once, for all patterns and their
+/// reverses together, with an Aho-Corasick automaton.
+///
+/// # Arguments
+///
+/// * `input` - A 2D array of characters to search through
+/// * `searches` - The patterns to search for
+///
+/// # Returns
+///
+/// * `Result<Vec<i32>, AppError>` - The number of instances of each pattern, in
+///   the order given, or an error
+pub fn count_instances_multi(input: &Array2<char>, searches: &[&str]) -> Result<Vec<i32>, AppError> {
+    // Each distinct needle and the patterns it counts towards; a palindrome
+    // only contributes itself so it isn't counted twice
+    let mut needles: Vec<String> = Vec::new();
+    let mut owners: Vec<Vec<usize>> = Vec::new();
+    for (index, search) in searches.iter().enumerate().filter(|(_, search)| !search.is_empty()) {
+        let reversed: String = search.chars().rev().collect();
+        let mut variants = vec![search.to_string()];
+        if reversed != *search {
+            variants.push(reversed);
+        }
+        for variant in variants {
+            match needles.iter().position(|needle| *needle == variant) {
+                Some(existing) => owners[existing].push(index),
+                None => {
+                    needles.push(variant);
+                    owners.push(vec![index]);
                 }
             }
-            // Down-left diagonal
-            if i + search_len <= rows && j >= search_len - 1 {
-                let diag_chars: Vec<char> = (0..search_len).map(|k| input[[i + k, j - k]]).collect();
-                if diag_chars == search_chars || diag_chars == search_reverse {
-                    num_instances += 1;
-                }
+        }
+    }
+
+    let mut counts = vec![0; searches.len()];
+    if needles.is_empty() {
+        return Ok(counts);
+    }
+    let automaton = AhoCorasick::new(&needles).map_err(|_| AppError::ArgError("patterns are too large to search for"))?;
+    for line in grid_lines(input) {
+        for found in automaton.find_overlapping_iter(&line) {
+            for &index in &owners[found.pattern().as_usize()] {
+                counts[index] += 1;
             }
         }
     }
 
-    Ok(num_instances)
+    Ok(counts)
 }
 
 /// Searches for X-shaped patterns in an Array2 of characters.
-/// An X-pattern consists of a three-character string where:
+/// An X-pattern consists of an odd-length string where:
 /// - The middle character is at the center
-/// - The first and last characters form an X shape around the center
+/// - The string runs along both diagonals, which cross at the center
 /// - The pattern can be read in either direction along both diagonals
 ///
 /// # Arguments
 ///
 /// * `input` - A 2D array of characters to search through
-/// * `search` - A three-character string to search for
+/// * `search` - An odd-length string to search for; even-length and empty
+///   strings never form an X
 ///
 /// # Returns
 ///
@@ -83,38 +330,59 @@ pub fn count_instances(input: &Array2<char>, search: &str) -> Result<i32, AppErr
 ///   A
 /// M   S
 /// ```
+/// and for "XMASX":
+/// ```text
+/// X       X
+///   M   M
+///     A
+///   S   S
+/// X       X
+/// ```
 pub fn count_x_instances(input: &Array2<char>, search: &str) -> Result<i32, AppError> {
-    let mut num_instances = 0;
+    Ok(find_x_instances(input, search).len() as i32)
+}
+
+/// Finds every X-pattern of `search`, as `count_x_instances` counts them.
+///
+/// # Arguments
+///
+/// * `input` - A 2D array of characters to search through
+/// * `search` - An odd-length string to search for
+///
+/// # Returns
+///
+/// * `Vec<(usize, usize)>` - The (row, col) of each X's center, in row-major order
+pub fn find_x_instances(input: &Array2<char>, search: &str) -> Vec<(usize, usize)> {
+    let mut centers = Vec::new();
     let (rows, cols) = input.dim();
+    let chars: Vec<char> = search.chars().collect();
+    let len = chars.len();
 
-    // Need at least 3x3 area to form an X pattern
-    if search.len() != 3 || rows < 3 || cols < 3 {
-        return Ok(0);
+    // Need an odd length, and at least a len x len area to form an X pattern
+    if len.is_multiple_of(2) || rows < len || cols < len {
+        return centers;
     }
+    let half = len / 2;
 
-    let chars: Vec<char> = search.chars().collect();
+    // Whether the diagonal through `cells` reads as the pattern either way round
+    let reads = |cells: &dyn Fn(usize) -> (usize, usize)| {
+        (0..len).all(|k| input[cells(k)] == chars[k]) || (0..len).all(|k| input[cells(k)] == chars[len - 1 - k])
+    };
 
-    // Check each possible 3x3 grid center point
-    for i in 1..rows - 1 {
-        for j in 1..cols - 1 {
+    // Check each possible center point
+    for i in half..rows - half {
+        for j in half..cols - half {
             // Check if center is the middle character
-            if input[[i, j]] == chars[1] {
-                // Check top-left to bottom-right corners
-                if (input[[i - 1, j - 1]] == chars[0] && input[[i + 1, j + 1]] == chars[2])
-                    || (input[[i - 1, j - 1]] == chars[2] && input[[i + 1, j + 1]] == chars[0])
-                {
-                    // Check top-right to bottom-left corners
-                    if (input[[i - 1, j + 1]] == chars[0] && input[[i + 1, j - 1]] == chars[2])
-                        || (input[[i - 1, j + 1]] == chars[2] && input[[i + 1, j - 1]] == chars[0])
-                    {
-                        num_instances += 1;
-                    }
+            if input[[i, j]] == chars[half] {
+                // Check top-left to bottom-right, then top-right to bottom-left
+                if reads(&|k| (i - half + k, j - half + k)) && reads(&|k| (i - half + k, j + half - k)) {
+                    centers.push((i, j));
                 }
             }
         }
     }
 
-    Ok(num_instances)
+    centers
 }
 
 #[cfg(test)]
@@ -136,6 +404,137 @@ mod tests {
         Ok(())
     }
 
+    /// Tests restricting the search to some directions
+    #[test]
+    fn test_directions() -> Result<(), Box<dyn Error>> {
+        let input = read_file("data/inputtest")?;
+        let count = |directions| count_instances_with(&input, "XMAS", Topology::Flat, directions);
+        assert_eq!(count(Directions::ALL)?, 18);
+        assert_eq!(count(Directions::HORIZONTAL)?, 3);
+        assert_eq!(count(Directions::HORIZONTAL | Directions::REVERSED)?, 5);
+        let forward = Directions::HORIZONTAL | Directions::VERTICAL | Directions::DIAG_DOWN_RIGHT | Directions::DIAG_DOWN_LEFT;
+        assert_eq!(count(forward)?, 6);
+        assert_eq!(count(forward | Directions::REVERSED)?, 18);
+        assert_eq!(Directions::default(), forward | Directions::REVERSED);
+
+        // A palindrome is still only counted once along each line
+        let input = Array2::from_shape_vec((1, 3), "ABA".chars().collect())?;
+        let count = count_instances_with(&input, "ABA", Topology::Flat, Directions::HORIZONTAL | Directions::REVERSED)?;
+        assert_eq!(count, 1);
+        Ok(())
+    }
+
+    /// Tests searching a grid of non-ASCII letters
+    #[test]
+    fn test_unicode_instances() -> Result<(), Box<dyn Error>> {
+        let input = crate::file_io::parse_grid("ΑΒΓΔ\nΒΒΓΑ\nΓΓΓΑ\nΔΑΑΔ\n")?;
+        assert_eq!(input.dim(), (4, 4));
+        // Row 0, column 0 and the main diagonal
+        assert_eq!(count_instances(&input, "ΑΒΓΔ")?, 3);
+        assert_eq!(count_x_instances(&input, "ΑΒΓ")?, 0);
+        Ok(())
+    }
+
+    /// Tests the find_instances function
+    #[test]
+    fn test_find_instances() -> Result<(), Box<dyn Error>> {
+        let input = read_file("data/inputtest")?;
+        let matches = find_instances(&input, "XMAS");
+        assert_eq!(matches.len(), 18);
+        assert!(matches.contains(&Match {
+            start: (0, 5),
+            direction: Direction::Horizontal,
+            orientation: Orientation::Forward,
+        }));
+        assert!(matches.contains(&Match {
+            start: (1, 1),
+            direction: Direction::Horizontal,
+            orientation: Orientation::Backward,
+        }));
+
+        // XS
+        // AX
+        let input = Array2::from_shape_vec((2, 2), "XSAX".chars().collect())?;
+        let matches = find_instances(&input, "XS");
+        assert_eq!(
+            matches,
+            vec![
+                Match { start: (0, 0), direction: Direction::Horizontal, orientation: Orientation::Forward },
+                Match { start: (0, 1), direction: Direction::Vertical, orientation: Orientation::Backward },
+            ]
+        );
+        assert_eq!(matches[1].cells(2).collect::<Vec<_>>(), vec![(0, 1), (1, 1)]);
+
+        // A palindrome is counted once along each line, single characters
+        // once per axis
+        let input = Array2::from_shape_vec((1, 3), "ABA".chars().collect())?;
+        assert_eq!(find_instances(&input, "ABA").len(), 1);
+        assert_eq!(find_instances(&input, "B").len(), 4);
+        Ok(())
+    }
+
+    /// Tests count_x_instances with a five-character pattern
+    #[test]
+    fn test_num_x_instances_longer() -> Result<(), Box<dyn Error>> {
+        // X...X
+        // .M.M.
+        // ..A..
+        // .S.S.
+        // X...X
+        let input = Array2::from_shape_vec((5, 5), "X...X.M.M...A...S.S.X...X".chars().collect())?;
+        assert_eq!(count_x_instances(&input, "XMASX")?, 1);
+        assert_eq!(count_x_instances(&input, "XSAMX")?, 1);
+        assert_eq!(count_x_instances(&input, "MA")?, 0);
+        Ok(())
+    }
+
+    /// Tests searching with lines that wrap around the edges
+    #[test]
+    fn test_torus() -> Result<(), Box<dyn Error>> {
+        // ASX
+        // ...
+        // M..
+        let input = Array2::from_shape_vec((3, 3), "ASX...M..".chars().collect())?;
+        assert_eq!(count_instances(&input, "XAS")?, 0);
+        assert_eq!(count_instances_with(&input, "XAS", Topology::Torus, Directions::ALL)?, 1);
+        let matches = find_instances_with(&input, "SAX", Topology::Torus, Directions::ALL);
+        assert_eq!(
+            matches,
+            vec![Match { start: (0, 2), direction: Direction::Horizontal, orientation: Orientation::Backward }]
+        );
+        assert_eq!(matches[0].cells_wrapping(3, input.dim()).collect::<Vec<_>>(), vec![(0, 2), (0, 0), (0, 1)]);
+
+        // A match may not reuse cells, even if the pattern fits around the line twice
+        let input = Array2::from_shape_vec((1, 2), "AB".chars().collect())?;
+        // Left and right, and the diagonals, all step to the same neighbour,
+        // so "AB" is only found once
+        assert_eq!(count_instances_with(&input, "AB", Topology::Torus, Directions::ALL)?, 1);
+        assert_eq!(count_instances_with(&input, "ABA", Topology::Torus, Directions::ALL)?, 0);
+
+        let input = read_file("data/inputtest")?;
+        assert!(count_instances_with(&input, "XMAS", Topology::Torus, Directions::ALL)? >= 18);
+        Ok(())
+    }
+
+    /// Tests count_instances_multi against one count_instances per pattern
+    #[test]
+    fn test_count_instances_multi() -> Result<(), Box<dyn Error>> {
+        let input = read_file("data/inputtest")?;
+        let searches = ["XMAS", "SAMX", "MAS", "A", "", "XMAS"];
+        let counts = count_instances_multi(&input, &searches)?;
+        let expected: Vec<i32> = searches
+            .iter()
+            .map(|search| count_instances(&input, search))
+            .collect::<Result<_, _>>()?;
+        assert_eq!(counts, expected);
+        assert_eq!(counts[0], 18);
+
+        // A palindrome is counted once along each line
+        let input = Array2::from_shape_vec((1, 3), "ABA".chars().collect())?;
+        assert_eq!(count_instances_multi(&input, &["ABA"])?, vec![1]);
+        Ok(())
+    }
+
     /// Tests the count_x_instances function
     #[test]
     fn test_num_x_mas_instances() -> Result<(), Box<dyn Error>> {
@@ -0,0 +1,70 @@
+//! Word search in a cube of characters, along all 26 directions.
+
+use aoc_common::AppError;
+use ndarray::Array3;
+use rayon::prelude::*;
+
+use crate::calculations::Topology;
+use crate::search::{axes, Search};
+
+/// Searches for instances of a string pattern in an Array3 of characters.
+/// The search is performed along all 13 lines through each cell, and the
+/// pattern can be found forwards or backwards along each, covering all 26
+/// direction vectors. A palindrome is only counted once per position.
+///
+/// Layers are searched in parallel and their counts summed.
+///
+/// # Arguments
+///
+/// * `input` - A 3D array of characters, indexed by (layer, row, col)
+/// * `search` - The pattern to search for
+///
+/// # Returns
+///
+/// * `Result<i32, AppError>` - The number of pattern instances found, or an error
+pub fn count_instances_3d(input: &Array3<char>, search: &str) -> Result<i32, AppError> {
+    let (layers, rows, cols) = input.dim();
+    let search = Search::new(search, [layers, rows, cols], &axes::<3>(), Topology::Flat, |_, _| true);
+
+    let num_instances = (0..layers)
+        .into_par_iter()
+        .map(|layer| {
+            let mut layer_instances = 0;
+            for row in 0..rows {
+                for col in 0..cols {
+                    search.starting_at(|position| input[position], [layer, row, col], |_, _, _| layer_instances += 1);
+                }
+            }
+            layer_instances
+        })
+        .sum();
+    Ok(num_instances)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculations::count_instances;
+    use crate::file_io::parse_cube;
+    use ndarray::Array2;
+    use std::error::Error;
+
+    #[test]
+    fn test_count_instances_3d() -> Result<(), Box<dyn Error>> {
+        // XMAS along a layer, down the layers, and along the space diagonal
+        let cube = parse_cube("X..S\n.M..\n....\nXMAS\n\n....\n.M..\n....\nM...\n\n....\n....\n..A.\nA...\n\n....\n....\n....\nS..S\n")?;
+        assert_eq!(cube.dim(), (4, 4, 4));
+        assert_eq!(count_instances_3d(&cube, "XMAS")?, 3);
+        Ok(())
+    }
+
+    /// A single layer has the same instances as the 2D grid
+    #[test]
+    fn test_single_layer_matches_2d() -> Result<(), Box<dyn Error>> {
+        let content = std::fs::read_to_string("data/inputtest")?;
+        let cube = parse_cube(&content)?;
+        let grid: Array2<char> = cube.index_axis(ndarray::Axis(0), 0).to_owned();
+        assert_eq!(count_instances_3d(&cube, "XMAS")?, count_instances(&grid, "XMAS")?);
+        Ok(())
+    }
+}
@@ -1,6 +1,6 @@
-use ndarray::Array2;
+use ndarray::{Array2, Array3};
 
-use crate::AppError;
+use aoc_common::AppError;
 
 /// Reads a file and converts its contents into a 2D array of characters.
 /// Each line in the file becomes a row in the array.
@@ -20,10 +20,161 @@ use crate::AppError;
 /// - The file contains lines of different lengths
 pub fn read_file(filename: &str) -> Result<Array2<char>, AppError> {
     let content = std::fs::read_to_string(filename)?;
+    parse_grid(&content)
+}
+
+/// Converts puzzle input text into a 2D array of characters.
+/// Each line becomes a row in the array, one column per `char`, so non-ASCII
+/// letters take one cell each. Empty input gives an empty array.
+///
+/// # Arguments
+///
+/// * `content` - The puzzle input text
+///
+/// # Returns
+///
+/// * `Result<Array2<char>, AppError>` - A 2D array of characters, or
+///   `AppError::RaggedInput` for the first line whose length differs from the
+///   first line's
+pub fn parse_grid(content: &str) -> Result<Array2<char>, AppError> {
     let lines: Vec<&str> = content.lines().collect();
-    let rows = lines.len();
-    let cols = lines[0].len();
+    let cols = lines.first().map_or(0, |line| line.chars().count());
+    let widths = lines.iter().map(|line| line.chars().count());
+    if let Some((index, got)) = widths.enumerate().find(|&(_, width)| width != cols) {
+        return Err(AppError::RaggedInput { line: index + 1, expected: cols, got });
+    }
 
     let data: Vec<char> = lines.join("").chars().collect();
-    Array2::from_shape_vec((rows, cols), data).map_err(|_| AppError::Array2CreationError)
+    Array2::from_shape_vec((lines.len(), cols), data).map_err(|_| AppError::Array2CreationError)
+}
+
+/// Like `parse_grid`, but pads lines shorter than the longest one with `fill`
+/// instead of rejecting them.
+///
+/// # Arguments
+///
+/// * `content` - The puzzle input text
+/// * `fill` - The character appended to short lines
+///
+/// # Returns
+///
+/// * `Result<Array2<char>, AppError>` - A 2D array of characters as wide as the
+///   longest line, or an error
+pub fn parse_grid_padded(content: &str, fill: char) -> Result<Array2<char>, AppError> {
+    let lines: Vec<&str> = content.lines().collect();
+    let cols = lines.iter().map(|line| line.chars().count()).max().unwrap_or(0);
+
+    let mut data: Vec<char> = Vec::with_capacity(lines.len() * cols);
+    for line in &lines {
+        let start = data.len();
+        data.extend(line.chars());
+        data.extend(std::iter::repeat_n(fill, cols - (data.len() - start)));
+    }
+    Array2::from_shape_vec((lines.len(), cols), data).map_err(|_| AppError::Array2CreationError)
+}
+
+/// Reads a file of blank-line-separated layers and converts it into a 3D
+/// array of characters, indexed by (layer, row, col).
+///
+/// # Arguments
+///
+/// * `filename` - Path to the input file
+///
+/// # Returns
+///
+/// * `Result<Array3<char>, AppError>` - A 3D array of characters from the file, or an error
+pub fn read_cube(filename: &str) -> Result<Array3<char>, AppError> {
+    let content = std::fs::read_to_string(filename)?;
+    parse_cube(&content)
+}
+
+/// Converts text of blank-line-separated layers into a 3D array of characters.
+/// Each layer is parsed like `parse_grid`.
+///
+/// # Arguments
+///
+/// * `content` - The layers, separated by blank lines
+///
+/// # Returns
+///
+/// * `Result<Array3<char>, AppError>` - A 3D array of characters, or an error if
+///   the layers have different numbers of lines or `AppError::RaggedInput` if
+///   their lines have different lengths
+pub fn parse_cube(content: &str) -> Result<Array3<char>, AppError> {
+    // Each line is kept with its 1-based line number, for errors
+    let mut layers: Vec<Vec<(usize, &str)>> = vec![Vec::new()];
+    for (index, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            if !layers.last().is_some_and(Vec::is_empty) {
+                layers.push(Vec::new());
+            }
+        } else {
+            layers.last_mut().expect("layers is never empty").push((index + 1, line));
+        }
+    }
+    if layers.last().is_some_and(Vec::is_empty) {
+        layers.pop();
+    }
+
+    let depth = layers.len();
+    let rows = layers.first().map_or(0, Vec::len);
+    let cols = layers.first().and_then(|layer| layer.first()).map_or(0, |(_, line)| line.chars().count());
+    if layers.iter().any(|layer| layer.len() != rows) {
+        return Err(AppError::Array2CreationError);
+    }
+    let widths = layers.iter().flatten().map(|&(line, text)| (line, text.chars().count()));
+    if let Some((line, got)) = widths.into_iter().find(|&(_, width)| width != cols) {
+        return Err(AppError::RaggedInput { line, expected: cols, got });
+    }
+
+    let data: Vec<char> = layers.iter().flatten().flat_map(|(_, line)| line.chars()).collect();
+    Array3::from_shape_vec((depth, rows, cols), data).map_err(|_| AppError::Array2CreationError)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_grid_ragged() {
+        let result = parse_grid("XMAS\nXMA\nXMAS\n");
+        assert!(matches!(result, Err(AppError::RaggedInput { line: 2, expected: 4, got: 3 })));
+    }
+
+    #[test]
+    fn test_parse_grid_unicode() -> Result<(), AppError> {
+        let grid = parse_grid("ÄÖÜ\nαβγ\n日本語\n")?;
+        assert_eq!(grid.dim(), (3, 3));
+        assert_eq!(grid[[1, 2]], 'γ');
+        assert!(matches!(parse_grid("ÄÖÜ\nab\n"), Err(AppError::RaggedInput { line: 2, expected: 3, got: 2 })));
+
+        let grid = parse_grid_padded("日本\nα\n", '.')?;
+        assert_eq!(grid.row(1).to_vec(), vec!['α', '.']);
+
+        let cube = parse_cube("αβ\nγδ\n\nεζ\nηθ\n")?;
+        assert_eq!(cube.dim(), (2, 2, 2));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_grid_empty() -> Result<(), AppError> {
+        assert_eq!(parse_grid("")?.dim(), (0, 0));
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_cube_ragged() {
+        let result = parse_cube("AB\nCD\n\nEF\nG\n");
+        assert!(matches!(result, Err(AppError::RaggedInput { line: 5, expected: 2, got: 1 })));
+    }
+
+    #[test]
+    fn test_parse_grid_padded() -> Result<(), AppError> {
+        let grid = parse_grid_padded("XM\nXMAS\n\nX\n", '.')?;
+        assert_eq!(grid.dim(), (4, 4));
+        assert_eq!(grid.row(0).to_vec(), vec!['X', 'M', '.', '.']);
+        assert_eq!(grid.row(2).to_vec(), vec!['.'; 4]);
+        assert_eq!(grid.row(3).to_vec(), vec!['X', '.', '.', '.']);
+        Ok(())
+    }
 }
@@ -0,0 +1,101 @@
+//! PNG heatmap of how many pattern matches pass through each grid cell.
+
+use ndarray::Array2;
+use std::fs::File;
+use std::io::{self, BufWriter};
+
+use aoc_common::AppError;
+
+use crate::calculations::{find_instances_with, Directions, Topology};
+
+/// Width and height in pixels of one grid cell in the rendered image
+const CELL_PIXELS: usize = 4;
+
+/// Counts, for every cell, how many instances of `search` pass through it.
+///
+/// Matches are found by `find_instances`: horizontally, vertically and
+/// diagonally, forwards or backwards.
+///
+/// # Arguments
+///
+/// * `input` - A 2D array of characters to search through
+/// * `search` - The pattern to search for
+///
+/// # Returns
+///
+/// * `Array2<u32>` - The number of matches covering each cell
+pub fn match_density(input: &Array2<char>, search: &str) -> Array2<u32> {
+    match_density_with(input, search, Topology::default())
+}
+
+/// Like `match_density`, with lines that behave at the edges as `topology` says
+pub fn match_density_with(input: &Array2<char>, search: &str, topology: Topology) -> Array2<u32> {
+    let mut density = Array2::zeros(input.dim());
+    let search_len = search.chars().count();
+
+    for found in find_instances_with(input, search, topology, Directions::ALL) {
+        for cell in found.cells_wrapping(search_len, input.dim()) {
+            density[cell] += 1;
+        }
+    }
+
+    density
+}
+
+/// Writes a match density grid as a greyscale PNG, brighter cells having more matches.
+///
+/// # Arguments
+///
+/// * `density` - Match counts per cell, as returned by `match_density`
+/// * `path` - Path of the PNG file to write
+///
+/// # Returns
+///
+/// * `Result<(), AppError>` - Success or an error if the image cannot be written
+pub fn write_heatmap_png(density: &Array2<u32>, path: &str) -> Result<(), AppError> {
+    let (rows, cols) = density.dim();
+    let (width, height) = (cols * CELL_PIXELS, rows * CELL_PIXELS);
+    let max = density.iter().copied().max().unwrap_or(0).max(1);
+
+    let mut pixels = vec![0u8; width * height];
+    for ((row, col), &count) in density.indexed_iter() {
+        let brightness = (count * 255 / max) as u8;
+        for y in row * CELL_PIXELS..(row + 1) * CELL_PIXELS {
+            pixels[y * width + col * CELL_PIXELS..y * width + (col + 1) * CELL_PIXELS].fill(brightness);
+        }
+    }
+
+    let file = File::create(path)?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), width as u32, height as u32);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(io::Error::from)?;
+    writer.write_image_data(&pixels).map_err(io::Error::from)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculations::count_instances;
+    use crate::file_io::read_file;
+    use std::error::Error;
+
+    /// Every match covers exactly one cell per pattern character
+    #[test]
+    fn test_match_density_total() -> Result<(), Box<dyn Error>> {
+        let input = read_file("data/inputtest")?;
+        let density = match_density(&input, "XMAS");
+        let num_matches = count_instances(&input, "XMAS")? as u32;
+        assert_eq!(density.sum(), num_matches * 4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_match_density_cells() {
+        let input = Array2::from_shape_vec((2, 4), "XMASSAMX".chars().collect()).unwrap();
+        let density = match_density(&input, "XMAS");
+        assert_eq!(density.row(0).to_vec(), vec![1, 1, 1, 1]);
+        assert_eq!(density.row(1).to_vec(), vec![1, 1, 1, 1]);
+    }
+}
@@ -0,0 +1,79 @@
+//! Terminal reprint of the grid with matched letters coloured, for `--highlight`.
+
+use ndarray::Array2;
+
+use crate::calculations::{find_instances_with, find_x_instances, Directions, Topology};
+
+/// ANSI colour of letters in a straight match only
+const LINE_COLOR: u8 = 33;
+/// ANSI colour of letters in an X-shaped match only
+const CROSS_COLOR: u8 = 36;
+/// ANSI colour of letters in both kinds of match
+const BOTH_COLOR: u8 = 35;
+
+/// Reprints the grid with every letter of a straight match of `search` and
+/// of an X-shaped match of `x_search` coloured: yellow, cyan, or magenta if
+/// the letter is part of both.
+///
+/// # Arguments
+///
+/// * `input` - A 2D array of characters
+/// * `search` - The pattern found by `find_instances_with`
+/// * `x_search` - The pattern found by `find_x_instances`
+/// * `topology` - How the straight matches behave at the edges
+///
+/// # Returns
+///
+/// * `String` - One line per row, with ANSI escapes around coloured letters
+pub fn render_highlight(input: &Array2<char>, search: &str, x_search: &str, topology: Topology) -> String {
+    let mut in_line = Array2::from_elem(input.dim(), false);
+    let search_len = search.chars().count();
+    for found in find_instances_with(input, search, topology, Directions::ALL) {
+        for cell in found.cells_wrapping(search_len, input.dim()) {
+            in_line[cell] = true;
+        }
+    }
+
+    let mut in_cross = Array2::from_elem(input.dim(), false);
+    let half = x_search.chars().count() / 2;
+    for (i, j) in find_x_instances(input, x_search) {
+        for k in 0..=2 * half {
+            in_cross[[i - half + k, j - half + k]] = true;
+            in_cross[[i - half + k, j + half - k]] = true;
+        }
+    }
+
+    let mut text = String::new();
+    for ((row, col), &c) in input.indexed_iter() {
+        match (in_line[[row, col]], in_cross[[row, col]]) {
+            (false, false) => text.push(c),
+            (true, false) => text.push_str(&format!("\x1b[{}m{}\x1b[0m", LINE_COLOR, c)),
+            (false, true) => text.push_str(&format!("\x1b[{}m{}\x1b[0m", CROSS_COLOR, c)),
+            (true, true) => text.push_str(&format!("\x1b[{}m{}\x1b[0m", BOTH_COLOR, c)),
+        }
+        if col + 1 == input.ncols() {
+            text.push('\n');
+        }
+    }
+    text
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn test_render_highlight() -> Result<(), Box<dyn Error>> {
+        // M.S
+        // XAS
+        // M.S
+        let input = Array2::from_shape_vec((3, 3), "M.SXASM.S".chars().collect())?;
+        let text = render_highlight(&input, "XAS", "MAS", Topology::Flat);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines[0], "\x1b[36mM\x1b[0m.\x1b[36mS\x1b[0m");
+        assert_eq!(lines[1], "\x1b[33mX\x1b[0m\x1b[35mA\x1b[0m\x1b[33mS\x1b[0m");
+        assert_eq!(lines[2], "\x1b[36mM\x1b[0m.\x1b[36mS\x1b[0m");
+        Ok(())
+    }
+}
@@ -0,0 +1,52 @@
+//! Day 4: Ceres Search
+//!
+//! Library entry points shared by the binary and the `aoc` runner.
+
+pub mod calculations;
+pub mod cube;
+pub mod file_io;
+pub mod heatmap;
+pub mod highlight;
+mod search;
+pub mod stats;
+
+use aoc_common::{AppError, Solver};
+use ndarray::Array2;
+
+use calculations::{count_instances, count_x_instances};
+use file_io::parse_grid;
+
+/// Solver for the word search
+pub struct Day04;
+
+impl Solver for Day04 {
+    /// The word search grid
+    type Parsed = Array2<char>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AppError> {
+        parse_grid(input)
+    }
+
+    /// Counts XMAS in every direction
+    fn part1(grid: &Self::Parsed) -> Result<String, AppError> {
+        Ok(count_instances(grid, "XMAS")?.to_string())
+    }
+
+    /// Counts MAS crossed in an X
+    fn part2(grid: &Self::Parsed) -> Result<String, AppError> {
+        Ok(count_x_instances(grid, "MAS")?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<(), AppError> {
+        let parsed = Day04::parse(&std::fs::read_to_string("data/inputtest")?)?;
+        assert_eq!(Day04::part1(&parsed)?, "18");
+        assert_eq!(Day04::part2(&parsed)?, "9");
+        Ok(())
+    }
+}
@@ -8,39 +8,148 @@
 //!
 //! ```bash
 //! cargo run -- path/to/input/file
+//! cargo run -- path/to/input/file --heatmap xmas.png
+//! cargo run -- path/to/input/file --wrap
+//! cargo run -- path/to/input/file --highlight
+//! cargo run -- path/to/input/file --stats
+//! cargo run -- path/to/input/file --pad .
 //! ```
+//!
+//! With `--heatmap`, a PNG is written where each cell's brightness reflects how
+//! many XMAS matches pass through it. With `--wrap`, XMAS may also wrap around
+//! the edges of the grid, as if it were a torus. With `--highlight`, the grid
+//! is reprinted with XMAS letters in yellow, X-MAS letters in cyan and letters
+//! in both in magenta. `--stats` reports how the XMAS matches overlap.
+//!
+//! Lines of different lengths are rejected unless `--pad` gives a character to
+//! pad the shorter ones with. Without an input path, the input cached by
+//! `aoc fetch` is used.
 use std::error::Error;
+use std::time::Instant;
 
-// Internal imports
-mod calculations;
-mod errors;
-mod file_io;
-
-use calculations::{count_instances, count_x_instances};
-use errors::AppError;
-use file_io::read_file;
+use day_04::calculations::{count_instances_with, count_x_instances, Directions, Topology};
+use aoc_common::input::resolve_input;
+use aoc_common::logging;
+use aoc_common::messages;
+use aoc_common::msg;
+use aoc_common::output::{take_format, Format, Report};
+use aoc_common::AppError;
+use day_04::file_io::{parse_grid, parse_grid_padded};
+use day_04::heatmap::{match_density_with, write_heatmap_png};
+use day_04::highlight::render_highlight;
+use day_04::stats::match_stats;
 
 /// Main function that processes the input file and reports pattern matches.
 ///
 /// # Arguments
 ///
-/// Expects one command-line argument: the path to the input file
+/// Takes an optional path to the input file, optionally followed by
+/// `--heatmap <path.png>`, and `--wrap`, `--highlight`, `--stats`,
+/// `--pad <char>` and `--format <text|json>` anywhere
 ///
 /// # Returns
 ///
 /// * `Result<(), Box<dyn Error>>` - Success or an error if the file cannot be processed
 fn main() -> Result<(), Box<dyn Error>> {
-    println!("Welcome to Day 4!");
-    let path = std::env::args()
-        .nth(1)
-        .ok_or(AppError::ArgError("No input file provided"))?;
-    let input = read_file(&path)?;
-
-    let num_xmas_instances = count_instances(&input, "XMAS")?;
-    println!("Instances of XMAS: {}", num_xmas_instances);
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let format = take_format(&mut args)?;
+    logging::init(logging::take_verbosity(&mut args));
+    messages::init(messages::take_lang(&mut args)?.as_deref())?;
+    let topology = match args.iter().position(|arg| arg == "--wrap") {
+        Some(index) => {
+            args.remove(index);
+            Topology::Torus
+        }
+        None => Topology::Flat,
+    };
+    let highlight = match args.iter().position(|arg| arg == "--highlight") {
+        Some(index) => {
+            args.remove(index);
+            true
+        }
+        None => false,
+    };
+    let stats = match args.iter().position(|arg| arg == "--stats") {
+        Some(index) => {
+            args.remove(index);
+            true
+        }
+        None => false,
+    };
+    if stats && format == Format::Json {
+        return Err(Box::new(AppError::ArgError("--stats can't be combined with --format json")));
+    }
+    let fill = match args.iter().position(|arg| arg == "--pad") {
+        Some(index) => {
+            let fill = args.get(index + 1).and_then(|arg| {
+                let mut chars = arg.chars();
+                chars.next().filter(|_| chars.next().is_none())
+            });
+            let fill = fill.ok_or(AppError::ArgError("--pad requires a single character"))?;
+            args.drain(index..=index + 1);
+            Some(fill)
+        }
+        None => None,
+    };
+    if highlight && format == Format::Json {
+        return Err(Box::new(AppError::ArgError("--highlight can't be combined with --format json")));
+    }
+    if format == Format::Text {
+        println!("{}", msg!("welcome", 4));
+    }
+    let (path, heatmap_path) = match args.as_slice() {
+        [] => (None, None),
+        [path] => (Some(path), None),
+        [flag, out] if flag == "--heatmap" => (None, Some(out)),
+        [path, flag, out] if flag == "--heatmap" => (Some(path), Some(out)),
+        _ => {
+            return Err(Box::new(AppError::ArgError(
+                "Usage: day_04 [<input>] [--heatmap <path.png>] [--wrap] [--highlight] [--stats] [--pad <char>] [--format <text|json>] [-v|-vv]",
+            )))
+        }
+    };
+    let content = std::fs::read_to_string(resolve_input(path.map(String::as_str), 4)?)?;
+    let input = match fill {
+        Some(fill) => parse_grid_padded(&content, fill)?,
+        None => parse_grid(&content)?,
+    };
 
+    let start = Instant::now();
+    let num_xmas_instances = count_instances_with(&input, "XMAS", topology, Directions::ALL)?;
     let num_x_mas_instances = count_x_instances(&input, "MAS")?;
-    println!("Instances of MAS in X shape: {}", num_x_mas_instances);
+    let elapsed = start.elapsed();
+
+    match format {
+        Format::Text => {
+            println!("{}", msg!("day04-part1", num_xmas_instances));
+            println!("{}", msg!("day04-part2", num_x_mas_instances));
+            if highlight {
+                print!("{}", render_highlight(&input, "XMAS", "MAS", topology));
+            }
+            if stats {
+                let stats = match_stats(&input, "XMAS", topology);
+                println!("{}", msg!("day04-matched-cells", stats.matched_cells));
+                println!("{}", msg!("day04-sharing", stats.sharing_matches, stats.matches));
+                if let Some(((row, col), count)) = stats.densest {
+                    println!("{}", msg!("day04-densest", row, col, count));
+                }
+            }
+        }
+        Format::Json => {
+            let report = Report {
+                day: 4,
+                part1: Some(num_xmas_instances.to_string()),
+                part2: Some(num_x_mas_instances.to_string()),
+                elapsed,
+            };
+            println!("{}", report.to_json());
+        }
+    }
+
+    if let Some(out) = heatmap_path {
+        write_heatmap_png(&match_density_with(&input, "XMAS", topology), out)?;
+        eprintln!("{}", msg!("day04-wrote-heatmap", out));
+    }
 
     Ok(())
 }
@@ -0,0 +1,177 @@
+//! Dimension-generic core of the word search.
+//!
+//! Every cell is tried as the first character of the pattern, reading along
+//! each direction vector in turn. The 2D search in `calculations` and the 3D
+//! search in `cube` both wrap a `Search` over their grid's shape.
+
+use crate::calculations::{Orientation, Topology};
+
+/// One direction vector per line through a cell: the nonzero vectors with
+/// components in {-1, 0, 1} whose first nonzero component is positive. Their
+/// negations make up the rest, so there are 4 of them in 2D and 13 in 3D.
+pub(crate) fn axes<const N: usize>() -> Vec<[isize; N]> {
+    let mut axes = Vec::new();
+    for code in 0..3usize.pow(N as u32) {
+        let mut axis = [0; N];
+        let mut rest = code;
+        for component in axis.iter_mut().rev() {
+            *component = (rest % 3) as isize - 1;
+            rest /= 3;
+        }
+        if axis.iter().find(|&&c| c != 0).is_some_and(|&c| c > 0) {
+            axes.push(axis);
+        }
+    }
+    axes
+}
+
+/// One way of reading the pattern: along an axis, in one orientation
+#[derive(Debug, Clone, Copy)]
+struct Reading<const N: usize> {
+    /// Index of the axis in the list given to `Search::new`
+    axis: usize,
+    orientation: Orientation,
+    /// The step from one character to the next
+    step: [isize; N],
+}
+
+/// A search for one pattern in grids of one shape
+#[derive(Debug)]
+pub(crate) struct Search<'a, const N: usize> {
+    search: &'a str,
+    len: isize,
+    shape: [usize; N],
+    topology: Topology,
+    readings: Vec<Reading<N>>,
+}
+
+impl<'a, const N: usize> Search<'a, N> {
+    /// Prepares the readings of `search` along `axes`, each forwards and
+    /// backwards where `enabled` allows it.
+    ///
+    /// A palindrome reads the same both ways along an axis, so it is only
+    /// read forwards. On a torus, readings whose steps land on the same cells
+    /// as an earlier reading, such as left and right on a grid two columns
+    /// wide, are dropped so no instance is counted twice, as are readings
+    /// too long to fit around their line without reusing a cell.
+    pub(crate) fn new(
+        search: &'a str,
+        shape: [usize; N],
+        axes: &[[isize; N]],
+        topology: Topology,
+        enabled: impl Fn(usize, Orientation) -> bool,
+    ) -> Self {
+        let len = search.chars().count();
+        let palindrome = search.chars().eq(search.chars().rev());
+        let reduce = |step: [isize; N]| -> [isize; N] {
+            std::array::from_fn(|d| step[d].rem_euclid(shape[d].max(1) as isize))
+        };
+
+        let mut all = Vec::new();
+        for (axis, &delta) in axes.iter().enumerate() {
+            all.push(Reading { axis, orientation: Orientation::Forward, step: delta });
+            all.push(Reading { axis, orientation: Orientation::Backward, step: delta.map(|c| -c) });
+        }
+        all.retain(|reading| enabled(reading.axis, reading.orientation));
+        let readings = all
+            .iter()
+            .enumerate()
+            .filter(|&(k, reading)| {
+                if palindrome && reading.orientation == Orientation::Backward {
+                    return false;
+                }
+                match topology {
+                    Topology::Flat => true,
+                    Topology::Torus => {
+                        let step = reduce(reading.step);
+                        !all[..k].iter().any(|earlier| reduce(earlier.step) == step)
+                            && len <= cycle_len(reading.step, shape)
+                    }
+                }
+            })
+            .map(|(_, &reading)| reading)
+            .collect();
+
+        Self { search, len: len as isize, shape, topology, readings }
+    }
+
+    /// Calls `visit` with the axis, orientation and start of every instance
+    /// whose first character is at `first`, without allocating.
+    ///
+    /// `at` gives the character at a position inside the grid. A backward
+    /// instance is described from its other end, so `start` is always the
+    /// first cell along the axis.
+    pub(crate) fn starting_at(
+        &self,
+        at: impl Fn([usize; N]) -> char,
+        first: [usize; N],
+        mut visit: impl FnMut(usize, Orientation, [usize; N]),
+    ) {
+        if self.len == 0 || self.shape.contains(&0) {
+            return;
+        }
+
+        for reading in &self.readings {
+            let cell = |k: isize| -> Option<[usize; N]> {
+                let mut position = [0; N];
+                for d in 0..N {
+                    let coordinate = first[d] as isize + reading.step[d] * k;
+                    position[d] = match self.topology {
+                        Topology::Flat if coordinate < 0 || coordinate >= self.shape[d] as isize => return None,
+                        Topology::Flat => coordinate as usize,
+                        Topology::Torus => coordinate.rem_euclid(self.shape[d] as isize) as usize,
+                    };
+                }
+                Some(position)
+            };
+            let Some(last) = cell(self.len - 1) else {
+                continue;
+            };
+
+            let found = self
+                .search
+                .chars()
+                .zip(0..)
+                .all(|(c, k)| cell(k).is_some_and(|position| at(position) == c));
+            if found {
+                let start = match reading.orientation {
+                    Orientation::Forward => first,
+                    Orientation::Backward => last,
+                };
+                visit(reading.axis, reading.orientation, start);
+            }
+        }
+    }
+}
+
+fn gcd(a: usize, b: usize) -> usize {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+/// How many steps return to the starting cell on a torus of the given shape
+fn cycle_len<const N: usize>(step: [isize; N], shape: [usize; N]) -> usize {
+    (0..N).fold(1, |cycle, d| {
+        let size = shape[d].max(1);
+        let dim_cycle = size / gcd(step[d].unsigned_abs() % size, size);
+        cycle / gcd(cycle, dim_cycle) * dim_cycle
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_axes() {
+        assert_eq!(axes::<2>(), vec![[0, 1], [1, -1], [1, 0], [1, 1]]);
+        assert_eq!(axes::<3>().len(), 13);
+    }
+
+    #[test]
+    fn test_cycle_len() {
+        assert_eq!(cycle_len([0, 1], [3, 4]), 4);
+        assert_eq!(cycle_len([1, 1], [2, 3]), 6);
+        assert_eq!(cycle_len([1, -1], [4, 6]), 12);
+        assert_eq!(cycle_len([1, 0, 1], [2, 5, 2]), 2);
+    }
+}
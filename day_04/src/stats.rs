@@ -0,0 +1,81 @@
+//! Overlap statistics of the matches in a grid, for `--stats`.
+
+use ndarray::Array2;
+
+use crate::calculations::{find_instances_with, Directions, Topology};
+use crate::heatmap::match_density_with;
+
+/// How the matches of a pattern overlap
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchStats {
+    /// The number of matches
+    pub matches: usize,
+    /// Cells that are part of at least one match
+    pub matched_cells: usize,
+    /// Matches that share at least one cell with another match
+    pub sharing_matches: usize,
+    /// The cell covered by the most matches, and how many, or `None` if
+    /// there are no matches. Ties go to the first cell in row-major order.
+    pub densest: Option<((usize, usize), u32)>,
+}
+
+/// Finds every match of `search` and works out how they overlap.
+///
+/// # Arguments
+///
+/// * `input` - A 2D array of characters to search through
+/// * `search` - The pattern to search for
+/// * `topology` - How lines behave at the edges of the grid
+///
+/// # Returns
+///
+/// * `MatchStats` - The overlap statistics
+pub fn match_stats(input: &Array2<char>, search: &str, topology: Topology) -> MatchStats {
+    let search_len = search.chars().count();
+    let matches = find_instances_with(input, search, topology, Directions::ALL);
+
+    let density = match_density_with(input, search, topology);
+
+    let sharing_matches = matches
+        .iter()
+        .filter(|found| found.cells_wrapping(search_len, input.dim()).any(|cell| density[cell] > 1))
+        .count();
+    let densest = density
+        .indexed_iter()
+        .filter(|&(_, &count)| count > 0)
+        .fold(None, |best: Option<((usize, usize), u32)>, (cell, &count)| match best {
+            Some((_, most)) if most >= count => best,
+            _ => Some((cell, count)),
+        });
+
+    MatchStats {
+        matches: matches.len(),
+        matched_cells: density.iter().filter(|&&count| count > 0).count(),
+        sharing_matches,
+        densest,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::error::Error;
+
+    #[test]
+    fn test_match_stats() -> Result<(), Box<dyn Error>> {
+        // XMAS
+        // M...
+        // A...
+        // S...
+        let input = Array2::from_shape_vec((4, 4), "XMASM...A...S...".chars().collect())?;
+        let stats = match_stats(&input, "XMAS", Topology::Flat);
+        assert_eq!(
+            stats,
+            MatchStats { matches: 2, matched_cells: 7, sharing_matches: 2, densest: Some(((0, 0), 2)) }
+        );
+
+        let stats = match_stats(&input, "QQ", Topology::Flat);
+        assert_eq!(stats, MatchStats { matches: 0, matched_cells: 0, sharing_matches: 0, densest: None });
+        Ok(())
+    }
+}
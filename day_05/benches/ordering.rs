@@ -0,0 +1,20 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use day_05::calculations::{process_sequences, sum_valid_middles};
+use day_05::file_io::split_sections;
+
+const INPUT: &str = include_str!("../data/input");
+
+fn bench_ordering(c: &mut Criterion) {
+    let (rules, updates) = split_sections(INPUT).unwrap();
+
+    c.bench_function("sum_valid_middles", |b| {
+        b.iter(|| sum_valid_middles(black_box(&rules), black_box(&updates)))
+    });
+    c.bench_function("process_sequences", |b| {
+        b.iter(|| process_sequences(black_box(&rules), black_box(&updates)))
+    });
+}
+
+criterion_group!(benches, bench_ordering);
+criterion_main!(benches);
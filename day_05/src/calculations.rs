@@ -2,6 +2,9 @@
 
 use std::collections::HashMap;
 
+/// Ordering rules: each key must appear before all of its associated values
+pub type OrderingRules = HashMap<i32, Vec<i32>>;
+
 /// Processes a set of sequences according to ordering rules and calculates a total
 /// based on the middle values of reordered sequences.
 ///
@@ -12,14 +15,15 @@ use std::collections::HashMap;
 /// # Returns
 /// Sum of middle values from reordered invalid sequences
 pub fn process_sequences(
-    ordering_rules: HashMap<i32, Vec<i32>>,
-    update_sequences: Vec<Vec<i32>>,
+    ordering_rules: &OrderingRules,
+    update_sequences: &[Vec<i32>],
 ) -> i32 {
     let mut total = 0;
 
-    for mut update in update_sequences {
-        if !is_valid_sequence(&ordering_rules, &update) {
-            reorder_sequence(&ordering_rules, &mut update);
+    for update in update_sequences {
+        if !is_valid_sequence(ordering_rules, update) {
+            let mut update = update.clone();
+            reorder_sequence(ordering_rules, &mut update);
             if let Some(middle_value) = find_middle_value(&update) {
                 total += middle_value;
             }
@@ -29,6 +33,58 @@ pub fn process_sequences(
     total
 }
 
+/// Sums the middle values of the sequences that already follow the ordering rules
+///
+/// # Arguments
+/// * `ordering_rules` - HashMap where key must appear before its associated values in sequences
+/// * `update_sequences` - Sequences to validate
+///
+/// # Returns
+/// Sum of middle values from valid sequences
+pub fn sum_valid_middles(ordering_rules: &OrderingRules, update_sequences: &[Vec<i32>]) -> i32 {
+    update_sequences
+        .iter()
+        .filter(|update| is_valid_sequence(ordering_rules, update))
+        .filter_map(|update| find_middle_value(update))
+        .sum()
+}
+
+/// Describes each update: for part 1 whether it is already in order, for
+/// part 2 how each out-of-order update is reordered
+///
+/// # Arguments
+/// * `ordering_rules` - Rules defining required ordering between numbers
+/// * `update_sequences` - Sequences to describe
+/// * `reorder` - `false` for part 1, `true` for part 2
+///
+/// # Returns
+/// One line per update, naming the middle page that counts towards the answer
+pub fn explain_sequences(ordering_rules: &OrderingRules, update_sequences: &[Vec<i32>], reorder: bool) -> Vec<String> {
+    let pages = |update: &[i32]| update.iter().map(i32::to_string).collect::<Vec<_>>().join(",");
+    (1..)
+        .zip(update_sequences)
+        .map(|(line, update)| {
+            let valid = is_valid_sequence(ordering_rules, update);
+            let middle = |update: &[i32]| find_middle_value(update).map_or("none".to_string(), |page| page.to_string());
+            match (valid, reorder) {
+                (true, false) => format!("Update {}: {} is in order, middle page {}", line, pages(update), middle(update)),
+                (false, false) | (true, true) => format!("Update {}: {} doesn't count", line, pages(update)),
+                (false, true) => {
+                    let mut reordered = update.clone();
+                    reorder_sequence(ordering_rules, &mut reordered);
+                    format!(
+                        "Update {}: {} reordered to {}, middle page {}",
+                        line,
+                        pages(update),
+                        pages(&reordered),
+                        middle(&reordered)
+                    )
+                }
+            }
+        })
+        .collect()
+}
+
 /// Checks if a sequence follows all ordering rules
 ///
 /// # Arguments
@@ -37,7 +93,7 @@ pub fn process_sequences(
 ///
 /// # Returns
 /// `true` if sequence follows all rules, `false` otherwise
-fn is_valid_sequence(ordering_rules: &HashMap<i32, Vec<i32>>, update: &Vec<i32>) -> bool {
+fn is_valid_sequence(ordering_rules: &OrderingRules, update: &[i32]) -> bool {
     for (i, &key) in update.iter().enumerate() {
         if let Some(values) = ordering_rules.get(&key) {
             for &value in values {
@@ -59,7 +115,7 @@ fn is_valid_sequence(ordering_rules: &HashMap<i32, Vec<i32>>, update: &Vec<i32>)
 ///
 /// # Returns
 /// The middle value if vector is non-empty, None otherwise
-fn find_middle_value(update: &Vec<i32>) -> Option<i32> {
+fn find_middle_value(update: &[i32]) -> Option<i32> {
     let len = update.len();
     if len == 0 {
         None
@@ -75,7 +131,7 @@ fn find_middle_value(update: &Vec<i32>) -> Option<i32> {
 /// # Arguments
 /// * `ordering_rules` - Rules defining required ordering between numbers
 /// * `update` - Sequence to reorder (modified in place)
-fn reorder_sequence(ordering_rules: &HashMap<i32, Vec<i32>>, update: &mut Vec<i32>) {
+fn reorder_sequence(ordering_rules: &OrderingRules, update: &mut [i32]) {
     let mut changed = true;
     while changed {
         changed = false;
@@ -102,7 +158,23 @@ mod tests {
     #[test]
     fn test_process_sequences() {
         let (ordering_rules, update_sequences) = read_file_and_split("data/inputtest").unwrap();
-        let total = process_sequences(ordering_rules, update_sequences);
+        let total = process_sequences(&ordering_rules, &update_sequences);
         assert_eq!(total, 123);
     }
+
+    #[test]
+    fn test_sum_valid_middles() {
+        let (ordering_rules, update_sequences) = read_file_and_split("data/inputtest").unwrap();
+        assert_eq!(sum_valid_middles(&ordering_rules, &update_sequences), 143);
+    }
+
+    #[test]
+    fn test_explain_sequences() {
+        let (ordering_rules, update_sequences) = read_file_and_split("data/inputtest").unwrap();
+        let in_order = explain_sequences(&ordering_rules, &update_sequences, false);
+        assert_eq!(in_order[0], "Update 1: 75,47,61,53,29 is in order, middle page 61");
+        assert_eq!(in_order[3], "Update 4: 75,97,47,61,53 doesn't count");
+        let reordered = explain_sequences(&ordering_rules, &update_sequences, true);
+        assert_eq!(reordered[3], "Update 4: 75,97,47,61,53 reordered to 97,75,47,61,53, middle page 47");
+    }
 }
@@ -1,5 +1,6 @@
-use crate::errors::AppError;
-use std::collections::HashMap;
+use crate::calculations::OrderingRules;
+use aoc_common::AppError;
+use tracing::debug;
 
 /// Reads the content of a file and splits it on double new lines.
 /// Returns ordering rules and updates
@@ -10,19 +11,32 @@ use std::collections::HashMap;
 ///
 /// # Returns
 ///
-/// * `Result<(HashMap<i32, Vec<i32>>, Vec<Vec<i32>>), AppError>` - A tuple containing a hashmap of ordering rules and a vector of update sequences or an error
+/// * `Result<(OrderingRules, Vec<Vec<i32>>), AppError>` - A tuple containing a hashmap of ordering rules and a vector of update sequences or an error
 pub fn read_file_and_split(
     path: &str,
-) -> Result<(HashMap<i32, Vec<i32>>, Vec<Vec<i32>>), AppError> {
+) -> Result<(OrderingRules, Vec<Vec<i32>>), AppError> {
     let content = std::fs::read_to_string(path)?;
-    println!("Read {} bytes", content.len());
+    debug!("Read {} bytes", content.len());
+    split_sections(&content)
+}
+
+/// Splits puzzle input on double new lines into ordering rules and updates
+///
+/// # Arguments
+///
+/// * `content` - The puzzle input text
+///
+/// # Returns
+///
+/// * `Result<(OrderingRules, Vec<Vec<i32>>), AppError>` - A tuple containing a hashmap of ordering rules and a vector of update sequences or an error
+pub fn split_sections(content: &str) -> Result<(OrderingRules, Vec<Vec<i32>>), AppError> {
     // Split the input file into sections based on double newlines
     let sections: Vec<&str> = content.split("\n\n").collect();
 
     // Parse the first section into ordering rules
     // Format: key|value where value must come after key in sequences
-    let mut ordering_rules: HashMap<i32, Vec<i32>> = HashMap::new();
-    if let Some(first_section) = sections.get(0) {
+    let mut ordering_rules = OrderingRules::new();
+    if let Some(first_section) = sections.first() {
         for line in first_section.lines() {
             let parts: Vec<&str> = line.split('|').collect();
             if parts.len() == 2 {
@@ -30,7 +44,7 @@ pub fn read_file_and_split(
                 let value = parts[1].parse().map_err(AppError::ParseError)?;
                 ordering_rules
                     .entry(key)
-                    .or_insert_with(Vec::new)
+                    .or_default()
                     .push(value);
             }
         }
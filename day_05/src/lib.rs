@@ -0,0 +1,51 @@
+//! Day 5: Print Queue
+//!
+//! Library entry points shared by the binary and the `aoc` runner.
+
+pub mod calculations;
+pub mod file_io;
+
+use aoc_common::{AppError, Solver};
+
+use calculations::{explain_sequences, process_sequences, sum_valid_middles, OrderingRules};
+use file_io::split_sections;
+
+/// Solver for the safety manual print queue
+pub struct Day05;
+
+impl Solver for Day05 {
+    /// The ordering rules and the update sequences
+    type Parsed = (OrderingRules, Vec<Vec<i32>>);
+
+    fn parse(input: &str) -> Result<Self::Parsed, AppError> {
+        split_sections(input)
+    }
+
+    /// Sums the middle pages of the correctly-ordered updates
+    fn part1((ordering_rules, update_sequences): &Self::Parsed) -> Result<String, AppError> {
+        Ok(sum_valid_middles(ordering_rules, update_sequences).to_string())
+    }
+
+    /// Sums the middle pages of the incorrectly-ordered updates after reordering them
+    fn part2((ordering_rules, update_sequences): &Self::Parsed) -> Result<String, AppError> {
+        Ok(process_sequences(ordering_rules, update_sequences).to_string())
+    }
+
+    /// Which updates were already in order, or how the others were reordered
+    fn explain((ordering_rules, update_sequences): &Self::Parsed, part: u32) -> Result<Vec<String>, AppError> {
+        Ok(explain_sequences(ordering_rules, update_sequences, part == 2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<(), AppError> {
+        let parsed = Day05::parse(&std::fs::read_to_string("data/inputtest")?)?;
+        assert_eq!(Day05::part1(&parsed)?, "143");
+        assert_eq!(Day05::part2(&parsed)?, "123");
+        Ok(())
+    }
+}
@@ -6,30 +6,53 @@
 
 // Standard library imports
 use std::error::Error;
+use std::time::Instant;
 
 // Internal module imports
-use calculations::process_sequences;
-use errors::AppError;
-use file_io::read_file_and_split;
-
-mod calculations;
-mod errors;
-mod file_io;
+use aoc_common::input::resolve_input;
+use aoc_common::logging;
+use aoc_common::messages;
+use aoc_common::msg;
+use aoc_common::output::{take_format, Format, Report};
+use day_05::calculations::{process_sequences, sum_valid_middles};
+use day_05::file_io::read_file_and_split;
 
 fn main() -> Result<(), Box<dyn Error>> {
-    println!("Welcome to Day 5!");
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let format = take_format(&mut args)?;
+    logging::init(logging::take_verbosity(&mut args));
+    messages::init(messages::take_lang(&mut args)?.as_deref())?;
+    if format == Format::Text {
+        println!("{}", msg!("welcome", 5));
+    }
     
-    // Get input file path from command line arguments
-    let path = std::env::args()
-        .nth(1)
-        .ok_or(AppError::ArgError("No input file provided"))?;
+    // Get input file path from command line arguments, or the cached input
+    let path = resolve_input(args.first().map(String::as_str), 5)?;
 
     // Read and parse input file
     let (ordering_rules, update_sequences) = read_file_and_split(&path)?;
     
-    // Process sequences and calculate total
-    let total = process_sequences(ordering_rules, update_sequences);
-    println!("Total: {}", total);
+    // Process sequences and calculate totals
+    let start = Instant::now();
+    let valid_total = sum_valid_middles(&ordering_rules, &update_sequences);
+    let total = process_sequences(&ordering_rules, &update_sequences);
+    let elapsed = start.elapsed();
+
+    match format {
+        Format::Text => {
+            println!("{}", msg!("day05-part1", valid_total));
+            println!("{}", msg!("day05-part2", total));
+        }
+        Format::Json => {
+            let report = Report {
+                day: 5,
+                part1: Some(valid_total.to_string()),
+                part2: Some(total.to_string()),
+                elapsed,
+            };
+            println!("{}", report.to_json());
+        }
+    }
 
     Ok(())
 }
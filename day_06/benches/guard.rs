@@ -0,0 +1,30 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use day_06::calculations::{
+    count_guard_path, count_loop_obstructions_with, find_loop_obstructions_in, find_start_position, Algorithm,
+};
+use day_06::file_io::parse_grid;
+use day_06::grid::SparseGrid;
+
+const INPUT: &str = include_str!("../data/input");
+
+fn bench_guard(c: &mut Criterion) {
+    let grid = parse_grid(INPUT).unwrap();
+
+    c.bench_function("count_guard_path", |b| b.iter(|| count_guard_path(black_box(&grid))));
+    c.bench_function("count_loop_obstructions/step", |b| {
+        b.iter(|| count_loop_obstructions_with(black_box(&grid), Algorithm::Step))
+    });
+    c.bench_function("count_loop_obstructions/jump", |b| {
+        b.iter(|| count_loop_obstructions_with(black_box(&grid), Algorithm::Jump))
+    });
+
+    let sparse = SparseGrid::from(&grid);
+    let (start, facing) = find_start_position(&grid).unwrap();
+    c.bench_function("count_loop_obstructions/jump_sparse", |b| {
+        b.iter(|| find_loop_obstructions_in(black_box(&sparse), start, facing, Algorithm::Jump, &()))
+    });
+}
+
+criterion_group!(benches, bench_guard);
+criterion_main!(benches);
@@ -0,0 +1,212 @@
+//! Terminal animation of the guard's patrol for `--visualize`.
+//!
+//! Keys: space pauses or resumes, `n` or → advances one step while paused,
+//! `+`/`-` change the speed, and `q` or Esc quits.
+
+use std::collections::HashSet;
+use std::io::{self, Write};
+use std::time::{Duration, Instant};
+
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
+use crossterm::{cursor, execute, queue, terminal};
+use ndarray::Array2;
+
+use crate::calculations::{Direction, Step};
+
+/// Delay between steps when the animation starts
+const INITIAL_DELAY: Duration = Duration::from_millis(50);
+/// Shortest and longest delay the speed keys can reach
+const MIN_DELAY: Duration = Duration::from_millis(1);
+const MAX_DELAY: Duration = Duration::from_millis(1000);
+
+/// The arrow drawn for the guard facing `direction`
+fn guard_symbol(direction: Direction) -> char {
+    match direction {
+        Direction::Up => '^',
+        Direction::Right => '>',
+        Direction::Down => 'v',
+        Direction::Left => '<',
+    }
+}
+
+/// First index of a window of `size` cells centred on `center`, kept within `0..len`
+fn window_start(center: usize, size: usize, len: usize) -> usize {
+    center.saturating_sub(size / 2).min(len.saturating_sub(size))
+}
+
+/// Renders the map after `step` steps of the route as lines of text.
+///
+/// Cells the guard has already stood on are marked `X` and the guard is drawn
+/// as an arrow. If the map is larger than `view` (rows, columns), only the
+/// part around the guard is shown.
+///
+/// # Arguments
+///
+/// * `grid` - The map the guard patrols
+/// * `route` - The route as returned by `trace_guard_route`
+/// * `step` - Index into `route` of the guard's current position
+/// * `view` - The most rows and columns to render
+pub fn render_frame(grid: &Array2<char>, route: &[Step], step: usize, view: (usize, usize)) -> Vec<String> {
+    let Some(&(guard, facing)) = route.get(step) else {
+        return Vec::new();
+    };
+    let visited: HashSet<(usize, usize)> = route[..step].iter().map(|&(pos, _)| pos).collect();
+
+    let (rows, cols) = grid.dim();
+    let top = window_start(guard.0, view.0, rows);
+    let left = window_start(guard.1, view.1, cols);
+
+    (top..rows.min(top + view.0))
+        .map(|row| {
+            (left..cols.min(left + view.1))
+                .map(|col| match (row, col) {
+                    pos if pos == guard => guard_symbol(facing),
+                    pos if grid[pos] == '#' => '#',
+                    pos if visited.contains(&pos) => 'X',
+                    _ => '.',
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Animates the guard walking `route` in the terminal until the route ends
+/// and a key is pressed, or the user quits.
+///
+/// # Arguments
+///
+/// * `grid` - The map the guard patrols
+/// * `route` - The route as returned by `trace_guard_route`
+pub fn animate(grid: &Array2<char>, route: &[Step]) -> io::Result<()> {
+    let mut stdout = io::stdout();
+    terminal::enable_raw_mode()?;
+    let _restore = RestoreTerminal;
+    execute!(stdout, terminal::EnterAlternateScreen, cursor::Hide)?;
+
+    play(&mut stdout, grid, route)
+}
+
+/// Leaves the alternate screen and raw mode when dropped, so the terminal is
+/// restored however `animate` ends, including on an error or a panic
+struct RestoreTerminal;
+
+impl Drop for RestoreTerminal {
+    fn drop(&mut self) {
+        // Both steps run even if the first fails; there is nowhere to report errors from here
+        let _ = execute!(io::stdout(), cursor::Show, terminal::LeaveAlternateScreen);
+        let _ = terminal::disable_raw_mode();
+    }
+}
+
+/// Runs the animation loop on a terminal that is already in raw mode
+fn play(out: &mut impl Write, grid: &Array2<char>, route: &[Step]) -> io::Result<()> {
+    let mut step = 0;
+    let mut delay = INITIAL_DELAY;
+    let mut paused = false;
+
+    loop {
+        let (width, height) = terminal::size()?;
+        // Leave a line for the status bar
+        let view = (usize::from(height).saturating_sub(1).max(1), usize::from(width));
+        let finished = step + 1 >= route.len();
+
+        queue!(out, terminal::Clear(terminal::ClearType::All), cursor::MoveTo(0, 0))?;
+        for line in render_frame(grid, route, step, view) {
+            write!(out, "{}\r\n", line)?;
+        }
+        let state = if finished {
+            "done, any key to exit"
+        } else if paused {
+            "paused"
+        } else {
+            "running"
+        };
+        write!(
+            out,
+            "step {}/{}  {:?}/step  {}  [space] pause  [n] step  [+/-] speed  [q] quit",
+            step + 1,
+            route.len(),
+            delay,
+            state
+        )?;
+        out.flush()?;
+
+        // Wait for the next step, or indefinitely when paused or finished
+        let deadline = (!paused && !finished).then(|| Instant::now() + delay);
+        let mut advance = false;
+        while !advance {
+            let timeout = deadline.map_or(MAX_DELAY, |deadline| deadline.saturating_duration_since(Instant::now()));
+            if !event::poll(timeout)? {
+                if deadline.is_some() {
+                    advance = true;
+                }
+                continue;
+            }
+            let Event::Key(KeyEvent { code, kind: KeyEventKind::Press, .. }) = event::read()? else {
+                continue;
+            };
+            if finished {
+                return Ok(());
+            }
+            match code {
+                KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+                KeyCode::Char(' ') => {
+                    paused = !paused;
+                    break;
+                }
+                KeyCode::Char('n') | KeyCode::Right if paused => advance = true,
+                KeyCode::Char('+') => {
+                    delay = (delay / 2).max(MIN_DELAY);
+                    break;
+                }
+                KeyCode::Char('-') => {
+                    delay = (delay * 2).min(MAX_DELAY);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        if advance {
+            step += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculations::trace_guard_route;
+    use crate::file_io::read_file;
+
+    #[test]
+    fn test_render_frame() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = read_file("data/inputtest")?;
+        let route = trace_guard_route(&grid)?;
+
+        let frame = render_frame(&grid, &route, 0, (10, 10));
+        assert_eq!(frame.len(), 10);
+        assert_eq!(frame[6], ".#..^.....");
+
+        // After walking up to the first obstacle and turning right
+        let frame = render_frame(&grid, &route, 6, (10, 10));
+        assert_eq!(frame[1], "....X>...#");
+        assert_eq!(frame[6], ".#..X.....");
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_frame_crops_around_guard() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = read_file("data/inputtest")?;
+        let route = trace_guard_route(&grid)?;
+
+        // The guard starts at (6, 4); a 3x3 view is centred on it
+        let frame = render_frame(&grid, &route, 0, (3, 3));
+        assert_eq!(frame, ["...", ".^.", "..."]);
+
+        // At the bottom edge the view stops at the last row of the map
+        let frame = render_frame(&grid, &route, route.len() - 1, (3, 3));
+        assert_eq!(frame, ["XX#", "XX.", "#v."]);
+        assert!(render_frame(&grid, &route, route.len(), (3, 3)).is_empty());
+        Ok(())
+    }
+}
@@ -1,8 +1,12 @@
 use ndarray::Array2;
-use crate::errors::AppError;
+use rayon::prelude::*;
+use std::collections::HashSet;
+use aoc_common::AppError;
 
-#[derive(Debug, Clone, Copy)]
-enum Direction {
+use crate::grid::GridLike;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
     Up,
     Right,
     Down,
@@ -27,160 +31,603 @@ impl Direction {
             Direction::Left => (0, -1),
         }
     }
+
+    /// A distinct bit per direction, for recording visited states in a bitset
+    fn bit(&self) -> u8 {
+        match self {
+            Direction::Up => 1,
+            Direction::Right => 2,
+            Direction::Down => 4,
+            Direction::Left => 8,
+        }
+    }
+}
+
+/// How a guard's patrol ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// The guard walked off the map
+    Exit,
+    /// The guard returned to a position it already left in the same direction
+    Loop,
+}
+
+/// The cells a guard covered and how the patrol ended
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathResult {
+    /// Every position the guard stood on, including the start
+    pub visited: HashSet<(usize, usize)>,
+    pub outcome: Outcome,
 }
 
-pub fn count_guard_path(mut grid: Array2<char>) -> Result<i32, AppError> {
+/// The patrols of several guards sharing one map
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GuardsResult {
+    /// Each guard's patrol, in the order the guards were given
+    pub paths: Vec<PathResult>,
+    /// Every position at least one guard stood on
+    pub coverage: HashSet<(usize, usize)>,
+}
+
+/// Walks the patrols of every guard on the map at the same time.
+///
+/// Guards don't block each other: each one only turns at obstacles, so the
+/// patrols are simulated independently on a thread pool.
+///
+/// # Returns
+///
+/// * `Result<GuardsResult, AppError>` - Each guard's patrol and their combined
+///   coverage, or an error if the map holds no guard
+pub fn walk_guards(grid: &Array2<char>) -> Result<GuardsResult, AppError> {
+    let starts = find_start_positions(grid);
+    if starts.is_empty() {
+        return Err(AppError::NoStartPosition);
+    }
+
+    let paths: Vec<PathResult> = starts
+        .par_iter()
+        .map(|&(start, facing)| walk(grid, start, facing))
+        .collect();
+    let coverage = paths.iter().flat_map(|path| path.visited.iter().copied()).collect();
+    Ok(GuardsResult { paths, coverage })
+}
+
+/// Counts the distinct positions the guard visits before leaving the map
+pub fn count_guard_path(grid: &Array2<char>) -> Result<usize, AppError> {
     // Find starting position and direction
-    let (start_pos, direction) = find_start_position(&grid)
+    let (start, facing) = find_start_position(grid)
         .ok_or(AppError::NoStartPosition)?;
-    
-    let mut pos = start_pos;
-    let mut facing = direction;
-    let mut path_count = 0;
-
-    // Mark the starting position with X
-    grid[start_pos] = 'X';
-    path_count += 1;
-
-    loop {
-        // Mark current position
-        if grid[pos] == '.' {
-            grid[pos] = 'X';
-            path_count += 1;
-        }
 
-        // Check if we've reached an edge
-        if is_at_edge(&grid, pos) {
+    Ok(walk(grid, start, facing).visited.len())
+}
+
+/// A position on the guard's route and the direction the guard was facing there
+pub type Step = ((usize, usize), Direction);
+
+/// Traces the guard's route from the starting position until it leaves the map.
+///
+/// The route follows the same steps as `walk`, so it covers exactly the positions
+/// counted by `count_guard_path`. Turning on the spot doesn't add a position, and
+/// the walk stops if the guard returns to a position it already left in the same
+/// direction.
+///
+/// # Returns
+///
+/// * `Result<Vec<Step>, AppError>` - Every position the guard stood on, in order,
+///   paired with the direction it was facing when it arrived there
+pub fn trace_guard_route(grid: &Array2<char>) -> Result<Vec<Step>, AppError> {
+    let start = find_start_position(grid).ok_or(AppError::NoStartPosition)?;
+    let mut route: Vec<Step> = Vec::new();
+    let mut seen = HashSet::new();
+    let mut state = Some(start);
+
+    while let Some((pos, facing)) = state {
+        if !seen.insert((pos, facing)) {
             break;
         }
-
-        // Get next position
-        let (next_pos, new_direction) = get_next_position(&grid, pos, facing);
-        pos = next_pos;
-        facing = new_direction;
-    }
-
-    Ok(path_count)
-}
-
-fn find_start_position(grid: &Array2<char>) -> Option<((usize, usize), Direction)> {
-    for (i, &cell) in grid.iter().enumerate() {
-        if cell == '^' {
-            let pos = (i / grid.ncols(), i % grid.ncols());
-            return Some((pos, Direction::Up));
-        } else if cell == '>' {
-            let pos = (i / grid.ncols(), i % grid.ncols());
-            return Some((pos, Direction::Right));
-        } else if cell == 'v' {
-            let pos = (i / grid.ncols(), i % grid.ncols());
-            return Some((pos, Direction::Down));
-        } else if cell == '<' {
-            let pos = (i / grid.ncols(), i % grid.ncols());
-            return Some((pos, Direction::Left));
+        if route.last().map(|&(last, _)| last) != Some(pos) {
+            route.push((pos, facing));
         }
+        state = step(grid, pos, facing, None);
+    }
+
+    Ok(route)
+}
+
+/// The direction a guard drawn as `cell` is facing, if `cell` is a guard
+fn guard_direction(cell: char) -> Option<Direction> {
+    match cell {
+        '^' => Some(Direction::Up),
+        '>' => Some(Direction::Right),
+        'v' => Some(Direction::Down),
+        '<' => Some(Direction::Left),
+        _ => None,
     }
-    None
 }
 
-fn is_at_edge(grid: &Array2<char>, pos: (usize, usize)) -> bool {
-    pos.0 == 0 || pos.0 == grid.nrows() - 1 || 
-    pos.1 == 0 || pos.1 == grid.ncols() - 1
+/// Finds the guard (`^`, `>`, `v` or `<`) and the direction it is facing.
+///
+/// If the map holds several guards, the first in row-major order is returned.
+pub fn find_start_position(grid: &Array2<char>) -> Option<((usize, usize), Direction)> {
+    grid.indexed_iter()
+        .find_map(|(pos, &cell)| guard_direction(cell).map(|facing| (pos, facing)))
+}
+
+/// Finds every guard on the map and the direction each is facing, in row-major order
+pub fn find_start_positions(grid: &Array2<char>) -> Vec<((usize, usize), Direction)> {
+    grid.indexed_iter()
+        .filter_map(|(pos, &cell)| guard_direction(cell).map(|facing| (pos, facing)))
+        .collect()
+}
+
+/// Picks the positions from `route` where a new obstruction could be placed
+fn get_possible_obstructions(
+    grid: &impl GridLike,
+    guard_pos: (usize, usize),
+    route: impl IntoIterator<Item = (usize, usize)>,
+) -> Vec<(usize, usize)> {
+    // Skip if:
+    // - It's the guard's position
+    // - It's already an obstruction (#)
+    route
+        .into_iter()
+        .filter(|&pos| pos != guard_pos && !grid.is_obstacle(pos))
+        .collect()
 }
 
-fn get_next_position(
-    grid: &Array2<char>, 
-    pos: (usize, usize), 
-    facing: Direction
-) -> ((usize, usize), Direction) {
+/// Moves the guard one step: forward if the cell ahead is free, otherwise a
+/// quarter turn to the right on the spot.
+///
+/// # Returns
+///
+/// * `Option<Step>` - The guard's new position and direction, or `None` if
+///   the step leaves the map
+fn step(
+    grid: &impl GridLike,
+    pos: (usize, usize),
+    facing: Direction,
+    obstruction: Option<(usize, usize)>,
+) -> Option<Step> {
     let (dr, dc) = facing.get_movement();
-    let next_row = (pos.0 as i32 + dr) as usize;
-    let next_col = (pos.1 as i32 + dc) as usize;
-
-    // Check if next position is obstructed
-    if next_row >= grid.nrows() || next_col >= grid.ncols() || 
-       grid[(next_row, next_col)] == '#' {
-        // Turn right and try again
-        let new_direction = facing.turn_right();
-        let (dr, dc) = new_direction.get_movement();
-        let next_row = (pos.0 as i32 + dr) as usize;
-        let next_col = (pos.1 as i32 + dc) as usize;
-        ((next_row, next_col), new_direction)
+    let (nrows, ncols) = grid.dim();
+    let next_row = pos.0.checked_add_signed(dr as isize).filter(|&row| row < nrows)?;
+    let next_col = pos.1.checked_add_signed(dc as isize).filter(|&col| col < ncols)?;
+    let next = (next_row, next_col);
+
+    if grid.is_obstacle(next) || obstruction == Some(next) {
+        Some((pos, facing.turn_right()))
     } else {
-        // Move forward
-        ((next_row, next_col), facing)
+        Some((next, facing))
     }
 }
 
-fn get_possible_obstructions(
-    grid: &Array2<char>,
-    guard_pos: (usize, usize)
-) -> Vec<(usize, usize)> {
-    let mut positions = Vec::new();
-    
-    // Iterate through all grid positions
-    for row in 0..grid.nrows() {
-        for col in 0..grid.ncols() {
-            let pos = (row, col);
-            // Skip if:
-            // - It's the guard's position
-            // - It's already an obstruction (#)
-            if pos != guard_pos && 
-               grid[pos] == '.' {
-                positions.push(pos);
-            }
+/// Walks the guard's patrol and collects every position it stands on.
+///
+/// The grid is only read, so the same map can be walked from several threads
+/// or reused for rendering without cloning it.
+///
+/// # Arguments
+///
+/// * `grid` - The lab map
+/// * `start` - The guard's starting position
+/// * `facing` - The direction the guard starts in
+pub fn walk(grid: &impl GridLike, start: (usize, usize), facing: Direction) -> PathResult {
+    let mut visited = HashSet::new();
+    let outcome = patrol(grid, start, facing, None, |pos| {
+        visited.insert(pos);
+    });
+    PathResult { visited, outcome }
+}
+
+/// Simulates the guard's patrol until it leaves the map or starts repeating itself.
+///
+/// Like `walk`, but skips collecting the visited positions.
+///
+/// # Arguments
+///
+/// * `grid` - The lab map
+/// * `start` - The guard's starting position
+/// * `facing` - The direction the guard starts in
+/// * `obstruction` - A position to treat as `#` in addition to those on the map
+pub fn simulate(
+    grid: &impl GridLike,
+    start: (usize, usize),
+    facing: Direction,
+    obstruction: Option<(usize, usize)>,
+) -> Outcome {
+    patrol(grid, start, facing, obstruction, |_| {})
+}
+
+/// Runs the patrol shared by `walk` and `simulate`, calling `visit` with each
+/// position the guard stands on.
+///
+/// Every (position, direction) state is recorded in a bitset; reaching a state
+/// a second time means the guard is walking the same loop forever.
+fn patrol(
+    grid: &impl GridLike,
+    start: (usize, usize),
+    facing: Direction,
+    obstruction: Option<(usize, usize)>,
+    mut visit: impl FnMut((usize, usize)),
+) -> Outcome {
+    let mut seen = Array2::<u8>::zeros(grid.dim());
+    let mut state = Some((start, facing));
+
+    while let Some((pos, facing)) = state {
+        if seen[pos] & facing.bit() != 0 {
+            return Outcome::Loop;
         }
+        seen[pos] |= facing.bit();
+        visit(pos);
+        state = step(grid, pos, facing, obstruction);
     }
-    
-    positions
+
+    Outcome::Exit
 }
 
-pub fn count_loop_obstructions(grid: Array2<char>) -> Result<usize, AppError> {
-    // Find starting position and direction
-    let (guard_pos, _) = find_start_position(&grid)
-        .ok_or(AppError::NoStartPosition)?;
-    
-    let possible_obstructions = get_possible_obstructions(&grid, guard_pos);
-    let mut loop_count = 0;
-
-    // Try each possible obstruction
-    for obs_pos in possible_obstructions {
-        let mut test_grid = grid.clone();
-        test_grid[obs_pos] = '#';  // Place obstruction
-
-        // Run the guard path and check if it forms a loop
-        if let Ok(path_count) = count_guard_path(test_grid) {
-            // If the guard hasn't reached an edge (indicated by path_count being > 0)
-            // then we've found a loop
-            if path_count > 0 {
-                loop_count += 1;
+/// Sorted obstacle positions for every row and column of the map, so the guard
+/// can jump straight to the next obstacle instead of walking cell by cell
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ObstacleIndex {
+    /// For each row, the columns holding a `#`, ascending
+    rows: Vec<Vec<usize>>,
+    /// For each column, the rows holding a `#`, ascending
+    cols: Vec<Vec<usize>>,
+}
+
+impl ObstacleIndex {
+    /// Indexes the obstacles of `grid`
+    pub fn new(grid: &impl GridLike) -> Self {
+        let (nrows, ncols) = grid.dim();
+        let mut rows = vec![Vec::new(); nrows];
+        let mut cols = vec![Vec::new(); ncols];
+        // Row-major order pushes both lists in ascending order
+        for (row, col) in grid.obstacles() {
+            rows[row].push(col);
+            cols[col].push(row);
+        }
+        Self { rows, cols }
+    }
+
+    /// Finds where the guard stops when walking from `pos` in direction `facing`.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(usize, usize)>` - The position just before the nearest obstacle
+    ///   ahead, which is `pos` itself if the obstacle is adjacent, or `None` if
+    ///   the guard walks off the map
+    pub fn next_stop(
+        &self,
+        (row, col): (usize, usize),
+        facing: Direction,
+        obstruction: Option<(usize, usize)>,
+    ) -> Option<(usize, usize)> {
+        match facing {
+            Direction::Up => {
+                let extra = obstruction.filter(|&(r, c)| c == col && r < row).map(|(r, _)| r);
+                let obstacle = before(&self.cols[col], row).max(extra)?;
+                Some((obstacle + 1, col))
+            }
+            Direction::Down => {
+                let extra = obstruction.filter(|&(r, c)| c == col && r > row).map(|(r, _)| r);
+                let obstacle = nearest(after(&self.cols[col], row), extra)?;
+                Some((obstacle - 1, col))
+            }
+            Direction::Left => {
+                let extra = obstruction.filter(|&(r, c)| r == row && c < col).map(|(_, c)| c);
+                let obstacle = before(&self.rows[row], col).max(extra)?;
+                Some((row, obstacle + 1))
+            }
+            Direction::Right => {
+                let extra = obstruction.filter(|&(r, c)| r == row && c > col).map(|(_, c)| c);
+                let obstacle = nearest(after(&self.rows[row], col), extra)?;
+                Some((row, obstacle - 1))
             }
         }
     }
+}
+
+/// The largest entry of a sorted list below `value`
+fn before(sorted: &[usize], value: usize) -> Option<usize> {
+    sorted[..sorted.partition_point(|&x| x < value)].last().copied()
+}
+
+/// The smallest entry of a sorted list above `value`
+fn after(sorted: &[usize], value: usize) -> Option<usize> {
+    sorted.get(sorted.partition_point(|&x| x <= value)).copied()
+}
+
+/// The smaller of two optional positions, ignoring missing ones
+fn nearest(a: Option<usize>, b: Option<usize>) -> Option<usize> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.min(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Simulates the guard's patrol by jumping between obstacles.
+///
+/// Gives the same outcome as `simulate`. Only the states where the guard
+/// turns are recorded, since a repeated turn means a repeated loop.
+///
+/// # Arguments
+///
+/// * `index` - The obstacles of the lab map
+/// * `start` - The guard's starting position
+/// * `facing` - The direction the guard starts in
+/// * `obstruction` - A position to treat as `#` in addition to those on the map
+pub fn simulate_jumps(
+    index: &ObstacleIndex,
+    start: (usize, usize),
+    mut facing: Direction,
+    obstruction: Option<(usize, usize)>,
+) -> Outcome {
+    let mut turns = HashSet::new();
+    let mut pos = start;
+
+    while let Some(stop) = index.next_stop(pos, facing, obstruction) {
+        pos = stop;
+        facing = facing.turn_right();
+        if !turns.insert((pos, facing)) {
+            return Outcome::Loop;
+        }
+    }
+
+    Outcome::Exit
+}
+
+/// How `count_loop_obstructions_with` simulates each candidate obstruction
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    /// Walk cell by cell with `simulate`
+    Step,
+    /// Jump between obstacles with `simulate_jumps`
+    #[default]
+    Jump,
+}
+
+/// Counts the positions where one extra obstruction would trap the guard in a
+/// loop, using the default algorithm.
+pub fn count_loop_obstructions(grid: &Array2<char>) -> Result<usize, AppError> {
+    count_loop_obstructions_with(grid, Algorithm::default())
+}
+
+/// Receives progress updates from `find_loop_obstructions`.
+///
+/// Candidates are tested on several threads at once, so `tested` can be
+/// called concurrently and in any order.
+pub trait Progress: Sync {
+    /// Called once, before testing starts, with the number of candidates
+    fn start(&self, _candidates: usize) {}
+
+    /// Called after each candidate is tested, with its position and whether
+    /// it caused a loop
+    fn tested(&self, _pos: (usize, usize), _is_loop: bool) {}
+}
+
+/// Reports nothing
+impl Progress for () {}
+
+impl<P: Progress + ?Sized> Progress for &P {
+    fn start(&self, candidates: usize) {
+        (**self).start(candidates);
+    }
 
-    Ok(loop_count)
+    fn tested(&self, pos: (usize, usize), is_loop: bool) {
+        (**self).tested(pos, is_loop);
+    }
+}
+
+/// Reports to both
+impl<A: Progress, B: Progress> Progress for (A, B) {
+    fn start(&self, candidates: usize) {
+        self.0.start(candidates);
+        self.1.start(candidates);
+    }
+
+    fn tested(&self, pos: (usize, usize), is_loop: bool) {
+        self.0.tested(pos, is_loop);
+        self.1.tested(pos, is_loop);
+    }
+}
+
+/// Counts the positions where one extra obstruction would trap the guard in a loop.
+///
+/// Only positions on the guard's original route can change its patrol, so
+/// those are the only candidates. Each is simulated independently on a thread
+/// pool; the grid is shared between them rather than cloned.
+pub fn count_loop_obstructions_with(grid: &Array2<char>, algorithm: Algorithm) -> Result<usize, AppError> {
+    Ok(find_loop_obstructions(grid, algorithm, &())?.len())
+}
+
+/// Finds the positions where one extra obstruction would trap the guard in a
+/// loop, reporting each tested candidate to `progress`.
+///
+/// # Returns
+///
+/// * `Result<Vec<(usize, usize)>, AppError>` - The loop-causing positions in
+///   row-major order, or an error if the map has no guard
+pub fn find_loop_obstructions(
+    grid: &Array2<char>,
+    algorithm: Algorithm,
+    progress: &impl Progress,
+) -> Result<Vec<(usize, usize)>, AppError> {
+    // Find starting position and direction
+    let (guard_pos, facing) = find_start_position(grid)
+        .ok_or(AppError::NoStartPosition)?;
+
+    Ok(find_loop_obstructions_in(grid, guard_pos, facing, algorithm, progress))
+}
+
+/// Like `find_loop_obstructions`, but skips the candidates in `tested`, so an
+/// interrupted search can be resumed.
+///
+/// # Returns
+///
+/// * `Result<Vec<(usize, usize)>, AppError>` - The loop-causing positions among
+///   the untested candidates, in row-major order, or an error if the map has no guard
+pub fn find_loop_obstructions_skipping(
+    grid: &Array2<char>,
+    algorithm: Algorithm,
+    progress: &impl Progress,
+    tested: &HashSet<(usize, usize)>,
+) -> Result<Vec<(usize, usize)>, AppError> {
+    let (guard_pos, facing) = find_start_position(grid)
+        .ok_or(AppError::NoStartPosition)?;
+
+    let route = walk(grid, guard_pos, facing).visited;
+    let mut candidates = get_possible_obstructions(grid, guard_pos, route);
+    candidates.retain(|pos| !tested.contains(pos));
+    Ok(test_candidates(grid, guard_pos, facing, candidates, algorithm, progress))
+}
+
+/// Like `find_loop_obstructions`, for any map representation and a guard
+/// starting at `guard_pos` facing `facing`
+pub fn find_loop_obstructions_in(
+    grid: &impl GridLike,
+    guard_pos: (usize, usize),
+    facing: Direction,
+    algorithm: Algorithm,
+    progress: &impl Progress,
+) -> Vec<(usize, usize)> {
+    let route = walk(grid, guard_pos, facing).visited;
+    let possible_obstructions = get_possible_obstructions(grid, guard_pos, route);
+    test_candidates(grid, guard_pos, facing, possible_obstructions, algorithm, progress)
+}
+
+/// Simulates the guard with each candidate obstruction in turn and keeps
+/// those that trap it, in row-major order
+fn test_candidates(
+    grid: &impl GridLike,
+    guard_pos: (usize, usize),
+    facing: Direction,
+    possible_obstructions: Vec<(usize, usize)>,
+    algorithm: Algorithm,
+    progress: &impl Progress,
+) -> Vec<(usize, usize)> {
+    progress.start(possible_obstructions.len());
+
+    // Try each possible obstruction and keep those that trap the guard
+    let index = match algorithm {
+        Algorithm::Step => None,
+        Algorithm::Jump => Some(ObstacleIndex::new(grid)),
+    };
+    let mut loop_positions: Vec<(usize, usize)> = possible_obstructions
+        .into_par_iter()
+        .filter(|&obs_pos| {
+            let outcome = match &index {
+                None => simulate(grid, guard_pos, facing, Some(obs_pos)),
+                Some(index) => simulate_jumps(index, guard_pos, facing, Some(obs_pos)),
+            };
+            progress.tested(obs_pos, outcome == Outcome::Loop);
+            outcome == Outcome::Loop
+        })
+        .collect();
+
+    loop_positions.sort_unstable();
+    loop_positions
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::read_file;
+    use crate::file_io::read_file;
 
     use super::*;
     
     #[test]
     fn test_guard_path_count() -> Result<(), Box<dyn std::error::Error>> {
         let grid = read_file("data/inputtest")?;
-        let path_count = count_guard_path(grid)?;
+        let path_count = count_guard_path(&grid)?;
         assert_eq!(path_count, 41);
         Ok(())
     }
 
+    #[test]
+    fn test_walk() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = read_file("data/inputtest")?;
+        let path = walk(&grid, (6, 4), Direction::Up);
+        assert_eq!(path.outcome, Outcome::Exit);
+        assert_eq!(path.visited.len(), 41);
+        assert!(path.visited.contains(&(6, 4)));
+        assert!(path.visited.contains(&(9, 7)));
+
+        // Boxed in on all sides, the guard turns on the spot forever
+        let mut boxed = Array2::from_elem((3, 3), '#');
+        boxed[(1, 1)] = '^';
+        let path = walk(&boxed, (1, 1), Direction::Up);
+        assert_eq!(path.outcome, Outcome::Loop);
+        assert_eq!(path.visited, HashSet::from([(1, 1)]));
+        Ok(())
+    }
+
+    #[test]
+    fn test_trace_guard_route() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = read_file("data/inputtest")?;
+        let route = trace_guard_route(&grid)?;
+        assert_eq!(route.first(), Some(&((6, 4), Direction::Up)));
+        assert_eq!(route[5], ((1, 4), Direction::Up));
+        assert_eq!(route[6], ((1, 5), Direction::Right));
+        assert_eq!(route.last(), Some(&((9, 7), Direction::Down)));
+
+        let visited: HashSet<(usize, usize)> = route.iter().map(|&(pos, _)| pos).collect();
+        assert_eq!(visited.len(), 41);
+
+        // Blocked ahead and to the right, the guard turns twice before moving
+        // down, then keeps going until it leaves the map
+        let corner = Array2::from_shape_vec((3, 3), ".#..^#...".chars().collect())?;
+        let route = trace_guard_route(&corner)?;
+        assert_eq!(route, vec![((1, 1), Direction::Up), ((2, 1), Direction::Down)]);
+        let visited: HashSet<(usize, usize)> = route.iter().map(|&(pos, _)| pos).collect();
+        assert_eq!(visited, walk(&corner, (1, 1), Direction::Up).visited);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_start_positions() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = read_file("data/inputtest")?;
+        assert_eq!(find_start_positions(&grid), vec![((6, 4), Direction::Up)]);
+
+        let mut grid = Array2::from_elem((3, 3), '.');
+        grid[(0, 2)] = 'v';
+        grid[(2, 0)] = '>';
+        grid[(2, 2)] = '#';
+        assert_eq!(find_start_positions(&grid), vec![((0, 2), Direction::Down), ((2, 0), Direction::Right)]);
+        assert_eq!(find_start_position(&grid), Some(((0, 2), Direction::Down)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_walk_guards() -> Result<(), Box<dyn std::error::Error>> {
+        // .#..
+        // ...<
+        // ^...
+        let mut grid = Array2::from_elem((3, 4), '.');
+        grid[(0, 1)] = '#';
+        grid[(1, 3)] = '<';
+        grid[(2, 0)] = '^';
+
+        let guards = walk_guards(&grid)?;
+        assert_eq!(guards.paths.len(), 2);
+        // Walks left along the middle row
+        assert_eq!(guards.paths[0].visited.len(), 4);
+        // Walks straight up the first column
+        assert_eq!(guards.paths[1].visited.len(), 3);
+        // The two paths cross at (1, 0)
+        assert_eq!(guards.coverage.len(), 6);
+
+        let empty = Array2::from_elem((2, 2), '.');
+        assert!(matches!(walk_guards(&empty), Err(AppError::NoStartPosition)));
+        Ok(())
+    }
+
     #[test]
     fn test_possible_obstructions() {
         let mut grid = Array2::from_elem((4, 4), '.');
         grid[(1, 1)] = '^';  // Guard position
         grid[(0, 0)] = '#';  // Existing obstruction
 
-        let obstructions = get_possible_obstructions(&grid, (1, 1));
+        let all_cells = grid.indexed_iter().map(|(pos, _)| pos);
+        let obstructions = get_possible_obstructions(&grid, (1, 1), all_cells);
         
         // Should not include:
         // - Guard position (1,1)
@@ -191,10 +638,120 @@ mod tests {
         assert!(!obstructions.contains(&(0, 0))); // Edge
     }
 
+    #[test]
+    fn test_simulate() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = read_file("data/inputtest")?;
+        assert_eq!(simulate(&grid, (6, 4), Direction::Up, None), Outcome::Exit);
+        // The first of the six loop-causing positions in the puzzle description
+        assert_eq!(simulate(&grid, (6, 4), Direction::Up, Some((6, 3))), Outcome::Loop);
+        // Not on the guard's route, so nothing changes
+        assert_eq!(simulate(&grid, (6, 4), Direction::Up, Some((0, 0))), Outcome::Exit);
+        Ok(())
+    }
+
+    #[test]
+    fn test_next_stop() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = read_file("data/inputtest")?;
+        let index = ObstacleIndex::new(&grid);
+        assert_eq!(index.next_stop((6, 4), Direction::Up, None), Some((1, 4)));
+        assert_eq!(index.next_stop((1, 4), Direction::Right, None), Some((1, 8)));
+        assert_eq!(index.next_stop((6, 4), Direction::Up, Some((3, 4))), Some((4, 4)));
+        // An obstruction behind the guard doesn't matter
+        assert_eq!(index.next_stop((6, 4), Direction::Up, Some((8, 4))), Some((1, 4)));
+        assert_eq!(index.next_stop((7, 7), Direction::Down, None), None);
+        assert_eq!(index.next_stop((7, 7), Direction::Down, Some((9, 7))), Some((8, 7)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulate_jumps() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = read_file("data/inputtest")?;
+        let index = ObstacleIndex::new(&grid);
+        assert_eq!(simulate_jumps(&index, (6, 4), Direction::Up, None), Outcome::Exit);
+        assert_eq!(simulate_jumps(&index, (6, 4), Direction::Up, Some((6, 3))), Outcome::Loop);
+        Ok(())
+    }
+
+    #[test]
+    fn test_algorithms_agree() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = read_file("data/inputtest")?;
+        assert_eq!(count_loop_obstructions_with(&grid, Algorithm::Step)?, 6);
+        assert_eq!(count_loop_obstructions_with(&grid, Algorithm::Jump)?, 6);
+        Ok(())
+    }
+
+    #[test]
+    fn test_sparse_grid() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::grid::SparseGrid;
+
+        let grid = read_file("data/inputtest")?;
+        let sparse = SparseGrid::from(&grid);
+        assert_eq!(walk(&sparse, (6, 4), Direction::Up), walk(&grid, (6, 4), Direction::Up));
+        assert_eq!(simulate(&sparse, (6, 4), Direction::Up, Some((6, 3))), Outcome::Loop);
+        for algorithm in [Algorithm::Step, Algorithm::Jump] {
+            assert_eq!(find_loop_obstructions_in(&sparse, (6, 4), Direction::Up, algorithm, &()).len(), 6);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_progress_reporting() -> Result<(), Box<dyn std::error::Error>> {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Default)]
+        struct Counts {
+            candidates: AtomicUsize,
+            tested: AtomicUsize,
+            loops: AtomicUsize,
+        }
+
+        impl Progress for Counts {
+            fn start(&self, candidates: usize) {
+                self.candidates.store(candidates, Ordering::Relaxed);
+            }
+
+            fn tested(&self, _pos: (usize, usize), is_loop: bool) {
+                self.tested.fetch_add(1, Ordering::Relaxed);
+                if is_loop {
+                    self.loops.fetch_add(1, Ordering::Relaxed);
+                }
+            }
+        }
+
+        let grid = read_file("data/inputtest")?;
+        let counts = Counts::default();
+        let loop_count = find_loop_obstructions(&grid, Algorithm::Jump, &counts)?.len();
+
+        // The route covers 41 cells, one of which is the guard's start
+        assert_eq!(counts.candidates.into_inner(), 40);
+        assert_eq!(counts.tested.into_inner(), 40);
+        assert_eq!(counts.loops.into_inner(), loop_count);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_loop_obstructions() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = read_file("data/inputtest")?;
+        // The six positions marked `O` in the puzzle description
+        let expected = vec![(6, 3), (7, 6), (7, 7), (8, 1), (8, 3), (9, 7)];
+        assert_eq!(find_loop_obstructions(&grid, Algorithm::Step, &())?, expected);
+        assert_eq!(find_loop_obstructions(&grid, Algorithm::Jump, &())?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_loop_obstructions_skipping() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = read_file("data/inputtest")?;
+        let tested = HashSet::from([(6, 3), (7, 6), (1, 5)]);
+        let found = find_loop_obstructions_skipping(&grid, Algorithm::Jump, &(), &tested)?;
+        assert_eq!(found, vec![(7, 7), (8, 1), (8, 3), (9, 7)]);
+        Ok(())
+    }
+
     #[test]
     fn test_count_loop_obstructions() -> Result<(), Box<dyn std::error::Error>> {
         let grid = read_file("data/inputtest")?;
-        let loop_count = count_loop_obstructions(grid)?;
+        let loop_count = count_loop_obstructions(&grid)?;
         assert_eq!(loop_count, 6);
         Ok(())
     }
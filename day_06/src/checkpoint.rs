@@ -0,0 +1,204 @@
+//! Resumable part 2 searches, for `--checkpoint`.
+//!
+//! A `Checkpoint` records every tested obstruction candidate and whether it
+//! trapped the guard, and writes them to a file every few seconds. Opening the
+//! same file again restores them, so `find_loop_obstructions_skipping` only has
+//! to test the rest.
+//!
+//! The file is plain text: a header line `map <rows> <cols> <row> <col> <hash>`
+//! with the map size, the guard's start and a fingerprint of the map's cells,
+//! then one `<row> <col> <loop|exit>` line per tested candidate.
+
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use aoc_common::AppError;
+use ndarray::Array2;
+
+use crate::calculations::{find_start_position, Progress};
+
+/// How often the checkpoint file is rewritten while candidates are tested
+const SAVE_INTERVAL: Duration = Duration::from_secs(5);
+
+/// The tested candidates of one part 2 search, persisted to a file
+#[derive(Debug)]
+pub struct Checkpoint {
+    path: PathBuf,
+    /// The map size, guard start and fingerprint, written as the header
+    header: String,
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    tested: HashSet<(usize, usize)>,
+    loops: HashSet<(usize, usize)>,
+    last_saved: Instant,
+    /// The first error from a periodic save, reported by `finish`
+    save_error: Option<io::Error>,
+}
+
+impl Checkpoint {
+    /// Opens the checkpoint at `path` for a search on `grid`, restoring the
+    /// candidates it records if the file exists.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Checkpoint, AppError>` - The checkpoint, or an error if the file
+    ///   cannot be read, is malformed or was written for a different map
+    pub fn open(path: &Path, grid: &Array2<char>) -> Result<Self, AppError> {
+        let ((row, col), _) = find_start_position(grid).ok_or(AppError::NoStartPosition)?;
+        let header = format!("map {} {} {} {} {:016x}", grid.nrows(), grid.ncols(), row, col, fingerprint(grid));
+
+        let mut tested = HashSet::new();
+        let mut loops = HashSet::new();
+        match std::fs::read_to_string(path) {
+            Ok(contents) => {
+                let mut lines = contents.lines();
+                if lines.next() != Some(header.as_str()) {
+                    return Err(AppError::ArgError("--checkpoint file was written for a different map"));
+                }
+                for line in lines {
+                    let (pos, is_loop) = parse_line(line)?;
+                    tested.insert(pos);
+                    if is_loop {
+                        loops.insert(pos);
+                    }
+                }
+            }
+            Err(error) if error.kind() == io::ErrorKind::NotFound => {}
+            Err(error) => return Err(error.into()),
+        }
+
+        let state = State { tested, loops, last_saved: Instant::now(), save_error: None };
+        Ok(Self { path: path.to_path_buf(), header, state: Mutex::new(state) })
+    }
+
+    /// The candidates already tested
+    pub fn tested(&self) -> HashSet<(usize, usize)> {
+        self.lock().tested.clone()
+    }
+
+    /// Saves the checkpoint a final time.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<(usize, usize)>, AppError>` - Every loop-causing position
+    ///   recorded, in row-major order, or an error if any save failed
+    pub fn finish(self) -> Result<Vec<(usize, usize)>, AppError> {
+        let mut state = self.state.into_inner().unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some(error) = state.save_error.take() {
+            return Err(error.into());
+        }
+        save(&self.path, &self.header, &state)?;
+
+        let mut loops: Vec<_> = state.loops.into_iter().collect();
+        loops.sort_unstable();
+        Ok(loops)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+}
+
+impl Progress for Checkpoint {
+    fn tested(&self, pos: (usize, usize), is_loop: bool) {
+        let mut state = self.lock();
+        state.tested.insert(pos);
+        if is_loop {
+            state.loops.insert(pos);
+        }
+
+        if state.last_saved.elapsed() >= SAVE_INTERVAL {
+            state.last_saved = Instant::now();
+            if let Err(error) = save(&self.path, &self.header, &state) {
+                state.save_error.get_or_insert(error);
+            }
+        }
+    }
+}
+
+/// Hashes the map's cells in row-major order with 64-bit FNV-1a, which unlike
+/// `DefaultHasher` gives the same value in every build, so a checkpoint
+/// written for one obstacle layout is never resumed on another
+fn fingerprint(grid: &Array2<char>) -> u64 {
+    grid.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &cell| {
+        (hash ^ u64::from(cell)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+/// Parses a `<row> <col> <loop|exit>` line
+fn parse_line(line: &str) -> Result<((usize, usize), bool), AppError> {
+    let mut fields = line.split_whitespace();
+    let row = fields.next().ok_or(AppError::ArgError("malformed --checkpoint file"))?.parse()?;
+    let col = fields.next().ok_or(AppError::ArgError("malformed --checkpoint file"))?.parse()?;
+    let is_loop = match fields.next() {
+        Some("loop") => true,
+        Some("exit") => false,
+        _ => return Err(AppError::ArgError("malformed --checkpoint file")),
+    };
+    Ok(((row, col), is_loop))
+}
+
+/// Writes the state to a temporary file and moves it over `path`, so an
+/// interruption never leaves a half-written checkpoint
+fn save(path: &Path, header: &str, state: &State) -> io::Result<()> {
+    let mut tested: Vec<_> = state.tested.iter().copied().collect();
+    tested.sort_unstable();
+
+    let mut contents = format!("{}\n", header);
+    for pos in tested {
+        let outcome = if state.loops.contains(&pos) { "loop" } else { "exit" };
+        let _ = writeln!(contents, "{} {} {}", pos.0, pos.1, outcome);
+    }
+
+    let temp = path.with_extension("tmp");
+    std::fs::write(&temp, contents)?;
+    std::fs::rename(&temp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::calculations::{find_loop_obstructions, find_loop_obstructions_skipping, Algorithm};
+    use crate::file_io::read_file;
+
+    #[test]
+    fn test_resume() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = read_file("data/inputtest")?;
+        let path = std::env::temp_dir().join(format!("day_06_checkpoint_{}.txt", std::process::id()));
+        let header = format!("map 10 10 6 4 {:016x}", fingerprint(&grid));
+        std::fs::write(&path, format!("{}\n1 5 exit\n6 3 loop\n", header))?;
+
+        let checkpoint = Checkpoint::open(&path, &grid)?;
+        assert_eq!(checkpoint.tested(), HashSet::from([(1, 5), (6, 3)]));
+        find_loop_obstructions_skipping(&grid, Algorithm::Jump, &checkpoint, &checkpoint.tested())?;
+        let loops = checkpoint.finish()?;
+        assert_eq!(loops, find_loop_obstructions(&grid, Algorithm::Jump, &())?);
+
+        // Every candidate is now recorded, so resuming again tests nothing
+        let checkpoint = Checkpoint::open(&path, &grid)?;
+        assert_eq!(checkpoint.tested().len(), 40);
+        let found = find_loop_obstructions_skipping(&grid, Algorithm::Jump, &(), &checkpoint.tested())?;
+        assert!(found.is_empty());
+
+        std::fs::write(&path, format!("map 10 10 0 0 {:016x}\n", fingerprint(&grid)))?;
+        assert!(matches!(Checkpoint::open(&path, &grid), Err(AppError::ArgError(_))));
+        std::fs::write(&path, format!("{}\n1 5 maybe\n", header))?;
+        assert!(matches!(Checkpoint::open(&path, &grid), Err(AppError::ArgError(_))));
+
+        // Same size and start, but an obstacle has moved
+        let mut moved = grid.clone();
+        moved[(0, 4)] = '.';
+        moved[(0, 3)] = '#';
+        std::fs::write(&path, format!("{}\n", header))?;
+        assert!(matches!(Checkpoint::open(&path, &moved), Err(AppError::ArgError(_))));
+        std::fs::remove_file(&path)?;
+        Ok(())
+    }
+}
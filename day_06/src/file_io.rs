@@ -1,6 +1,6 @@
 use ndarray::Array2;
 
-use crate::AppError;
+use aoc_common::AppError;
 
 /// Reads a file and converts its contents into a 2D array of characters.
 /// Each line in the file becomes a row in the array.
@@ -20,11 +20,47 @@ use crate::AppError;
 /// - The file contains lines of different lengths
 pub fn read_file(filename: &str) -> Result<Array2<char>, AppError> {
     let content = std::fs::read_to_string(filename)?;
+    parse_grid(&content)
+}
+
+/// Converts puzzle input text into a 2D array of characters.
+/// Each line becomes a row in the array, one column per `char`, so non-ASCII
+/// characters take one cell each.
+///
+/// # Arguments
+///
+/// * `content` - The puzzle input text
+///
+/// # Returns
+///
+/// * `Result<Array2<char>, AppError>` - A 2D array of characters, or
+///   `AppError::RaggedInput` if the lines have different lengths
+pub fn parse_grid(content: &str) -> Result<Array2<char>, AppError> {
     let lines: Vec<&str> = content.lines().collect();
     let rows = lines.len();
-    let cols = lines[0].len();
+    let cols = lines.first().map_or(0, |line| line.chars().count());
+    let widths = lines.iter().map(|line| line.chars().count());
+    if let Some((index, got)) = widths.enumerate().find(|&(_, width)| width != cols) {
+        return Err(AppError::RaggedInput { line: index + 1, expected: cols, got });
+    }
 
     let data: Vec<char> = lines.join("").chars().collect();
     Array2::from_shape_vec((rows, cols), data).map_err(|_| AppError::Array2CreationError)
 }
 
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_grid_unicode() -> Result<(), AppError> {
+        // Floor drawn with a middle dot, two bytes in UTF-8
+        let grid = parse_grid("··#\n·^·\n···\n")?;
+        assert_eq!(grid.dim(), (3, 3));
+        assert_eq!(grid[(0, 2)], '#');
+        assert_eq!(grid[(1, 1)], '^');
+        assert!(matches!(parse_grid("··#\n·^\n"), Err(AppError::RaggedInput { line: 2, expected: 3, got: 2 })));
+        assert_eq!(parse_grid("")?.dim(), (0, 0));
+        Ok(())
+    }
+}
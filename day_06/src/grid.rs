@@ -0,0 +1,99 @@
+use ndarray::Array2;
+use std::collections::HashSet;
+
+/// A lab map the guard can patrol: a bounded area with some cells obstructed
+pub trait GridLike: Sync {
+    /// The number of rows and columns
+    fn dim(&self) -> (usize, usize);
+
+    /// Whether `pos` holds an obstacle; `pos` must be inside the map
+    fn is_obstacle(&self, pos: (usize, usize)) -> bool;
+
+    /// Every obstacle position, in row-major order
+    fn obstacles(&self) -> Vec<(usize, usize)>;
+}
+
+impl GridLike for Array2<char> {
+    fn dim(&self) -> (usize, usize) {
+        Array2::dim(self)
+    }
+
+    fn is_obstacle(&self, pos: (usize, usize)) -> bool {
+        self[pos] == '#'
+    }
+
+    fn obstacles(&self) -> Vec<(usize, usize)> {
+        self.indexed_iter()
+            .filter(|&(_, &cell)| cell == '#')
+            .map(|(pos, _)| pos)
+            .collect()
+    }
+}
+
+/// A lab map stored as its obstacle positions and bounds only.
+///
+/// Cheaper than a dense `Array2<char>` for very large or mostly empty maps.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SparseGrid {
+    obstacles: HashSet<(usize, usize)>,
+    dim: (usize, usize),
+}
+
+impl SparseGrid {
+    /// Creates a map of `dim` rows and columns with the given obstacles.
+    ///
+    /// Obstacles outside the bounds are ignored.
+    pub fn new(dim: (usize, usize), obstacles: impl IntoIterator<Item = (usize, usize)>) -> Self {
+        let obstacles = obstacles
+            .into_iter()
+            .filter(|&(row, col)| row < dim.0 && col < dim.1)
+            .collect();
+        Self { obstacles, dim }
+    }
+}
+
+impl From<&Array2<char>> for SparseGrid {
+    fn from(grid: &Array2<char>) -> Self {
+        Self::new(grid.dim(), grid.obstacles())
+    }
+}
+
+impl GridLike for SparseGrid {
+    fn dim(&self) -> (usize, usize) {
+        self.dim
+    }
+
+    fn is_obstacle(&self, pos: (usize, usize)) -> bool {
+        self.obstacles.contains(&pos)
+    }
+
+    fn obstacles(&self) -> Vec<(usize, usize)> {
+        let mut obstacles: Vec<_> = self.obstacles.iter().copied().collect();
+        obstacles.sort_unstable();
+        obstacles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::file_io::read_file;
+
+    use super::*;
+
+    #[test]
+    fn test_sparse_matches_dense() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = read_file("data/inputtest")?;
+        let sparse = SparseGrid::from(&grid);
+        assert_eq!(GridLike::dim(&sparse), (10, 10));
+        assert_eq!(sparse.obstacles(), grid.obstacles());
+        assert!(sparse.is_obstacle((0, 4)));
+        assert!(!sparse.is_obstacle((6, 4)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_sparse_ignores_out_of_bounds() {
+        let sparse = SparseGrid::new((2, 2), [(0, 1), (2, 0), (1, 5)]);
+        assert_eq!(sparse.obstacles(), vec![(0, 1)]);
+    }
+}
@@ -0,0 +1,80 @@
+//! Day 6: Guard Gallivant
+//!
+//! Library entry points shared by the binary and the `aoc` runner.
+
+pub mod animate;
+pub mod calculations;
+pub mod checkpoint;
+pub mod file_io;
+pub mod grid;
+pub mod render;
+
+use aoc_common::{AppError, Solver};
+use ndarray::Array2;
+
+use calculations::{
+    count_guard_path, count_loop_obstructions, find_loop_obstructions, trace_guard_route, Algorithm,
+};
+use file_io::parse_grid;
+
+/// Solver for the guard's patrol
+pub struct Day06;
+
+impl Solver for Day06 {
+    /// The lab map
+    type Parsed = Array2<char>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AppError> {
+        parse_grid(input)
+    }
+
+    /// Counts the distinct positions the guard visits
+    fn part1(grid: &Self::Parsed) -> Result<String, AppError> {
+        Ok(count_guard_path(grid)?.to_string())
+    }
+
+    /// Counts the positions where a new obstruction would trap the guard in a loop
+    fn part2(grid: &Self::Parsed) -> Result<String, AppError> {
+        Ok(count_loop_obstructions(grid)?.to_string())
+    }
+
+    /// Where the guard turns and leaves the map, or where an obstruction traps it in a loop
+    fn explain(grid: &Self::Parsed, part: u32) -> Result<Vec<String>, AppError> {
+        if part == 1 {
+            let route = trace_guard_route(grid)?;
+            let mut lines: Vec<String> = route
+                .windows(2)
+                .filter(|pair| pair[0].1 != pair[1].1)
+                .map(|pair| format!("Turns at {:?} to face {:?}", pair[0].0, pair[1].1))
+                .collect();
+            if let Some(&(pos, facing)) = route.last() {
+                lines.push(format!("Leaves the map from {:?} facing {:?}", pos, facing));
+            }
+            return Ok(lines);
+        }
+        Ok(find_loop_obstructions(grid, Algorithm::default(), &())?
+            .into_iter()
+            .map(|pos| format!("An obstruction at {:?} traps the guard in a loop", pos))
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<(), AppError> {
+        let grid = Day06::parse(&std::fs::read_to_string("data/inputtest")?)?;
+        assert_eq!(Day06::part1(&grid)?, "41");
+        assert_eq!(Day06::part2(&grid)?, "6");
+
+        let turns = Day06::explain(&grid, 1)?;
+        assert_eq!(turns[0], "Turns at (1, 4) to face Right");
+        assert_eq!(turns.last().map(String::as_str), Some("Leaves the map from (9, 7) facing Down"));
+        let loops = Day06::explain(&grid, 2)?;
+        assert_eq!(loops.len(), 6);
+        assert_eq!(loops[0], "An obstruction at (6, 3) traps the guard in a loop");
+        Ok(())
+    }
+}
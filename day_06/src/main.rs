@@ -1,26 +1,197 @@
-mod calculations;
-mod file_io;
-mod errors;
-
-use calculations::count_guard_path;
-use file_io::read_file;
-use errors::AppError;
+use day_06::calculations::{
+    count_guard_path, find_loop_obstructions, find_loop_obstructions_skipping, trace_guard_route, walk_guards, Algorithm,
+    Progress,
+};
+use day_06::file_io::read_file;
+use aoc_common::input::resolve_input;
+use aoc_common::logging;
+use aoc_common::messages;
+use aoc_common::msg;
+use aoc_common::output::{take_format, Format, Report};
+use aoc_common::AppError;
+use day_06::animate::animate;
+use day_06::checkpoint::Checkpoint;
+use day_06::render::{render_route_svg, write_loop_map, write_render, write_route_gif, GifOptions};
 
 use std::error::Error;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+/// Shows the part 2 candidate search as a progress bar on stderr; the bar is
+/// hidden when stderr isn't a terminal
+struct SearchProgress {
+    bar: ProgressBar,
+    loops: AtomicUsize,
+}
 
+impl SearchProgress {
+    fn new() -> Self {
+        let bar = ProgressBar::new(0).with_style(
+            ProgressStyle::with_template("{bar:40} {pos}/{len} candidates, {msg}, ETA {eta}")
+                .expect("progress template is valid"),
+        );
+        bar.set_message("0 loops");
+        Self { bar, loops: AtomicUsize::new(0) }
+    }
+}
+
+impl Progress for SearchProgress {
+    fn start(&self, candidates: usize) {
+        self.bar.set_length(candidates as u64);
+    }
+
+    fn tested(&self, _pos: (usize, usize), is_loop: bool) {
+        if is_loop {
+            let loops = self.loops.fetch_add(1, Ordering::Relaxed) + 1;
+            self.bar.set_message(format!("{} loops", loops));
+        }
+        self.bar.inc(1);
+    }
+}
+
+/// Usage: `cargo run -- [<input>] [--svg <path.svg>] [--render <path.svg|path.png>]
+/// [--gif <path.gif> [--stride <steps>] [--cell-size <pixels>]] [--loop-map <path.txt|path.svg|path.png>]
+/// [--checkpoint <path>] [--visualize] [--format <text|json>] [-v|-vv]`
+///
+/// `--svg` draws the route as lines coloured by direction over the obstacles
+/// and start position, while `--render` draws every visited cell instead, as
+/// an SVG or PNG image depending on the file extension. `--gif` animates the walk, one
+/// frame every `--stride` steps (10 by default) with `--cell-size` pixels per
+/// cell (4 by default).
+///
+/// `--loop-map` writes the map with every position where an extra obstruction
+/// traps the guard marked, as text (`O` on the map) or as an SVG or PNG image.
+///
+/// `--checkpoint` saves the part 2 search to a file every few seconds; running
+/// again with the same file skips the candidates it already records.
+///
+/// `--visualize` animates the guard's walk in the terminal before the answers
+/// are printed; see `day_06::animate` for the keys.
+///
+/// If the map holds more than one guard, the answers are for the first guard
+/// in reading order, followed in text mode by each guard's path length and
+/// their combined coverage.
+///
+/// Without an input path, the input cached by `aoc fetch` is used.
 fn main() -> Result<(), Box<dyn Error>> {
-    println!("Welcome to Day 6!");
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let format = take_format(&mut args)?;
+    logging::init(logging::take_verbosity(&mut args));
+    messages::init(messages::take_lang(&mut args)?.as_deref())?;
+    if format == Format::Text {
+        println!("{}", msg!("welcome", 6));
+    }
 
-    let args: Vec<String> = std::env::args().collect();
-    if args.len() != 2 {
-        return Err(Box::new(AppError::ArgError("Please provide a file path as argument")));
+    let mut args = args.into_iter().peekable();
+    let file_path = args.next_if(|arg| !arg.starts_with("--"));
+    let mut svg_path = None;
+    let mut render_path = None;
+    let mut gif_path = None;
+    let mut gif_options = GifOptions::default();
+    let mut loop_map_path = None;
+    let mut checkpoint_path = None;
+    let mut visualize = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--svg" => svg_path = Some(args.next().ok_or(AppError::ArgError("--svg requires a path"))?),
+            "--render" => {
+                render_path = Some(args.next().ok_or(AppError::ArgError("--render requires a path"))?)
+            }
+            "--gif" => gif_path = Some(args.next().ok_or(AppError::ArgError("--gif requires a path"))?),
+            "--stride" => {
+                gif_options.stride = args.next().ok_or(AppError::ArgError("--stride requires a number"))?.parse()?
+            }
+            "--cell-size" => {
+                gif_options.cell_size = args.next().ok_or(AppError::ArgError("--cell-size requires a number"))?.parse()?
+            }
+            "--loop-map" => {
+                loop_map_path = Some(args.next().ok_or(AppError::ArgError("--loop-map requires a path"))?)
+            }
+            "--checkpoint" => {
+                checkpoint_path = Some(args.next().ok_or(AppError::ArgError("--checkpoint requires a path"))?)
+            }
+            "--visualize" => visualize = true,
+            _ => {
+                return Err(Box::new(AppError::ArgError(
+                    "Usage: day_06 [<input>] [--svg <path.svg>] [--render <path.svg|path.png>] [--gif <path.gif> [--stride <steps>] [--cell-size <pixels>]] [--loop-map <path.txt|path.svg|path.png>] [--checkpoint <path>] [--visualize] [--format <text|json>] [-v|-vv]",
+                )))
+            }
+        }
+    }
+    if visualize && format == Format::Json {
+        return Err(Box::new(AppError::ArgError("--visualize can't be combined with --format json")));
     }
 
-    let file_path = &args[1];
-    let contents = read_file(file_path)?;
-    let result = count_guard_path(contents)?;
-    
-    println!("Result: {}", result);
+    let contents = read_file(&resolve_input(file_path.as_deref(), 6)?)?;
+
+    if visualize {
+        animate(&contents, &trace_guard_route(&contents)?)?;
+    }
+
+    if let Some(svg_path) = svg_path {
+        let route = trace_guard_route(&contents)?;
+        std::fs::write(&svg_path, render_route_svg(&contents, &route)?)?;
+        eprintln!("{}", msg!("day06-wrote-svg", route.len(), svg_path));
+    }
+
+    if let Some(render_path) = render_path {
+        let visited = write_render(&contents, Path::new(&render_path))?;
+        eprintln!("{}", msg!("day06-wrote-render", visited, render_path));
+    }
+
+    if let Some(gif_path) = gif_path {
+        let route = trace_guard_route(&contents)?;
+        let frames = write_route_gif(&contents, &route, Path::new(&gif_path), gif_options)?;
+        eprintln!("{}", msg!("day06-wrote-gif", frames, route.len(), gif_path));
+    }
+
+    let start = Instant::now();
+    let result = count_guard_path(&contents)?;
+    let progress = SearchProgress::new();
+    let loop_positions = match checkpoint_path {
+        None => find_loop_obstructions(&contents, Algorithm::default(), &progress)?,
+        Some(checkpoint_path) => {
+            let checkpoint = Checkpoint::open(Path::new(&checkpoint_path), &contents)?;
+            let tested = checkpoint.tested();
+            find_loop_obstructions_skipping(&contents, Algorithm::default(), &(&progress, &checkpoint), &tested)?;
+            checkpoint.finish()?
+        }
+    };
+    let loop_count = loop_positions.len();
+    progress.bar.finish_and_clear();
+    let elapsed = start.elapsed();
+
+    if let Some(loop_map_path) = loop_map_path {
+        write_loop_map(&contents, &loop_positions, Path::new(&loop_map_path))?;
+        eprintln!("{}", msg!("day06-wrote-loop-map", loop_count, loop_map_path));
+    }
+
+    match format {
+        Format::Text => {
+            println!("{}", msg!("day06-part1", result));
+            println!("{}", msg!("day06-part2", loop_count));
+
+            let guards = walk_guards(&contents)?;
+            if guards.paths.len() > 1 {
+                for (i, path) in guards.paths.iter().enumerate() {
+                    println!("{}", msg!("day06-guard-visits", i + 1, path.visited.len()));
+                }
+                println!("{}", msg!("day06-coverage", guards.coverage.len()));
+            }
+        }
+        Format::Json => {
+            let report = Report {
+                day: 6,
+                part1: Some(result.to_string()),
+                part2: Some(loop_count.to_string()),
+                elapsed,
+            };
+            println!("{}", report.to_json());
+        }
+    }
     
     Ok(())
 }
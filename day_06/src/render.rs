@@ -0,0 +1,443 @@
+//! Image export of the lab map and the cells the guard visits, for `--render`.
+//!
+//! The map is first reduced to one `Cell` per position, which is then drawn
+//! either as SVG squares or as PNG pixels. `render_route_svg` draws the route
+//! over the same squares for `--svg`, `write_route_gif` animates the cells as
+//! the guard walks its route, and `write_loop_map` marks the positions where
+//! an extra obstruction traps the guard, for `--loop-map`.
+
+use std::borrow::Cow;
+use std::collections::HashSet;
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::Path;
+
+use aoc_common::AppError;
+use ndarray::Array2;
+
+use crate::calculations::{find_start_position, walk, Direction, Step};
+
+/// Size of one grid cell, in SVG user units and in PNG pixels
+const CELL_SIZE: usize = 6;
+
+/// Closing tag of an SVG document, after which nothing else is drawn
+const SVG_END: &str = "</svg>\n";
+
+/// What is drawn at one position of the map
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(u8)]
+pub enum Cell {
+    Floor,
+    Obstacle,
+    Visited,
+    Start,
+    /// The guard's current position, only used in animations
+    Guard,
+    /// A position where an extra obstruction traps the guard in a loop
+    LoopObstruction,
+}
+
+impl Cell {
+    /// Every cell kind, in palette order
+    const ALL: [Cell; 6] = [
+        Cell::Floor,
+        Cell::Obstacle,
+        Cell::Visited,
+        Cell::Start,
+        Cell::Guard,
+        Cell::LoopObstruction,
+    ];
+
+    /// Fill colour as RGB
+    fn rgb(self) -> [u8; 3] {
+        match self {
+            Cell::Floor => [0xff, 0xff, 0xff],
+            Cell::Obstacle => [0x33, 0x33, 0x33],
+            Cell::Visited => [0x9e, 0xca, 0xe1],
+            Cell::Start => [0xe4, 0x1a, 0x1c],
+            Cell::Guard => [0xff, 0x7f, 0x00],
+            Cell::LoopObstruction => [0x4d, 0xaf, 0x4a],
+        }
+    }
+}
+
+/// Settings for `write_route_gif`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GifOptions {
+    /// Route steps advanced between frames
+    pub stride: usize,
+    /// Width and height of one grid cell in pixels
+    pub cell_size: usize,
+    /// Time each frame is shown, in hundredths of a second
+    pub delay: u16,
+}
+
+impl Default for GifOptions {
+    fn default() -> Self {
+        Self { stride: 10, cell_size: 4, delay: 4 }
+    }
+}
+
+/// Classifies every position of the map.
+///
+/// # Arguments
+///
+/// * `grid` - The lab map
+/// * `visited` - Positions the guard stood on
+/// * `start` - The guard's starting position, drawn on top of the visited cells
+pub fn classify_cells(grid: &Array2<char>, visited: &HashSet<(usize, usize)>, start: (usize, usize)) -> Array2<Cell> {
+    Array2::from_shape_fn(grid.dim(), |pos| {
+        if pos == start {
+            Cell::Start
+        } else if grid[pos] == '#' {
+            Cell::Obstacle
+        } else if visited.contains(&pos) {
+            Cell::Visited
+        } else {
+            Cell::Floor
+        }
+    })
+}
+
+/// Renders the cells as an SVG document with one square per non-floor cell
+pub fn render_cells_svg(cells: &Array2<Cell>) -> String {
+    let (rows, cols) = cells.dim();
+    let (width, height) = (cols * CELL_SIZE, rows * CELL_SIZE);
+
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    );
+    let [r, g, b] = Cell::Floor.rgb();
+    let _ = writeln!(svg, r##"<rect width="{width}" height="{height}" fill="#{r:02x}{g:02x}{b:02x}"/>"##);
+    for ((row, col), &cell) in cells.indexed_iter() {
+        if cell == Cell::Floor {
+            continue;
+        }
+        let [r, g, b] = cell.rgb();
+        let _ = writeln!(
+            svg,
+            r##"<rect x="{}" y="{}" width="{CELL_SIZE}" height="{CELL_SIZE}" fill="#{r:02x}{g:02x}{b:02x}"/>"##,
+            col * CELL_SIZE,
+            row * CELL_SIZE,
+        );
+    }
+    svg.push_str(SVG_END);
+    svg
+}
+
+/// Colour used for route segments walked in the given direction
+fn direction_color(direction: Direction) -> &'static str {
+    match direction {
+        Direction::Up => "#e41a1c",
+        Direction::Right => "#377eb8",
+        Direction::Down => "#4daf4a",
+        Direction::Left => "#984ea3",
+    }
+}
+
+/// Centre of a cell in SVG coordinates
+fn cell_center(pos: (usize, usize)) -> (usize, usize) {
+    (pos.1 * CELL_SIZE + CELL_SIZE / 2, pos.0 * CELL_SIZE + CELL_SIZE / 2)
+}
+
+/// Renders the map and the guard's route as an SVG document.
+///
+/// The obstacles and start position are drawn by `render_cells_svg`, and
+/// each step of the route on top of them as a line coloured by the direction
+/// walked.
+///
+/// # Arguments
+///
+/// * `grid` - The map the guard patrols
+/// * `route` - The route as returned by `trace_guard_route`
+///
+/// # Returns
+///
+/// * `Result<String, AppError>` - The SVG document, or an error if the route is empty
+pub fn render_route_svg(grid: &Array2<char>, route: &[Step]) -> Result<String, AppError> {
+    let Some(&(start, _)) = route.first() else {
+        return Err(AppError::NoStartPosition);
+    };
+    let mut svg = render_cells_svg(&classify_cells(grid, &HashSet::new(), start));
+    svg.truncate(svg.len() - SVG_END.len());
+
+    for pair in route.windows(2) {
+        let (x1, y1) = cell_center(pair[0].0);
+        let (x2, y2) = cell_center(pair[1].0);
+        let _ = writeln!(
+            svg,
+            r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}" stroke="{}" stroke-width="2" stroke-linecap="round"/>"#,
+            direction_color(pair[1].1)
+        );
+    }
+    svg.push_str(SVG_END);
+    Ok(svg)
+}
+
+/// Writes the cells as an RGB PNG, each cell a square of pixels.
+///
+/// # Returns
+///
+/// * `Result<(), AppError>` - Success or an error if the image cannot be written
+pub fn write_cells_png(cells: &Array2<Cell>, path: &Path) -> Result<(), AppError> {
+    let (rows, cols) = cells.dim();
+    let (width, height) = (cols * CELL_SIZE, rows * CELL_SIZE);
+
+    let mut pixels = vec![0u8; width * height * 3];
+    for (y, line) in pixels.chunks_exact_mut(width * 3).enumerate() {
+        for (x, pixel) in line.chunks_exact_mut(3).enumerate() {
+            pixel.copy_from_slice(&cells[(y / CELL_SIZE, x / CELL_SIZE)].rgb());
+        }
+    }
+
+    let file = File::create(path)?;
+    let mut encoder = png::Encoder::new(BufWriter::new(file), width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgb);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder.write_header().map_err(io::Error::from)?;
+    writer.write_image_data(&pixels).map_err(io::Error::from)?;
+    Ok(())
+}
+
+/// Scales cells up to palette indices, `cell_size` pixels per cell in each direction
+fn cell_pixels(cells: &Array2<Cell>, cell_size: usize) -> Vec<u8> {
+    let (rows, cols) = cells.dim();
+    let mut pixels = Vec::with_capacity(rows * cols * cell_size * cell_size);
+    for row in cells.rows() {
+        let line: Vec<u8> = row
+            .iter()
+            .flat_map(|&cell| std::iter::repeat_n(cell as u8, cell_size))
+            .collect();
+        for _ in 0..cell_size {
+            pixels.extend_from_slice(&line);
+        }
+    }
+    pixels
+}
+
+/// Writes an animated GIF of the guard walking `route`, one frame every
+/// `options.stride` steps plus a final frame at the end of the route.
+///
+/// # Arguments
+///
+/// * `grid` - The map the guard patrols
+/// * `route` - The route as returned by `trace_guard_route`
+/// * `path` - Path of the GIF file to write
+/// * `options` - Frame stride, cell size and frame delay
+///
+/// # Returns
+///
+/// * `Result<usize, AppError>` - The number of frames written, or an error
+pub fn write_route_gif(grid: &Array2<char>, route: &[Step], path: &Path, options: GifOptions) -> Result<usize, AppError> {
+    let Some(&(start, _)) = route.first() else {
+        return Err(AppError::NoStartPosition);
+    };
+    if options.stride == 0 || options.cell_size == 0 {
+        return Err(AppError::ArgError("--stride and --cell-size must be at least 1"));
+    }
+    let (rows, cols) = grid.dim();
+    let (Ok(width), Ok(height)) = (u16::try_from(cols * options.cell_size), u16::try_from(rows * options.cell_size))
+    else {
+        return Err(AppError::ArgError("--cell-size is too large for a GIF of this map"));
+    };
+
+    let palette: Vec<u8> = Cell::ALL.iter().flat_map(|cell| cell.rgb()).collect();
+    let file = File::create(path)?;
+    let mut encoder = gif::Encoder::new(BufWriter::new(file), width, height, &palette).map_err(io::Error::other)?;
+    encoder.set_repeat(gif::Repeat::Infinite).map_err(io::Error::other)?;
+
+    let mut cells = classify_cells(grid, &HashSet::new(), start);
+    let mut frames = 0;
+    for (index, &(pos, _)) in route.iter().enumerate() {
+        // Mark where the guard has been and draw it at its new position
+        if let Some(&(previous, _)) = index.checked_sub(1).and_then(|i| route.get(i)) {
+            if previous != start {
+                cells[previous] = Cell::Visited;
+            }
+        }
+        cells[pos] = Cell::Guard;
+
+        if index % options.stride == 0 || index + 1 == route.len() {
+            let frame = gif::Frame {
+                width,
+                height,
+                delay: options.delay,
+                buffer: Cow::Owned(cell_pixels(&cells, options.cell_size)),
+                ..gif::Frame::default()
+            };
+            encoder.write_frame(&frame).map_err(io::Error::other)?;
+            frames += 1;
+        }
+    }
+
+    Ok(frames)
+}
+
+/// Walks the guard's patrol and writes an image of it, as SVG or PNG
+/// depending on the extension of `path`.
+///
+/// # Returns
+///
+/// * `Result<usize, AppError>` - The number of visited cells drawn, or an error
+pub fn write_render(grid: &Array2<char>, path: &Path) -> Result<usize, AppError> {
+    let (start, facing) = find_start_position(grid).ok_or(AppError::NoStartPosition)?;
+    let visited = walk(grid, start, facing).visited;
+    let cells = classify_cells(grid, &visited, start);
+
+    match path.extension().and_then(|ext| ext.to_str()) {
+        Some("svg") => std::fs::write(path, render_cells_svg(&cells))?,
+        Some("png") => write_cells_png(&cells, path)?,
+        _ => return Err(AppError::ArgError("--render path must end in .svg or .png")),
+    }
+    Ok(visited.len())
+}
+
+/// Draws the map as text with every loop-causing position marked `O`, as in
+/// the puzzle description
+pub fn render_loop_map_text(grid: &Array2<char>, obstructions: &[(usize, usize)]) -> String {
+    let mut map = grid.clone();
+    for &pos in obstructions {
+        map[pos] = 'O';
+    }
+
+    let mut text = String::with_capacity(map.len() + map.nrows());
+    for row in map.rows() {
+        text.extend(row.iter());
+        text.push('\n');
+    }
+    text
+}
+
+/// Writes the map with every loop-causing position marked, as text, SVG or
+/// PNG depending on the extension of `path`.
+///
+/// # Arguments
+///
+/// * `grid` - The lab map
+/// * `obstructions` - The positions returned by `find_loop_obstructions`
+/// * `path` - Path of the `.txt`, `.svg` or `.png` file to write
+///
+/// # Returns
+///
+/// * `Result<(), AppError>` - Success or an error if the file cannot be written
+pub fn write_loop_map(grid: &Array2<char>, obstructions: &[(usize, usize)], path: &Path) -> Result<(), AppError> {
+    let extension = path.extension().and_then(|ext| ext.to_str());
+    if extension == Some("txt") {
+        std::fs::write(path, render_loop_map_text(grid, obstructions))?;
+        return Ok(());
+    }
+
+    let (start, facing) = find_start_position(grid).ok_or(AppError::NoStartPosition)?;
+    let mut cells = classify_cells(grid, &walk(grid, start, facing).visited, start);
+    for &pos in obstructions {
+        cells[pos] = Cell::LoopObstruction;
+    }
+
+    match extension {
+        Some("svg") => std::fs::write(path, render_cells_svg(&cells))?,
+        Some("png") => write_cells_png(&cells, path)?,
+        _ => return Err(AppError::ArgError("--loop-map path must end in .txt, .svg or .png")),
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::read_file;
+
+    #[test]
+    fn test_classify_cells() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = read_file("data/inputtest")?;
+        let (start, facing) = find_start_position(&grid).ok_or("no start")?;
+        let cells = classify_cells(&grid, &walk(&grid, start, facing).visited, start);
+
+        assert_eq!(cells[(6, 4)], Cell::Start);
+        assert_eq!(cells[(0, 4)], Cell::Obstacle);
+        assert_eq!(cells[(1, 4)], Cell::Visited);
+        assert_eq!(cells[(0, 0)], Cell::Floor);
+        assert_eq!(cells.iter().filter(|&&cell| cell == Cell::Visited).count(), 40);
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_cells_svg() {
+        let mut cells = Array2::from_elem((2, 3), Cell::Floor);
+        cells[(0, 1)] = Cell::Obstacle;
+        cells[(1, 2)] = Cell::Start;
+
+        let svg = render_cells_svg(&cells);
+        assert!(svg.starts_with(r#"<svg xmlns="http://www.w3.org/2000/svg" width="18" height="12""#));
+        // The background plus one square per non-floor cell
+        assert_eq!(svg.matches("<rect").count(), 3);
+        assert!(svg.contains(r##"<rect x="6" y="0" width="6" height="6" fill="#333333"/>"##));
+        assert!(svg.contains(r##"<rect x="12" y="6" width="6" height="6" fill="#e41a1c"/>"##));
+    }
+
+    #[test]
+    fn test_render_route_svg() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = read_file("data/inputtest")?;
+        let route = crate::calculations::trace_guard_route(&grid)?;
+        let svg = render_route_svg(&grid, &route)?;
+
+        assert!(svg.starts_with("<svg"));
+        assert!(svg.ends_with(SVG_END));
+        assert_eq!(svg.matches("fill=\"#333333\"").count(), 8);
+        assert_eq!(svg.matches("<line").count(), route.len() - 1);
+        // The first step is drawn from the centre of the start cell at (6, 4)
+        assert!(svg.contains(r##"<line x1="27" y1="39" x2="27" y2="33" stroke="#e41a1c""##));
+        assert!(matches!(render_route_svg(&grid, &[]), Err(AppError::NoStartPosition)));
+        Ok(())
+    }
+
+    #[test]
+    fn test_cell_pixels() {
+        let mut cells = Array2::from_elem((1, 2), Cell::Floor);
+        cells[(0, 1)] = Cell::Guard;
+        assert_eq!(cell_pixels(&cells, 2), [0, 0, 4, 4, 0, 0, 4, 4]);
+    }
+
+    #[test]
+    fn test_write_route_gif() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = read_file("data/inputtest")?;
+        let route = crate::calculations::trace_guard_route(&grid)?;
+        let path = std::env::temp_dir().join(format!("day_06_route_{}.gif", std::process::id()));
+
+        // 45 steps at a stride of 10 gives frames 0, 10, 20, 30, 40 and the last step
+        let options = GifOptions { stride: 10, cell_size: 2, delay: 1 };
+        let frames = write_route_gif(&grid, &route, &path, options)?;
+        let bytes = std::fs::read(&path)?;
+        std::fs::remove_file(&path)?;
+
+        assert_eq!(frames, 6);
+        assert!(bytes.starts_with(b"GIF89a"));
+        assert_eq!(&bytes[6..10], [20, 0, 20, 0]);
+
+        let options = GifOptions { stride: 0, ..GifOptions::default() };
+        assert!(matches!(write_route_gif(&grid, &route, &path, options), Err(AppError::ArgError(_))));
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_loop_map_text() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = read_file("data/inputtest")?;
+        let text = render_loop_map_text(&grid, &[(6, 3), (9, 7)]);
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 10);
+        assert_eq!(lines[6], ".#.O^.....");
+        assert_eq!(lines[9], "......#O..");
+        assert_eq!(text.matches('O').count(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_write_render_rejects_unknown_extension() -> Result<(), Box<dyn std::error::Error>> {
+        let grid = read_file("data/inputtest")?;
+        let result = write_render(&grid, Path::new("route.gif"));
+        assert!(matches!(result, Err(AppError::ArgError(_))));
+        Ok(())
+    }
+}
@@ -0,0 +1,124 @@
+//! Module for deciding which calibration equations can be made true by
+//! inserting operators between their operands.
+
+use aoc_common::AppError;
+
+/// A calibration equation: a target value and the operands that must produce it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Equation {
+    pub target: u64,
+    pub operands: Vec<u64>,
+}
+
+/// Removes the decimal digits of `suffix` from the end of `value`
+///
+/// # Returns
+/// `Some(prefix)` if `value` ends with the digits of `suffix`, `None` otherwise,
+/// including when `suffix` has too many digits for the prefix to fit in a `u64`
+fn strip_concatenation(value: u64, suffix: u64) -> Option<u64> {
+    let mut modulus: u64 = 10;
+    while modulus <= suffix {
+        modulus = modulus.checked_mul(10)?;
+    }
+    (value % modulus == suffix).then_some(value / modulus)
+}
+
+/// Checks whether operators can be inserted between the operands to reach the target
+///
+/// Operators are evaluated left to right. The search works backwards from the
+/// last operand, which prunes most branches early: addition is only undone
+/// when the target is large enough, multiplication only when the operand
+/// divides the target, and concatenation only when the target ends with the
+/// operand's digits.
+///
+/// # Arguments
+/// * `target` - The value the equation must produce
+/// * `operands` - The operands in order
+/// * `allow_concatenation` - Whether the `||` operator may be used (part 2)
+///
+/// # Returns
+/// `true` if some choice of operators produces the target, `false` otherwise
+fn can_produce(target: u64, operands: &[u64], allow_concatenation: bool) -> bool {
+    let Some((&last, rest)) = operands.split_last() else {
+        return false;
+    };
+    if rest.is_empty() {
+        return target == last;
+    }
+
+    // Multiplying by a trailing zero gives zero whatever the operands before it
+    (last == 0 && target == 0)
+        || (target >= last && can_produce(target - last, rest, allow_concatenation))
+        || (last != 0 && target.is_multiple_of(last) && can_produce(target / last, rest, allow_concatenation))
+        || (allow_concatenation
+            && strip_concatenation(target, last)
+                .is_some_and(|prefix| can_produce(prefix, rest, allow_concatenation)))
+}
+
+/// Sums the targets of all equations that can be made true
+///
+/// # Arguments
+/// * `equations` - The calibration equations
+/// * `allow_concatenation` - `false` for part 1 (`+` and `*`), `true` for part 2 (`+`, `*` and `||`)
+///
+/// # Returns
+/// The total calibration result, or an error if it overflows a `u64`
+pub fn total_calibration(equations: &[Equation], allow_concatenation: bool) -> Result<u64, AppError> {
+    equations
+        .iter()
+        .filter(|equation| can_produce(equation.target, &equation.operands, allow_concatenation))
+        .try_fold(0u64, |total, equation| total.checked_add(equation.target))
+        .ok_or(AppError::Overflow("total calibration result"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::read_equations;
+    use std::error::Error;
+
+    #[test]
+    fn test_total_calibration() -> Result<(), Box<dyn Error>> {
+        let equations = read_equations("data/inputtest")?;
+        assert_eq!(total_calibration(&equations, false)?, 3749);
+        Ok(())
+    }
+
+    #[test]
+    fn test_total_calibration_with_concatenation() -> Result<(), Box<dyn Error>> {
+        let equations = read_equations("data/inputtest")?;
+        assert_eq!(total_calibration(&equations, true)?, 11387);
+        Ok(())
+    }
+
+    #[test]
+    fn test_strip_concatenation() {
+        assert_eq!(strip_concatenation(156, 6), Some(15));
+        assert_eq!(strip_concatenation(1510, 10), Some(15));
+        assert_eq!(strip_concatenation(156, 5), None);
+        assert_eq!(strip_concatenation(6, 6), Some(0));
+        assert_eq!(strip_concatenation(6, 16), None);
+    }
+
+    #[test]
+    fn test_can_produce() {
+        assert!(can_produce(292, &[11, 6, 16, 20], false));
+        assert!(!can_produce(7290, &[6, 8, 6, 15], false));
+        assert!(can_produce(7290, &[6, 8, 6, 15], true));
+        assert!(can_produce(0, &[0, 0], false));
+        assert!(can_produce(7, &[3, 0, 7], false));
+        assert!(can_produce(0, &[5, 0], false));
+        assert!(!can_produce(5, &[3, 0, 1], false));
+    }
+
+    #[test]
+    fn test_large_values() {
+        // The concatenation modulus would have to exceed u64::MAX
+        assert!(!can_produce(1, &[1, 10_000_000_000_000_000_000], true));
+        assert!(can_produce(u64::MAX, &[0, u64::MAX], true));
+
+        let equation = Equation { target: u64::MAX, operands: vec![u64::MAX] };
+        let equations = vec![equation.clone(), equation];
+        assert!(matches!(total_calibration(&equations, false), Err(AppError::Overflow(_))));
+    }
+}
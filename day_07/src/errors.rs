@@ -0,0 +1,30 @@
+//! Errors from reading day 7's calibration equations, which only this day can produce.
+
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{msg, AppError};
+
+/// What can be wrong with an equation, carried by `AppError::Puzzle`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PuzzleError {
+    /// A line that is not of the form `target: operand operand ...`
+    InvalidEquation(String),
+}
+
+impl Error for PuzzleError {}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::InvalidEquation(line) => msg!("error-invalid-equation", line),
+        };
+        f.write_str(&message)
+    }
+}
+
+impl From<PuzzleError> for AppError {
+    fn from(error: PuzzleError) -> Self {
+        Self::Puzzle(Box::new(error))
+    }
+}
@@ -0,0 +1,76 @@
+use crate::calculations::Equation;
+use crate::errors::PuzzleError;
+use aoc_common::AppError;
+use tracing::debug;
+
+/// Parses the calibration equations, one per line.
+///
+/// # Arguments
+///
+/// * `content` - The puzzle input
+///
+/// # Returns
+///
+/// * `Result<Vec<Equation>, AppError>` - The equations in input order, or an error
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - A line is not of the form `target: operand operand ...`
+/// - A number cannot be parsed
+pub fn parse_equations(content: &str) -> Result<Vec<Equation>, AppError> {
+    let mut equations = Vec::new();
+    for line in content.lines().filter(|line| !line.trim().is_empty()) {
+        let (target, operands) = line
+            .split_once(':')
+            .ok_or_else(|| PuzzleError::InvalidEquation(line.to_string()))?;
+        let operands: Vec<u64> = operands
+            .split_whitespace()
+            .map(str::parse)
+            .collect::<Result<_, _>>()?;
+        if operands.is_empty() {
+            return Err(PuzzleError::InvalidEquation(line.to_string()).into());
+        }
+        equations.push(Equation {
+            target: target.trim().parse()?,
+            operands,
+        });
+    }
+
+    Ok(equations)
+}
+
+/// Reads the calibration equations from a file
+///
+/// # Arguments
+///
+/// * `path` - A string slice that holds the path to the file
+///
+/// # Returns
+///
+/// * `Result<Vec<Equation>, AppError>` - The parsed input, or an error if
+///   the file can't be read or parsed
+pub fn read_equations(path: &str) -> Result<Vec<Equation>, AppError> {
+    let content = std::fs::read_to_string(path)?;
+    debug!("Read {} bytes", content.len());
+    parse_equations(&content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_equations() -> Result<(), AppError> {
+        let equations = read_equations("data/inputtest")?;
+        assert_eq!(equations.len(), 9);
+        assert_eq!(
+            equations[1],
+            Equation {
+                target: 3267,
+                operands: vec![81, 40, 27]
+            }
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+//! Day 7: Bridge Repair
+//!
+//! Library entry points shared by the binary and the `aoc` runner.
+
+pub mod calculations;
+pub mod errors;
+pub mod file_io;
+
+use aoc_common::{AppError, Solver};
+
+use calculations::{total_calibration, Equation};
+use file_io::parse_equations;
+
+/// Solver for the bridge calibration equations
+pub struct Day07;
+
+impl Solver for Day07 {
+    /// The calibration equations in input order
+    type Parsed = Vec<Equation>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AppError> {
+        parse_equations(input)
+    }
+
+    /// Sums the targets reachable with `+` and `*`
+    fn part1(equations: &Self::Parsed) -> Result<String, AppError> {
+        Ok(total_calibration(equations, false)?.to_string())
+    }
+
+    /// Sums the targets reachable when concatenation is allowed as well
+    fn part2(equations: &Self::Parsed) -> Result<String, AppError> {
+        Ok(total_calibration(equations, true)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<(), AppError> {
+        let parsed = Day07::parse(&std::fs::read_to_string("data/inputtest")?)?;
+        assert_eq!(Day07::part1(&parsed)?, "3749");
+        assert_eq!(Day07::part2(&parsed)?, "11387");
+        Ok(())
+    }
+}
@@ -1,3 +1,61 @@
-fn main() {
-    println!("Welcome to Day 7!");
+//! Day 7: Bridge Repair
+//!
+//! This program checks calibration equations of the form `target: a b c ...`:
+//! 1. Sums the targets that can be produced by inserting `+` and `*` between the operands
+//! 2. Sums the targets that can be produced when concatenation (`||`) is allowed as well
+//!
+//! Operators are always evaluated left to right.
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run -- path/to/input/file
+//! cargo run -- path/to/input/file --format json
+//! ```
+use std::error::Error;
+use std::time::Instant;
+
+use aoc_common::input::resolve_input;
+use aoc_common::logging;
+use aoc_common::messages;
+use aoc_common::msg;
+use aoc_common::output::{take_format, Format, Report};
+
+use day_07::calculations::total_calibration;
+use day_07::file_io::read_equations;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let format = take_format(&mut args)?;
+    logging::init(logging::take_verbosity(&mut args));
+    messages::init(messages::take_lang(&mut args)?.as_deref())?;
+    if format == Format::Text {
+        println!("{}", msg!("welcome", 7));
+    }
+
+    let path = resolve_input(args.first().map(String::as_str), 7)?;
+    let equations = read_equations(&path)?;
+
+    let start = Instant::now();
+    let part1 = total_calibration(&equations, false)?;
+    let part2 = total_calibration(&equations, true)?;
+    let elapsed = start.elapsed();
+
+    match format {
+        Format::Text => {
+            println!("{}", msg!("day07-part1", part1));
+            println!("{}", msg!("day07-part2", part2));
+        }
+        Format::Json => {
+            let report = Report {
+                day: 7,
+                part1: Some(part1.to_string()),
+                part2: Some(part2.to_string()),
+                elapsed,
+            };
+            println!("{}", report.to_json());
+        }
+    }
+
+    Ok(())
 }
@@ -0,0 +1,129 @@
+use ndarray::Array2;
+use std::collections::{HashMap, HashSet};
+
+/// Groups antenna positions by frequency. Any character other than `.` or `#`
+/// is an antenna, and antennas with the same character share a frequency.
+///
+/// # Arguments
+///
+/// * `grid` - The antenna map
+///
+/// # Returns
+///
+/// * `HashMap<char, Vec<(usize, usize)>>` - The positions of the antennas of each frequency
+fn antenna_groups(grid: &Array2<char>) -> HashMap<char, Vec<(usize, usize)>> {
+    let mut groups: HashMap<char, Vec<(usize, usize)>> = HashMap::new();
+    for (pos, &cell) in grid.indexed_iter() {
+        if cell != '.' && cell != '#' {
+            groups.entry(cell).or_default().push(pos);
+        }
+    }
+    groups
+}
+
+/// Returns the position `step` times the offset from `from` to `to` away from `to`,
+/// or `None` if it falls outside the grid
+fn offset_position(
+    grid: &Array2<char>,
+    from: (usize, usize),
+    to: (usize, usize),
+    step: isize,
+) -> Option<(usize, usize)> {
+    let row = to.0 as isize + step * (to.0 as isize - from.0 as isize);
+    let col = to.1 as isize + step * (to.1 as isize - from.1 as isize);
+    (row >= 0 && col >= 0 && (row as usize) < grid.nrows() && (col as usize) < grid.ncols())
+        .then_some((row as usize, col as usize))
+}
+
+/// Finds every antinode created by pairs of antennas with the same frequency.
+///
+/// Without resonance, each pair creates two antinodes: one beyond each antenna,
+/// at the same distance as between the antennas. With resonance, every grid
+/// position in line with the pair at a multiple of that distance is an antinode,
+/// including the antennas themselves.
+///
+/// # Arguments
+///
+/// * `grid` - The antenna map
+/// * `resonant` - `false` for part 1, `true` for part 2 (resonant harmonics)
+///
+/// # Returns
+///
+/// * `HashSet<(usize, usize)>` - The distinct antinode positions inside the grid
+pub fn find_antinodes(grid: &Array2<char>, resonant: bool) -> HashSet<(usize, usize)> {
+    let mut antinodes = HashSet::new();
+
+    for positions in antenna_groups(grid).values() {
+        for (i, &a) in positions.iter().enumerate() {
+            for &b in &positions[i + 1..] {
+                for (from, to) in [(a, b), (b, a)] {
+                    if resonant {
+                        let mut step = 0;
+                        while let Some(pos) = offset_position(grid, from, to, step) {
+                            antinodes.insert(pos);
+                            step += 1;
+                        }
+                    } else if let Some(pos) = offset_position(grid, from, to, 1) {
+                        antinodes.insert(pos);
+                    }
+                }
+            }
+        }
+    }
+
+    antinodes
+}
+
+/// Renders the map with `#` marking antinodes that are not on an antenna.
+///
+/// # Arguments
+///
+/// * `grid` - The antenna map
+/// * `antinodes` - The antinode positions to mark
+///
+/// # Returns
+///
+/// * `String` - The rendered map, one line per row
+pub fn render_antinodes(grid: &Array2<char>, antinodes: &HashSet<(usize, usize)>) -> String {
+    let mut output = String::with_capacity(grid.len() + grid.nrows());
+    for (row, line) in grid.rows().into_iter().enumerate() {
+        for (col, &cell) in line.iter().enumerate() {
+            if cell == '.' && antinodes.contains(&(row, col)) {
+                output.push('#');
+            } else {
+                output.push(cell);
+            }
+        }
+        output.push('\n');
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::read_file;
+    use std::error::Error;
+
+    #[test]
+    fn test_antinode_count() -> Result<(), Box<dyn Error>> {
+        let grid = read_file("data/inputtest")?;
+        assert_eq!(find_antinodes(&grid, false).len(), 14);
+        Ok(())
+    }
+
+    #[test]
+    fn test_resonant_antinode_count() -> Result<(), Box<dyn Error>> {
+        let grid = read_file("data/inputtest")?;
+        assert_eq!(find_antinodes(&grid, true).len(), 34);
+        Ok(())
+    }
+
+    #[test]
+    fn test_render_antinodes() -> Result<(), Box<dyn Error>> {
+        let grid = Array2::from_shape_vec((3, 7), ".........a.a.........".chars().collect())?;
+        let antinodes = find_antinodes(&grid, false);
+        assert_eq!(render_antinodes(&grid, &antinodes), ".......\n#.a.a.#\n.......\n");
+        Ok(())
+    }
+}
@@ -0,0 +1,42 @@
+use ndarray::Array2;
+
+use crate::AppError;
+
+/// Converts puzzle input text into a 2D array of characters.
+/// Each line becomes a row in the array.
+///
+/// # Arguments
+///
+/// * `content` - The puzzle input
+///
+/// # Returns
+///
+/// * `Result<Array2<char>, AppError>` - A 2D array of characters, or an error
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The lines have different lengths
+pub fn parse_grid(content: &str) -> Result<Array2<char>, AppError> {
+    let lines: Vec<&str> = content.lines().collect();
+    let rows = lines.len();
+    let cols = lines.first().map_or(0, |line| line.len());
+
+    let data: Vec<char> = lines.join("").chars().collect();
+    Array2::from_shape_vec((rows, cols), data).map_err(|_| AppError::Array2CreationError)
+}
+
+/// Reads a grid of characters from a file
+///
+/// # Arguments
+///
+/// * `filename` - A string slice that holds the path to the file
+///
+/// # Returns
+///
+/// * `Result<Array2<char>, AppError>` - The parsed input, or an error if
+///   the file can't be read or parsed
+pub fn read_file(filename: &str) -> Result<Array2<char>, AppError> {
+    let content = std::fs::read_to_string(filename)?;
+    parse_grid(&content)
+}
@@ -0,0 +1,47 @@
+//! Day 8: Resonant Collinearity
+//!
+//! Library entry points shared by the binary and the `aoc` runner.
+
+pub mod calculations;
+pub mod file_io;
+
+use aoc_common::{AppError, Solver};
+use ndarray::Array2;
+
+use calculations::find_antinodes;
+use file_io::parse_grid;
+
+/// Solver for the antenna map
+pub struct Day08;
+
+impl Solver for Day08 {
+    /// The antenna map
+    type Parsed = Array2<char>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AppError> {
+        parse_grid(input)
+    }
+
+    /// Counts the antinode locations of each antenna pair
+    fn part1(grid: &Self::Parsed) -> Result<String, AppError> {
+        Ok(find_antinodes(grid, false).len().to_string())
+    }
+
+    /// Counts the antinode locations once resonant harmonics are taken into account
+    fn part2(grid: &Self::Parsed) -> Result<String, AppError> {
+        Ok(find_antinodes(grid, true).len().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<(), AppError> {
+        let parsed = Day08::parse(&std::fs::read_to_string("data/inputtest")?)?;
+        assert_eq!(Day08::part1(&parsed)?, "14");
+        assert_eq!(Day08::part2(&parsed)?, "34");
+        Ok(())
+    }
+}
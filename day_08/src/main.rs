@@ -1,3 +1,74 @@
-fn main() {
-    println!("Welcome to Day 8!");
+//! Day 8: Resonant Collinearity
+//!
+//! This program counts the antinodes created by pairs of same-frequency antennas:
+//! 1. Antinodes at twice the distance between each pair of antennas
+//! 2. Antinodes at every grid position in line with a pair (resonant harmonics)
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run -- path/to/input/file
+//! cargo run -- path/to/input/file --format json
+//! cargo run -- path/to/input/file --render
+//! ```
+//!
+//! With `--render`, the map is printed for both parts with `#` marking the antinodes.
+use std::error::Error;
+use std::time::Instant;
+
+use aoc_common::input::resolve_input;
+use aoc_common::logging;
+use aoc_common::messages;
+use aoc_common::msg;
+use aoc_common::output::{take_format, Format, Report};
+use aoc_common::AppError;
+
+use day_08::calculations::{find_antinodes, render_antinodes};
+use day_08::file_io::read_file;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let format = take_format(&mut args)?;
+    logging::init(logging::take_verbosity(&mut args));
+    messages::init(messages::take_lang(&mut args)?.as_deref())?;
+    if format == Format::Text {
+        println!("{}", msg!("welcome", 8));
+    }
+    let (path, render) = match args.as_slice() {
+        [] => (None, false),
+        [flag] if flag == "--render" => (None, true),
+        [path] => (Some(path.as_str()), false),
+        [path, flag] if flag == "--render" => (Some(path.as_str()), true),
+        _ => return Err(Box::new(AppError::ArgError("Usage: day_08 [<input>] [--render] [--format <text|json>] [-v|-vv]"))),
+    };
+    let grid = read_file(&resolve_input(path, 8)?)?;
+
+    let start = Instant::now();
+    let antinodes = find_antinodes(&grid, false);
+    let harmonic_antinodes = find_antinodes(&grid, true);
+    let elapsed = start.elapsed();
+
+    match format {
+        Format::Text => {
+            println!("{}", msg!("day08-part1", antinodes.len()));
+            if render {
+                println!("{}", render_antinodes(&grid, &antinodes));
+            }
+            println!("{}", msg!("day08-part2", harmonic_antinodes.len()));
+            if render {
+                println!("{}", render_antinodes(&grid, &harmonic_antinodes));
+            }
+        }
+        Format::Json => {
+            let report = Report {
+                day: 8,
+                part1: Some(antinodes.len().to_string()),
+                part2: Some(harmonic_antinodes.len().to_string()),
+                elapsed,
+            };
+            println!("{}", report.to_json());
+        }
+    }
+
+    Ok(())
 }
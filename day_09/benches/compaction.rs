@@ -0,0 +1,30 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use day_09::calculations::{compact_blocks, compact_files};
+use day_09::file_io::parse_disk_map;
+
+/// Number of digits in the generated disk map, about the size of a real input
+const DISK_MAP_LEN: usize = 20_000;
+
+/// Generates a deterministic pseudo-random disk map; file lengths are 1-9, free spans 0-9
+fn generate_disk_map(len: usize) -> String {
+    let mut state: u64 = 0x2024_1209;
+    (0..len)
+        .map(|i| {
+            state = state.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            let digit = (state >> 33) % 10;
+            let digit = if i % 2 == 0 { digit.max(1) } else { digit };
+            char::from(b'0' + digit as u8)
+        })
+        .collect()
+}
+
+fn bench_compaction(c: &mut Criterion) {
+    let disk_map = parse_disk_map(&generate_disk_map(DISK_MAP_LEN)).unwrap();
+
+    c.bench_function("compact_blocks", |b| b.iter(|| compact_blocks(black_box(&disk_map))));
+    c.bench_function("compact_files", |b| b.iter(|| compact_files(black_box(&disk_map))));
+}
+
+criterion_group!(benches, bench_compaction);
+criterion_main!(benches);
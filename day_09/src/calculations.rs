@@ -0,0 +1,140 @@
+//! Module for compacting the disk and computing the filesystem checksum.
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+/// Longest possible free span, as each span length is a single digit
+const MAX_SPAN: usize = 9;
+
+/// The layout of files and free space on the disk
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct DiskMap {
+    /// `(start, length)` of each file, indexed by file ID
+    pub files: Vec<(usize, usize)>,
+    /// `(start, length)` of each non-empty free span, in disk order
+    pub free: Vec<(usize, usize)>,
+}
+
+/// Checksum contribution of a file with the given ID occupying `length` blocks from `start`
+fn file_checksum(id: usize, start: usize, length: usize) -> u64 {
+    // Sum of the block positions start..start + length
+    let positions = length * start + length * length.saturating_sub(1) / 2;
+    (id * positions) as u64
+}
+
+/// Moves file blocks one at a time from the end of the disk into the leftmost
+/// free block, until no gaps remain, and returns the resulting checksum.
+///
+/// Runs in time linear in the number of blocks, using one pointer that scans
+/// free blocks from the left and one that scans file blocks from the right.
+///
+/// # Arguments
+/// * `disk_map` - The disk layout
+///
+/// # Returns
+/// The filesystem checksum after compaction
+pub fn compact_blocks(disk_map: &DiskMap) -> u64 {
+    let size = disk_map.files.iter().map(|&(start, length)| start + length).max().unwrap_or(0);
+    let mut blocks: Vec<Option<usize>> = vec![None; size];
+    for (id, &(start, length)) in disk_map.files.iter().enumerate() {
+        blocks[start..start + length].fill(Some(id));
+    }
+
+    let (mut left, mut right) = (0, size);
+    loop {
+        while left < right && blocks[left].is_some() {
+            left += 1;
+        }
+        while right > left && blocks[right - 1].is_none() {
+            right -= 1;
+        }
+        if right <= left + 1 {
+            break;
+        }
+        blocks.swap(left, right - 1);
+    }
+
+    blocks
+        .iter()
+        .enumerate()
+        .filter_map(|(position, block)| block.map(|id| (position * id) as u64))
+        .sum()
+}
+
+/// Moves whole files, highest ID first, into the leftmost free span that can
+/// hold them (if that span lies to the left of the file), and returns the
+/// resulting checksum.
+///
+/// Free spans are kept in one min-heap of start positions per span length, so
+/// finding the leftmost span that fits needs at most `MAX_SPAN` heap lookups
+/// instead of a scan over the whole disk.
+///
+/// # Arguments
+/// * `disk_map` - The disk layout
+///
+/// # Returns
+/// The filesystem checksum after compaction
+pub fn compact_files(disk_map: &DiskMap) -> u64 {
+    let mut spans: Vec<BinaryHeap<Reverse<usize>>> = vec![BinaryHeap::new(); MAX_SPAN + 1];
+    for &(start, length) in &disk_map.free {
+        spans[length.min(MAX_SPAN)].push(Reverse(start));
+    }
+
+    let mut checksum = 0;
+    for (id, &(file_start, file_length)) in disk_map.files.iter().enumerate().rev() {
+        let best = (file_length.max(1)..=MAX_SPAN)
+            .filter_map(|length| spans[length].peek().map(|&Reverse(start)| (start, length)))
+            .filter(|&(start, _)| start < file_start)
+            .min();
+
+        let start = match best {
+            Some((span_start, span_length)) => {
+                spans[span_length].pop();
+                let remaining = span_length - file_length;
+                if remaining > 0 {
+                    spans[remaining].push(Reverse(span_start + file_length));
+                }
+                span_start
+            }
+            None => file_start,
+        };
+        checksum += file_checksum(id, start, file_length);
+    }
+
+    checksum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::{parse_disk_map, read_disk_map};
+    use std::error::Error;
+
+    #[test]
+    fn test_compact_blocks() -> Result<(), Box<dyn Error>> {
+        let disk_map = read_disk_map("data/inputtest")?;
+        assert_eq!(compact_blocks(&disk_map), 1928);
+        Ok(())
+    }
+
+    #[test]
+    fn test_compact_files() -> Result<(), Box<dyn Error>> {
+        let disk_map = read_disk_map("data/inputtest")?;
+        assert_eq!(compact_files(&disk_map), 2858);
+        Ok(())
+    }
+
+    /// "12345" compacts to 022111222......, checksum 0*0 + 1*2 + 2*2 + 3*1 + ...
+    #[test]
+    fn test_compact_blocks_small() -> Result<(), Box<dyn Error>> {
+        let disk_map = parse_disk_map("12345")?;
+        assert_eq!(compact_blocks(&disk_map), 60);
+        Ok(())
+    }
+
+    #[test]
+    fn test_file_checksum() {
+        assert_eq!(file_checksum(2, 3, 3), 2 * (3 + 4 + 5));
+        assert_eq!(file_checksum(7, 4, 0), 0);
+    }
+}
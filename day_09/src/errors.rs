@@ -0,0 +1,30 @@
+//! Errors from reading day 9's disk map, which only this day can produce.
+
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{msg, AppError};
+
+/// What can be wrong with the disk map, carried by `AppError::Puzzle`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PuzzleError {
+    /// A character in the disk map that is not a digit
+    InvalidDigit(char),
+}
+
+impl Error for PuzzleError {}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::InvalidDigit(c) => msg!("error-invalid-digit", format!("{:?}", c)),
+        };
+        f.write_str(&message)
+    }
+}
+
+impl From<PuzzleError> for AppError {
+    fn from(error: PuzzleError) -> Self {
+        Self::Puzzle(Box::new(error))
+    }
+}
@@ -0,0 +1,66 @@
+use crate::calculations::DiskMap;
+use crate::errors::PuzzleError;
+use aoc_common::AppError;
+use tracing::debug;
+
+/// Parses a dense disk map, where digits alternate between the length of a
+/// file and the length of the free space that follows it.
+///
+/// # Arguments
+///
+/// * `content` - The disk map; surrounding whitespace is ignored
+///
+/// # Returns
+///
+/// * `Result<DiskMap, AppError>` - The file and free-space layout, or an error if a
+///   character is not a digit
+pub fn parse_disk_map(content: &str) -> Result<DiskMap, AppError> {
+    let mut disk_map = DiskMap::default();
+    let mut position = 0;
+
+    for (i, c) in content.trim().chars().enumerate() {
+        let length = c.to_digit(10).ok_or(PuzzleError::InvalidDigit(c))? as usize;
+        if i % 2 == 0 {
+            disk_map.files.push((position, length));
+        } else if length > 0 {
+            disk_map.free.push((position, length));
+        }
+        position += length;
+    }
+
+    Ok(disk_map)
+}
+
+/// Reads a file containing a dense disk map.
+///
+/// # Arguments
+///
+/// * `path` - A string slice that holds the path to the file
+///
+/// # Returns
+///
+/// * `Result<DiskMap, AppError>` - The parsed disk map, or an error
+pub fn read_disk_map(path: &str) -> Result<DiskMap, AppError> {
+    let content = std::fs::read_to_string(path)?;
+    debug!("Read {} bytes", content.len());
+    parse_disk_map(&content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_disk_map() -> Result<(), AppError> {
+        let disk_map = parse_disk_map("12345\n")?;
+        assert_eq!(disk_map.files, vec![(0, 1), (3, 3), (10, 5)]);
+        assert_eq!(disk_map.free, vec![(1, 2), (6, 4)]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_disk_map_invalid() {
+        let error = parse_disk_map("12x").err();
+        assert_eq!(error.as_ref().and_then(AppError::puzzle), Some(&PuzzleError::InvalidDigit('x')));
+    }
+}
@@ -0,0 +1,47 @@
+//! Day 9: Disk Fragmenter
+//!
+//! Library entry points shared by the binary, the benchmarks and the `aoc` runner.
+
+pub mod calculations;
+pub mod errors;
+pub mod file_io;
+
+use aoc_common::{AppError, Solver};
+
+use calculations::{compact_blocks, compact_files, DiskMap};
+use file_io::parse_disk_map;
+
+/// Solver for the amphipod's disk map
+pub struct Day09;
+
+impl Solver for Day09 {
+    /// The file and free-space layout
+    type Parsed = DiskMap;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AppError> {
+        parse_disk_map(input)
+    }
+
+    /// Checksum after moving single blocks into the leftmost free space
+    fn part1(disk_map: &Self::Parsed) -> Result<String, AppError> {
+        Ok(compact_blocks(disk_map).to_string())
+    }
+
+    /// Checksum after moving whole files into the leftmost free span that fits them
+    fn part2(disk_map: &Self::Parsed) -> Result<String, AppError> {
+        Ok(compact_files(disk_map).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<(), AppError> {
+        let parsed = Day09::parse(&std::fs::read_to_string("data/inputtest")?)?;
+        assert_eq!(Day09::part1(&parsed)?, "1928");
+        assert_eq!(Day09::part2(&parsed)?, "2858");
+        Ok(())
+    }
+}
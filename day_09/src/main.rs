@@ -1,3 +1,61 @@
-fn main() {
-    println!("Welcome to Day 9!");
+//! Day 9: Disk Fragmenter
+//!
+//! This program compacts a disk described by a dense disk map and prints the
+//! filesystem checksum:
+//! 1. Moving individual file blocks into the leftmost free block
+//! 2. Moving whole files into the leftmost free span that fits them
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run -- path/to/input/file
+//! cargo run -- path/to/input/file --format json
+//! cargo bench
+//! ```
+use std::error::Error;
+use std::time::Instant;
+
+use aoc_common::input::resolve_input;
+use aoc_common::logging;
+use aoc_common::messages;
+use aoc_common::msg;
+use aoc_common::output::{take_format, Format, Report};
+
+use day_09::calculations::{compact_blocks, compact_files};
+use day_09::file_io::read_disk_map;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let format = take_format(&mut args)?;
+    logging::init(logging::take_verbosity(&mut args));
+    messages::init(messages::take_lang(&mut args)?.as_deref())?;
+    if format == Format::Text {
+        println!("{}", msg!("welcome", 9));
+    }
+
+    let path = resolve_input(args.first().map(String::as_str), 9)?;
+    let disk_map = read_disk_map(&path)?;
+
+    let start = Instant::now();
+    let part1 = compact_blocks(&disk_map);
+    let part2 = compact_files(&disk_map);
+    let elapsed = start.elapsed();
+
+    match format {
+        Format::Text => {
+            println!("{}", msg!("day09-part1", part1));
+            println!("{}", msg!("day09-part2", part2));
+        }
+        Format::Json => {
+            let report = Report {
+                day: 9,
+                part1: Some(part1.to_string()),
+                part2: Some(part2.to_string()),
+                elapsed,
+            };
+            println!("{}", report.to_json());
+        }
+    }
+
+    Ok(())
 }
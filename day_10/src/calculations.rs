@@ -0,0 +1,131 @@
+use ndarray::Array2;
+use std::collections::HashSet;
+
+/// Height of a trailhead
+const TRAILHEAD: u8 = 0;
+/// Height of a trail's end
+const SUMMIT: u8 = 9;
+
+/// Returns the orthogonal neighbours of `pos` that are exactly one step higher
+fn uphill_neighbors(map: &Array2<u8>, pos: (usize, usize)) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let (rows, cols) = map.dim();
+    let height = map[pos];
+    [(-1, 0), (1, 0), (0, -1), (0, 1)]
+        .into_iter()
+        .filter_map(move |(dr, dc): (isize, isize)| {
+            let row = pos.0.checked_add_signed(dr)?;
+            let col = pos.1.checked_add_signed(dc)?;
+            (row < rows && col < cols && map[(row, col)] == height + 1).then_some((row, col))
+        })
+}
+
+/// Finds every trailhead (height 0) in the map
+fn trailheads(map: &Array2<u8>) -> impl Iterator<Item = (usize, usize)> + '_ {
+    map.indexed_iter()
+        .filter(|&(_, &height)| height == TRAILHEAD)
+        .map(|(pos, _)| pos)
+}
+
+/// Counts the distinct height-9 positions reachable from `start` by hiking
+/// trails that climb exactly one height per step, using a depth-first search
+/// that visits each position at most once
+fn trailhead_score(map: &Array2<u8>, start: (usize, usize)) -> usize {
+    let mut seen = HashSet::from([start]);
+    let mut stack = vec![start];
+    let mut summits = 0;
+
+    while let Some(pos) = stack.pop() {
+        if map[pos] == SUMMIT {
+            summits += 1;
+            continue;
+        }
+        for next in uphill_neighbors(map, pos) {
+            if seen.insert(next) {
+                stack.push(next);
+            }
+        }
+    }
+
+    summits
+}
+
+/// Sums the score of every trailhead: the number of distinct height-9
+/// positions reachable from it
+///
+/// # Arguments
+///
+/// * `map` - The topographic map
+///
+/// # Returns
+///
+/// * `usize` - The sum of all trailhead scores
+pub fn total_score(map: &Array2<u8>) -> usize {
+    trailheads(map).map(|start| trailhead_score(map, start)).sum()
+}
+
+/// Sums the rating of every trailhead: the number of distinct hiking trails
+/// starting there.
+///
+/// Paths are counted level by level: every cell's path count is the sum of
+/// the counts of its neighbours one height lower, so each cell is processed once.
+///
+/// # Arguments
+///
+/// * `map` - The topographic map
+///
+/// # Returns
+///
+/// * `usize` - The sum of all trailhead ratings
+pub fn total_rating(map: &Array2<u8>) -> usize {
+    let mut paths = Array2::<usize>::zeros(map.dim());
+    for start in trailheads(map) {
+        paths[start] = 1;
+    }
+
+    let mut rating = 0;
+    for height in TRAILHEAD..=SUMMIT {
+        for (pos, _) in map.indexed_iter().filter(|&(_, &h)| h == height) {
+            let count = paths[pos];
+            if count == 0 {
+                continue;
+            }
+            if height == SUMMIT {
+                rating += count;
+            }
+            for next in uphill_neighbors(map, pos) {
+                paths[next] += count;
+            }
+        }
+    }
+
+    rating
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::read_file;
+    use std::error::Error;
+
+    #[test]
+    fn test_total_score() -> Result<(), Box<dyn Error>> {
+        let map = read_file("data/inputtest")?;
+        assert_eq!(total_score(&map), 36);
+        Ok(())
+    }
+
+    #[test]
+    fn test_total_rating() -> Result<(), Box<dyn Error>> {
+        let map = read_file("data/inputtest")?;
+        assert_eq!(total_rating(&map), 81);
+        Ok(())
+    }
+
+    #[test]
+    fn test_single_trail() -> Result<(), Box<dyn Error>> {
+        let map = Array2::from_shape_vec((2, 5), vec![0, 1, 2, 3, 4, 9, 8, 7, 6, 5])?;
+        assert_eq!(total_score(&map), 1);
+        assert_eq!(total_rating(&map), 1);
+        Ok(())
+    }
+}
@@ -0,0 +1,30 @@
+//! Errors from reading day 10's topographic map, which only this day can produce.
+
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{msg, AppError};
+
+/// What can be wrong with the map, carried by `AppError::Puzzle`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PuzzleError {
+    /// A map cell that is not a height digit
+    InvalidHeight(char),
+}
+
+impl Error for PuzzleError {}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::InvalidHeight(c) => msg!("error-invalid-height", format!("{:?}", c)),
+        };
+        f.write_str(&message)
+    }
+}
+
+impl From<PuzzleError> for AppError {
+    fn from(error: PuzzleError) -> Self {
+        Self::Puzzle(Box::new(error))
+    }
+}
@@ -0,0 +1,62 @@
+use ndarray::Array2;
+
+use crate::AppError;
+use crate::errors::PuzzleError;
+
+/// Converts a topographic map into a 2D array of heights.
+/// Each line becomes a row in the array, and each character a digit 0-9.
+///
+/// # Arguments
+///
+/// * `content` - The puzzle input
+///
+/// # Returns
+///
+/// * `Result<Array2<u8>, AppError>` - A 2D array of heights, or an error
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - A character is not a digit
+/// - The lines have different lengths
+pub fn parse_map(content: &str) -> Result<Array2<u8>, AppError> {
+    let lines: Vec<&str> = content.lines().filter(|line| !line.is_empty()).collect();
+    let rows = lines.len();
+    let cols = lines.first().map_or(0, |line| line.chars().count());
+
+    let data: Vec<u8> = lines
+        .iter()
+        .flat_map(|line| line.chars())
+        .map(|c| c.to_digit(10).map(|d| d as u8).ok_or(AppError::from(PuzzleError::InvalidHeight(c))))
+        .collect::<Result<_, _>>()?;
+    Array2::from_shape_vec((rows, cols), data).map_err(|_| AppError::Array2CreationError)
+}
+
+/// Reads a topographic map from a file
+///
+/// # Arguments
+///
+/// * `filename` - A string slice that holds the path to the file
+///
+/// # Returns
+///
+/// * `Result<Array2<u8>, AppError>` - The parsed input, or an error if
+///   the file can't be read or parsed
+pub fn read_file(filename: &str) -> Result<Array2<u8>, AppError> {
+    let content = std::fs::read_to_string(filename)?;
+    parse_map(&content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_file() -> Result<(), AppError> {
+        let map = read_file("data/inputtest")?;
+        assert_eq!(map.dim(), (8, 8));
+        assert_eq!(map[[0, 0]], 8);
+        assert_eq!(map[[7, 7]], 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+//! Day 10: Hoof It
+//!
+//! Library entry points shared by the binary and the `aoc` runner.
+
+pub mod calculations;
+pub mod errors;
+pub mod file_io;
+
+use aoc_common::{AppError, Solver};
+use ndarray::Array2;
+
+use calculations::{total_rating, total_score};
+use file_io::parse_map;
+
+/// Solver for the lava island's topographic map
+pub struct Day10;
+
+impl Solver for Day10 {
+    /// The height of every position
+    type Parsed = Array2<u8>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AppError> {
+        parse_map(input)
+    }
+
+    /// Sums the number of peaks reachable from each trailhead
+    fn part1(map: &Self::Parsed) -> Result<String, AppError> {
+        Ok(total_score(map).to_string())
+    }
+
+    /// Sums the number of distinct trails from each trailhead
+    fn part2(map: &Self::Parsed) -> Result<String, AppError> {
+        Ok(total_rating(map).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<(), AppError> {
+        let parsed = Day10::parse(&std::fs::read_to_string("data/inputtest")?)?;
+        assert_eq!(Day10::part1(&parsed)?, "36");
+        assert_eq!(Day10::part2(&parsed)?, "81");
+        Ok(())
+    }
+}
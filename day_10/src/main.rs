@@ -1,3 +1,60 @@
-fn main() {
-    println!("Welcome to Day 10!");
+//! Day 10: Hoof It
+//!
+//! This program analyses hiking trails on a topographic map, where a trail
+//! starts at height 0, ends at height 9 and climbs exactly one per step:
+//! 1. Sums the trailhead scores (number of height-9 positions reachable)
+//! 2. Sums the trailhead ratings (number of distinct trails)
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run -- path/to/input/file
+//! cargo run -- path/to/input/file --format json
+//! ```
+use std::error::Error;
+use std::time::Instant;
+
+use aoc_common::input::resolve_input;
+use aoc_common::logging;
+use aoc_common::messages;
+use aoc_common::msg;
+use aoc_common::output::{take_format, Format, Report};
+
+use day_10::calculations::{total_rating, total_score};
+use day_10::file_io::read_file;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let format = take_format(&mut args)?;
+    logging::init(logging::take_verbosity(&mut args));
+    messages::init(messages::take_lang(&mut args)?.as_deref())?;
+    if format == Format::Text {
+        println!("{}", msg!("welcome", 10));
+    }
+
+    let path = resolve_input(args.first().map(String::as_str), 10)?;
+    let map = read_file(&path)?;
+
+    let start = Instant::now();
+    let part1 = total_score(&map);
+    let part2 = total_rating(&map);
+    let elapsed = start.elapsed();
+
+    match format {
+        Format::Text => {
+            println!("{}", msg!("day10-part1", part1));
+            println!("{}", msg!("day10-part2", part2));
+        }
+        Format::Json => {
+            let report = Report {
+                day: 10,
+                part1: Some(part1.to_string()),
+                part2: Some(part2.to_string()),
+                elapsed,
+            };
+            println!("{}", report.to_json());
+        }
+    }
+
+    Ok(())
 }
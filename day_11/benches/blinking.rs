@@ -0,0 +1,25 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+use day_11::calculations::{count_stones, Memo};
+
+/// A typical-looking starting arrangement
+const STONES: [u64; 8] = [4022724, 951333, 0, 21633, 5857, 97, 702, 6];
+
+fn bench_blinking(c: &mut Criterion) {
+    for blinks in [25, 75] {
+        c.bench_function(&format!("count_stones_{blinks}"), |b| {
+            b.iter(|| count_stones(black_box(&STONES), blinks, &mut Memo::new()))
+        });
+
+        let mut memo = Memo::new();
+        count_stones(&STONES, blinks, &mut memo);
+        println!(
+            "{blinks} blinks: {} cache entries, {:.1}% hit rate",
+            memo.len(),
+            memo.hit_rate() * 100.0
+        );
+    }
+}
+
+criterion_group!(benches, bench_blinking);
+criterion_main!(benches);
@@ -0,0 +1,142 @@
+//! Module for counting stones after repeated blinks.
+//!
+//! Stones never interact, so only the number of stones each one turns into
+//! matters, not their order. That count is memoized on (stone, blinks left).
+
+use std::collections::HashMap;
+
+/// A memoization table that records how often lookups were answered from the cache
+#[derive(Debug, Default)]
+pub struct Memo {
+    cache: HashMap<(u64, usize), u64>,
+    hits: u64,
+    misses: u64,
+}
+
+impl Memo {
+    /// Creates an empty memoization table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of lookups answered from the cache
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Number of lookups that had to be computed
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Fraction of lookups answered from the cache, between 0 and 1
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+
+    /// Number of distinct (stone, blinks) entries stored
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    /// Returns `true` if nothing has been stored yet
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+}
+
+/// Splits a number with an even count of decimal digits into its left and right halves
+///
+/// # Returns
+/// `Some((left, right))` for an even digit count, `None` otherwise
+fn split_digits(stone: u64) -> Option<(u64, u64)> {
+    let digits = stone.checked_ilog10().unwrap_or(0) + 1;
+    if !digits.is_multiple_of(2) {
+        return None;
+    }
+    let divisor = 10u64.pow(digits / 2);
+    Some((stone / divisor, stone % divisor))
+}
+
+/// Counts how many stones a single stone turns into after `blinks` blinks
+///
+/// On each blink, a 0 becomes 1, a number with an even digit count splits in
+/// two, and any other number is multiplied by 2024.
+fn count_stone(stone: u64, blinks: usize, memo: &mut Memo) -> u64 {
+    if blinks == 0 {
+        return 1;
+    }
+    if let Some(&count) = memo.cache.get(&(stone, blinks)) {
+        memo.hits += 1;
+        return count;
+    }
+    memo.misses += 1;
+
+    let count = if stone == 0 {
+        count_stone(1, blinks - 1, memo)
+    } else if let Some((left, right)) = split_digits(stone) {
+        count_stone(left, blinks - 1, memo) + count_stone(right, blinks - 1, memo)
+    } else {
+        count_stone(stone * 2024, blinks - 1, memo)
+    };
+
+    memo.cache.insert((stone, blinks), count);
+    count
+}
+
+/// Counts the stones after blinking `blinks` times
+///
+/// # Arguments
+/// * `stones` - The initial stones
+/// * `blinks` - How many times to blink
+/// * `memo` - The memoization table, which can be shared between calls
+///
+/// # Returns
+/// The number of stones
+pub fn count_stones(stones: &[u64], blinks: usize, memo: &mut Memo) -> u64 {
+    stones.iter().map(|&stone| count_stone(stone, blinks, memo)).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::read_stones;
+    use std::error::Error;
+
+    #[test]
+    fn test_count_stones_example() -> Result<(), Box<dyn Error>> {
+        let stones = read_stones("data/inputtest")?;
+        let mut memo = Memo::new();
+        assert_eq!(count_stones(&stones, 6, &mut memo), 22);
+        assert_eq!(count_stones(&stones, 25, &mut memo), 55312);
+        Ok(())
+    }
+
+    #[test]
+    fn test_count_stones_first_blink() {
+        let mut memo = Memo::new();
+        assert_eq!(count_stones(&[0, 1, 10, 99, 999], 1, &mut memo), 7);
+    }
+
+    #[test]
+    fn test_split_digits() {
+        assert_eq!(split_digits(1000), Some((10, 0)));
+        assert_eq!(split_digits(17), Some((1, 7)));
+        assert_eq!(split_digits(125), None);
+        assert_eq!(split_digits(0), None);
+    }
+
+    #[test]
+    fn test_memo_hit_rate() {
+        let mut memo = Memo::new();
+        count_stones(&[125, 17], 75, &mut memo);
+        assert!(memo.hits() > 0);
+        assert_eq!(memo.misses() as usize, memo.len());
+        assert!(memo.hit_rate() > 0.0 && memo.hit_rate() < 1.0);
+    }
+}
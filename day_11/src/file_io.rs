@@ -0,0 +1,44 @@
+use aoc_common::AppError;
+use tracing::debug;
+
+/// Parses the whitespace-separated numbers engraved on the stones
+///
+/// # Arguments
+///
+/// * `content` - The puzzle input
+///
+/// # Returns
+///
+/// * `Result<Vec<u64>, AppError>` - The stones in order, or an error
+pub fn parse_stones(content: &str) -> Result<Vec<u64>, AppError> {
+    Ok(content
+        .split_whitespace()
+        .map(str::parse)
+        .collect::<Result<_, _>>()?)
+}
+
+/// Reads a file containing the initial arrangement of stones
+///
+/// # Arguments
+///
+/// * `path` - A string slice that holds the path to the file
+///
+/// # Returns
+///
+/// * `Result<Vec<u64>, AppError>` - The stones in order, or an error
+pub fn read_stones(path: &str) -> Result<Vec<u64>, AppError> {
+    let content = std::fs::read_to_string(path)?;
+    debug!("Read {} bytes", content.len());
+    parse_stones(&content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_stones() -> Result<(), AppError> {
+        assert_eq!(read_stones("data/inputtest")?, vec![125, 17]);
+        Ok(())
+    }
+}
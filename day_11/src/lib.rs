@@ -0,0 +1,46 @@
+//! Day 11: Plutonian Pebbles
+//!
+//! Library entry points shared by the binary, the benchmarks and the `aoc` runner.
+
+pub mod calculations;
+pub mod file_io;
+
+use aoc_common::{AppError, Solver};
+
+use calculations::{count_stones, Memo};
+use file_io::parse_stones;
+
+/// Solver for the plutonian pebbles
+pub struct Day11;
+
+impl Solver for Day11 {
+    /// The stones in order
+    type Parsed = Vec<u64>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AppError> {
+        parse_stones(input)
+    }
+
+    /// Counts the stones after 25 blinks
+    fn part1(stones: &Self::Parsed) -> Result<String, AppError> {
+        Ok(count_stones(stones, 25, &mut Memo::new()).to_string())
+    }
+
+    /// Counts the stones after 75 blinks
+    fn part2(stones: &Self::Parsed) -> Result<String, AppError> {
+        Ok(count_stones(stones, 75, &mut Memo::new()).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<(), AppError> {
+        let parsed = Day11::parse(&std::fs::read_to_string("data/inputtest")?)?;
+        assert_eq!(Day11::part1(&parsed)?, "55312");
+        assert_eq!(Day11::part2(&parsed)?, "65601038650482");
+        Ok(())
+    }
+}
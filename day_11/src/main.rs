@@ -1,3 +1,64 @@
-fn main() {
-    println!("Welcome to Day 11!");
+//! Day 11: Plutonian Pebbles
+//!
+//! This program counts the stones after blinking at them:
+//! 1. After 25 blinks
+//! 2. After 75 blinks
+//!
+//! Both parts share one memoization table, whose hit rate is reported at the end.
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run -- path/to/input/file
+//! cargo run -- path/to/input/file --format json
+//! cargo bench
+//! ```
+use std::error::Error;
+use std::time::Instant;
+
+use aoc_common::input::resolve_input;
+use aoc_common::logging;
+use aoc_common::messages;
+use aoc_common::msg;
+use aoc_common::output::{take_format, Format, Report};
+
+use day_11::calculations::{count_stones, Memo};
+use day_11::file_io::read_stones;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let format = take_format(&mut args)?;
+    logging::init(logging::take_verbosity(&mut args));
+    messages::init(messages::take_lang(&mut args)?.as_deref())?;
+    if format == Format::Text {
+        println!("{}", msg!("welcome", 11));
+    }
+
+    let path = resolve_input(args.first().map(String::as_str), 11)?;
+    let stones = read_stones(&path)?;
+
+    let start = Instant::now();
+    let mut memo = Memo::new();
+    let part1 = count_stones(&stones, 25, &mut memo);
+    let part2 = count_stones(&stones, 75, &mut memo);
+    let elapsed = start.elapsed();
+
+    match format {
+        Format::Text => {
+            println!("{}", msg!("day11-part1", part1));
+            println!("{}", msg!("day11-part2", part2));
+            println!("{}", msg!("day11-cache", memo.len(), memo.hits(), memo.misses(), format!("{:.1}", memo.hit_rate() * 100.0)));
+        }
+        Format::Json => {
+            let report = Report {
+                day: 11,
+                part1: Some(part1.to_string()),
+                part2: Some(part2.to_string()),
+                elapsed,
+            };
+            println!("{}", report.to_json());
+        }
+    }
+
+    Ok(())
 }
@@ -0,0 +1,168 @@
+use ndarray::Array2;
+
+/// Orthogonal neighbour offsets in clockwise order: up, right, down, left
+const DIRECTIONS: [(isize, isize); 4] = [(-1, 0), (0, 1), (1, 0), (0, -1)];
+
+/// A connected region of garden plots growing the same plant
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Region {
+    /// The plant type growing in the region
+    pub plant: char,
+    /// Number of plots in the region
+    pub area: usize,
+    /// Number of plot edges that border another region or the map edge
+    pub perimeter: usize,
+    /// Number of straight fence sides, equal to the number of corners
+    pub sides: usize,
+}
+
+/// Returns the neighbour of `pos` in direction `(dr, dc)`, if it is inside the grid
+fn neighbor(grid: &Array2<char>, pos: (usize, usize), (dr, dc): (isize, isize)) -> Option<(usize, usize)> {
+    let row = pos.0.checked_add_signed(dr)?;
+    let col = pos.1.checked_add_signed(dc)?;
+    (row < grid.nrows() && col < grid.ncols()).then_some((row, col))
+}
+
+/// Labels every plot with the index of the connected region it belongs to,
+/// using an iterative flood fill from each unlabelled plot.
+///
+/// # Returns
+///
+/// * `(Array2<usize>, usize)` - The region label of each plot and the number of regions
+fn label_regions(grid: &Array2<char>) -> (Array2<usize>, usize) {
+    let mut labels = Array2::from_elem(grid.dim(), usize::MAX);
+    let mut count = 0;
+
+    for (start, &plant) in grid.indexed_iter() {
+        if labels[start] != usize::MAX {
+            continue;
+        }
+        labels[start] = count;
+        let mut stack = vec![start];
+        while let Some(pos) = stack.pop() {
+            for direction in DIRECTIONS {
+                if let Some(next) = neighbor(grid, pos, direction) {
+                    if labels[next] == usize::MAX && grid[next] == plant {
+                        labels[next] = count;
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+        count += 1;
+    }
+
+    (labels, count)
+}
+
+/// Partitions the garden into regions and measures the area, perimeter and
+/// number of sides of each.
+///
+/// Sides are counted via corners, since a closed polygon has as many sides as
+/// corners. For every pair of adjacent directions around a plot, it is an
+/// outside corner if both neighbours lie outside the region, and an inside
+/// corner if both lie inside but the diagonal between them does not.
+///
+/// # Arguments
+///
+/// * `grid` - The garden map
+///
+/// # Returns
+///
+/// * `Vec<Region>` - The regions, in order of their first plot in the grid
+pub fn find_regions(grid: &Array2<char>) -> Vec<Region> {
+    let (labels, count) = label_regions(grid);
+    let mut regions = vec![
+        Region {
+            plant: ' ',
+            area: 0,
+            perimeter: 0,
+            sides: 0,
+        };
+        count
+    ];
+
+    let same_region = |pos: (usize, usize), direction: (isize, isize), label: usize| {
+        neighbor(grid, pos, direction).is_some_and(|next| labels[next] == label)
+    };
+
+    for (pos, &label) in labels.indexed_iter() {
+        let region = &mut regions[label];
+        region.plant = grid[pos];
+        region.area += 1;
+
+        for (i, &direction) in DIRECTIONS.iter().enumerate() {
+            if !same_region(pos, direction, label) {
+                region.perimeter += 1;
+            }
+
+            let next_direction = DIRECTIONS[(i + 1) % DIRECTIONS.len()];
+            let diagonal = (direction.0 + next_direction.0, direction.1 + next_direction.1);
+            let first = same_region(pos, direction, label);
+            let second = same_region(pos, next_direction, label);
+            if (!first && !second) || (first && second && !same_region(pos, diagonal, label)) {
+                region.sides += 1;
+            }
+        }
+    }
+
+    regions
+}
+
+/// Total fence price when each region costs area × perimeter
+pub fn fence_price(regions: &[Region]) -> usize {
+    regions.iter().map(|region| region.area * region.perimeter).sum()
+}
+
+/// Total fence price with the bulk discount, where each region costs area × number of sides
+pub fn discounted_fence_price(regions: &[Region]) -> usize {
+    regions.iter().map(|region| region.area * region.sides).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::read_file;
+    use std::error::Error;
+
+    #[test]
+    fn test_fence_price() -> Result<(), Box<dyn Error>> {
+        let grid = read_file("data/inputtest")?;
+        assert_eq!(fence_price(&find_regions(&grid)), 1930);
+        Ok(())
+    }
+
+    #[test]
+    fn test_discounted_fence_price() -> Result<(), Box<dyn Error>> {
+        let grid = read_file("data/inputtest")?;
+        assert_eq!(discounted_fence_price(&find_regions(&grid)), 1206);
+        Ok(())
+    }
+
+    #[test]
+    fn test_small_example() -> Result<(), Box<dyn Error>> {
+        let grid = read_file("data/inputtest_small")?;
+        let regions = find_regions(&grid);
+        assert_eq!(regions.len(), 5);
+        assert_eq!(
+            regions[0],
+            Region {
+                plant: 'A',
+                area: 4,
+                perimeter: 10,
+                sides: 4
+            }
+        );
+        assert_eq!(fence_price(&regions), 140);
+        assert_eq!(discounted_fence_price(&regions), 80);
+        Ok(())
+    }
+
+    /// The E-shaped example, where the inside corners matter
+    #[test]
+    fn test_discounted_fence_price_e_shape() -> Result<(), Box<dyn Error>> {
+        let grid = Array2::from_shape_vec((5, 5), "EEEEEEXXXXEEEEEEXXXXEEEEE".chars().collect())?;
+        assert_eq!(discounted_fence_price(&find_regions(&grid)), 236);
+        Ok(())
+    }
+}
@@ -0,0 +1,47 @@
+//! Day 12: Garden Groups
+//!
+//! Library entry points shared by the binary and the `aoc` runner.
+
+pub mod calculations;
+pub mod file_io;
+
+use aoc_common::{AppError, Solver};
+use ndarray::Array2;
+
+use calculations::{discounted_fence_price, fence_price, find_regions};
+use file_io::parse_grid;
+
+/// Solver for the garden plots
+pub struct Day12;
+
+impl Solver for Day12 {
+    /// The plant type of every plot
+    type Parsed = Array2<char>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AppError> {
+        parse_grid(input)
+    }
+
+    /// Prices the fences by area times perimeter
+    fn part1(grid: &Self::Parsed) -> Result<String, AppError> {
+        Ok(fence_price(&find_regions(grid)).to_string())
+    }
+
+    /// Prices the fences by area times number of sides
+    fn part2(grid: &Self::Parsed) -> Result<String, AppError> {
+        Ok(discounted_fence_price(&find_regions(grid)).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<(), AppError> {
+        let parsed = Day12::parse(&std::fs::read_to_string("data/inputtest")?)?;
+        assert_eq!(Day12::part1(&parsed)?, "1930");
+        assert_eq!(Day12::part2(&parsed)?, "1206");
+        Ok(())
+    }
+}
@@ -1,3 +1,61 @@
-fn main() {
-    println!("Welcome to Day 12!");
+//! Day 12: Garden Groups
+//!
+//! This program splits a garden map into connected regions of the same plant
+//! and prices the fences around them:
+//! 1. Each region costs its area times its perimeter
+//! 2. Each region costs its area times its number of straight sides
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run -- path/to/input/file
+//! cargo run -- path/to/input/file --format json
+//! ```
+use std::error::Error;
+use std::time::Instant;
+
+use aoc_common::input::resolve_input;
+use aoc_common::logging;
+use aoc_common::messages;
+use aoc_common::msg;
+use aoc_common::output::{take_format, Format, Report};
+
+use day_12::calculations::{discounted_fence_price, fence_price, find_regions};
+use day_12::file_io::read_file;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let format = take_format(&mut args)?;
+    logging::init(logging::take_verbosity(&mut args));
+    messages::init(messages::take_lang(&mut args)?.as_deref())?;
+    if format == Format::Text {
+        println!("{}", msg!("welcome", 12));
+    }
+
+    let path = resolve_input(args.first().map(String::as_str), 12)?;
+    let grid = read_file(&path)?;
+
+    let start = Instant::now();
+    let regions = find_regions(&grid);
+    let part1 = fence_price(&regions);
+    let part2 = discounted_fence_price(&regions);
+    let elapsed = start.elapsed();
+
+    match format {
+        Format::Text => {
+            println!("{}", msg!("day12-part1", part1));
+            println!("{}", msg!("day12-part2", part2));
+        }
+        Format::Json => {
+            let report = Report {
+                day: 12,
+                part1: Some(part1.to_string()),
+                part2: Some(part2.to_string()),
+                elapsed,
+            };
+            println!("{}", report.to_json());
+        }
+    }
+
+    Ok(())
 }
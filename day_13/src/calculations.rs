@@ -0,0 +1,207 @@
+//! Module for finding the cheapest way to win each claw machine's prize.
+
+/// Offset added to both prize coordinates in part 2
+pub const PRIZE_OFFSET: i64 = 10_000_000_000_000;
+/// Tokens needed to press button A
+const COST_A: i64 = 3;
+/// Tokens needed to press button B
+const COST_B: i64 = 1;
+
+/// A claw machine: how far each button moves the claw, and where the prize is
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Machine {
+    pub button_a: (i64, i64),
+    pub button_b: (i64, i64),
+    pub prize: (i64, i64),
+}
+
+/// Solves the 2×2 integer linear system `a * (ax, ay) + b * (bx, by) = (px, py)`
+/// exactly using Cramer's rule.
+///
+/// # Returns
+/// `Some((a, b))` with the cheapest presses in non-negative integers, or `None`
+/// if the prize can't be reached. Collinear buttons are handled by `solve_collinear`.
+fn solve_presses(machine: &Machine) -> Option<(i64, i64)> {
+    let (ax, ay) = machine.button_a;
+    let (bx, by) = machine.button_b;
+    let (px, py) = machine.prize;
+
+    let determinant = ax * by - ay * bx;
+    if determinant == 0 {
+        return solve_collinear(machine);
+    }
+    let a_numerator = px * by - py * bx;
+    let b_numerator = ax * py - ay * px;
+    if a_numerator % determinant != 0 || b_numerator % determinant != 0 {
+        return None;
+    }
+
+    let (a, b) = (a_numerator / determinant, b_numerator / determinant);
+    (a >= 0 && b >= 0).then_some((a, b))
+}
+
+/// Solves a machine whose buttons move the claw along the same line.
+///
+/// The prize must lie on that line, which leaves the one-dimensional equation
+/// `a * u + b * v = p` along an axis the line isn't perpendicular to. Its integer
+/// solutions are `(a0 + k * v / g, b0 - k * u / g)` for `g = gcd(u, v)`, and the
+/// cost is linear in `k`, so the cheapest is at one end of the range of `k` that
+/// keeps both counts non-negative.
+///
+/// # Returns
+/// `Some((a, b))` with the fewest tokens, or `None` if the prize can't be reached
+fn solve_collinear(machine: &Machine) -> Option<(i64, i64)> {
+    let (ax, ay) = machine.button_a;
+    let (bx, by) = machine.button_b;
+    let (px, py) = machine.prize;
+
+    // The line is spanned by whichever button moves the claw at all
+    let (dx, dy) = if (ax, ay) != (0, 0) { (ax, ay) } else { (bx, by) };
+    if (dx, dy) == (0, 0) {
+        return ((px, py) == (0, 0)).then_some((0, 0));
+    }
+    if dx * py - dy * px != 0 {
+        return None;
+    }
+    let (u, v, p) = if dx != 0 { (ax, bx, px) } else { (ay, by, py) };
+
+    // One button doesn't move along the axis: press only the other one
+    if u == 0 || v == 0 {
+        let moving = u + v;
+        if p % moving != 0 || p / moving < 0 {
+            return None;
+        }
+        return Some(if u == 0 { (0, p / moving) } else { (p / moving, 0) });
+    }
+
+    let (g, x, y) = extended_gcd(u, v);
+    if p % g != 0 {
+        return None;
+    }
+    let (a0, b0) = (x * (p / g), y * (p / g));
+    let (step_a, step_b) = (v / g, u / g);
+
+    // Both counts stay non-negative while coefficient * k >= minimum
+    let mut lower: Option<i64> = None;
+    let mut upper: Option<i64> = None;
+    for (coefficient, minimum) in [(step_a, -a0), (-step_b, -b0)] {
+        if coefficient > 0 {
+            let k = -floor_div(-minimum, coefficient);
+            lower = Some(lower.map_or(k, |lower| lower.max(k)));
+        } else {
+            let k = floor_div(-minimum, -coefficient);
+            upper = Some(upper.map_or(k, |upper| upper.min(k)));
+        }
+    }
+    if let (Some(lower), Some(upper)) = (lower, upper) {
+        if lower > upper {
+            return None;
+        }
+    }
+
+    // The cost can't fall below zero, so it rises towards an open end
+    let slope = COST_A * step_a - COST_B * step_b;
+    let k = if slope >= 0 { lower.or(upper) } else { upper.or(lower) }?;
+    Some((a0 + k * step_a, b0 - k * step_b))
+}
+
+/// Returns `(g, x, y)` with `g = gcd(a, b) > 0` and `a * x + b * y = g`
+fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    if b == 0 {
+        (a.abs(), a.signum(), 0)
+    } else {
+        let (g, x, y) = extended_gcd(b, a % b);
+        (g, y, x - (a / b) * y)
+    }
+}
+
+/// Divides rounding towards negative infinity
+fn floor_div(numerator: i64, denominator: i64) -> i64 {
+    let quotient = numerator / denominator;
+    if numerator % denominator != 0 && (numerator < 0) != (denominator < 0) {
+        quotient - 1
+    } else {
+        quotient
+    }
+}
+
+/// Sums the tokens needed to win every prize that can be won
+///
+/// # Arguments
+/// * `machines` - The claw machines
+/// * `offset` - Added to both prize coordinates: 0 for part 1, `PRIZE_OFFSET` for part 2
+///
+/// # Returns
+/// The fewest tokens needed to win all winnable prizes
+pub fn total_tokens(machines: &[Machine], offset: i64) -> i64 {
+    machines
+        .iter()
+        .map(|machine| Machine {
+            prize: (machine.prize.0 + offset, machine.prize.1 + offset),
+            ..*machine
+        })
+        .filter_map(|machine| solve_presses(&machine))
+        .map(|(a, b)| a * COST_A + b * COST_B)
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::read_machines;
+    use std::error::Error;
+
+    #[test]
+    fn test_total_tokens() -> Result<(), Box<dyn Error>> {
+        let machines = read_machines("data/inputtest")?;
+        assert_eq!(total_tokens(&machines, 0), 480);
+        Ok(())
+    }
+
+    #[test]
+    fn test_total_tokens_with_offset() -> Result<(), Box<dyn Error>> {
+        let machines = read_machines("data/inputtest")?;
+        assert_eq!(total_tokens(&machines, PRIZE_OFFSET), 875318608908);
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_presses() -> Result<(), Box<dyn Error>> {
+        let machines = read_machines("data/inputtest")?;
+        assert_eq!(solve_presses(&machines[0]), Some((80, 40)));
+        assert_eq!(solve_presses(&machines[1]), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_presses_collinear() {
+        let machine = Machine {
+            button_a: (2, 2),
+            button_b: (1, 1),
+            prize: (4, 4),
+        };
+        assert_eq!(solve_presses(&machine), Some((0, 4)));
+
+        // A is cheaper per step when it moves more than three times as far as B
+        let machine = Machine { button_a: (6, 6), button_b: (1, 1), prize: (12, 12) };
+        assert_eq!(solve_presses(&machine), Some((2, 0)));
+
+        // Only one combination lands on the prize
+        let machine = Machine { button_a: (3, 3), button_b: (2, 2), prize: (7, 7) };
+        assert_eq!(solve_presses(&machine), Some((1, 2)));
+
+        // Off the line, or between the reachable positions
+        let machine = Machine { button_a: (2, 2), button_b: (1, 1), prize: (4, 5) };
+        assert_eq!(solve_presses(&machine), None);
+        let machine = Machine { button_a: (4, 2), button_b: (2, 1), prize: (5, 3) };
+        assert_eq!(solve_presses(&machine), None);
+        let machine = Machine { button_a: (4, 6), button_b: (2, 3), prize: (1, 1) };
+        assert_eq!(solve_presses(&machine), None);
+
+        // Along the y axis only, and with a button that doesn't move
+        let machine = Machine { button_a: (0, 5), button_b: (0, 1), prize: (0, 10) };
+        assert_eq!(solve_presses(&machine), Some((2, 0)));
+        let machine = Machine { button_a: (0, 0), button_b: (3, 3), prize: (9, 9) };
+        assert_eq!(solve_presses(&machine), Some((0, 3)));
+    }
+}
@@ -0,0 +1,31 @@
+//! Errors from reading day 13's claw machine descriptions, which only this day
+//! can produce.
+
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{msg, AppError};
+
+/// What can be wrong with a machine description, carried by `AppError::Puzzle`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PuzzleError {
+    /// A machine description that doesn't match the expected format
+    InvalidMachine(String),
+}
+
+impl Error for PuzzleError {}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::InvalidMachine(block) => msg!("error-invalid-machine", block),
+        };
+        f.write_str(&message)
+    }
+}
+
+impl From<PuzzleError> for AppError {
+    fn from(error: PuzzleError) -> Self {
+        Self::Puzzle(Box::new(error))
+    }
+}
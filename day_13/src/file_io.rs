@@ -0,0 +1,91 @@
+use crate::calculations::Machine;
+use crate::errors::PuzzleError;
+use aoc_common::AppError;
+use tracing::debug;
+
+/// Parses a line such as `Button A: X+94, Y+34` or `Prize: X=8400, Y=5400`
+/// into its X and Y values, checking the expected label and separator
+fn parse_line(line: &str, label: &str, separator: char) -> Option<Result<(i64, i64), AppError>> {
+    let rest = line.trim().strip_prefix(label)?.trim();
+    let (x, y) = rest.split_once(',')?;
+    let x = x.trim().strip_prefix('X')?.strip_prefix(separator)?;
+    let y = y.trim().strip_prefix('Y')?.strip_prefix(separator)?;
+    Some(x.parse().and_then(|x| Ok((x, y.parse()?))).map_err(AppError::from))
+}
+
+/// Parses claw machine descriptions separated by blank lines.
+///
+/// # Arguments
+///
+/// * `content` - The puzzle input
+///
+/// # Returns
+///
+/// * `Result<Vec<Machine>, AppError>` - The machines in input order, or an error
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - A block doesn't consist of the `Button A`, `Button B` and `Prize` lines
+/// - A number cannot be parsed
+pub fn parse_machines(content: &str) -> Result<Vec<Machine>, AppError> {
+    content
+        .replace("\r\n", "\n")
+        .split("\n\n")
+        .filter(|block| !block.trim().is_empty())
+        .map(|block| {
+            let invalid = || PuzzleError::InvalidMachine(block.trim().to_string());
+            let lines: Vec<&str> = block.trim().lines().collect();
+            let [a, b, prize] = lines.as_slice() else {
+                return Err(invalid().into());
+            };
+            Ok(Machine {
+                button_a: parse_line(a, "Button A:", '+').ok_or_else(invalid)??,
+                button_b: parse_line(b, "Button B:", '+').ok_or_else(invalid)??,
+                prize: parse_line(prize, "Prize:", '=').ok_or_else(invalid)??,
+            })
+        })
+        .collect()
+}
+
+/// Reads the claw machine descriptions from a file
+///
+/// # Arguments
+///
+/// * `path` - A string slice that holds the path to the file
+///
+/// # Returns
+///
+/// * `Result<Vec<Machine>, AppError>` - The parsed input, or an error if
+///   the file can't be read or parsed
+pub fn read_machines(path: &str) -> Result<Vec<Machine>, AppError> {
+    let content = std::fs::read_to_string(path)?;
+    debug!("Read {} bytes", content.len());
+    parse_machines(&content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_machines() -> Result<(), AppError> {
+        let machines = read_machines("data/inputtest")?;
+        assert_eq!(machines.len(), 4);
+        assert_eq!(
+            machines[0],
+            Machine {
+                button_a: (94, 34),
+                button_b: (22, 67),
+                prize: (8400, 5400)
+            }
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_line_rejects_wrong_label() {
+        assert!(parse_line("Button B: X+1, Y+2", "Button A:", '+').is_none());
+        assert!(parse_line("Prize: X+1, Y+2", "Prize:", '=').is_none());
+    }
+}
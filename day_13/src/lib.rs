@@ -0,0 +1,47 @@
+//! Day 13: Claw Contraption
+//!
+//! Library entry points shared by the binary and the `aoc` runner.
+
+pub mod calculations;
+pub mod errors;
+pub mod file_io;
+
+use aoc_common::{AppError, Solver};
+
+use calculations::{total_tokens, Machine, PRIZE_OFFSET};
+use file_io::parse_machines;
+
+/// Solver for the claw machines
+pub struct Day13;
+
+impl Solver for Day13 {
+    /// The machines in input order
+    type Parsed = Vec<Machine>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AppError> {
+        parse_machines(input)
+    }
+
+    /// Fewest tokens needed to win every winnable prize
+    fn part1(machines: &Self::Parsed) -> Result<String, AppError> {
+        Ok(total_tokens(machines, 0).to_string())
+    }
+
+    /// Fewest tokens once every prize is moved by `PRIZE_OFFSET`
+    fn part2(machines: &Self::Parsed) -> Result<String, AppError> {
+        Ok(total_tokens(machines, PRIZE_OFFSET).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<(), AppError> {
+        let parsed = Day13::parse(&std::fs::read_to_string("data/inputtest")?)?;
+        assert_eq!(Day13::part1(&parsed)?, "480");
+        assert_eq!(Day13::part2(&parsed)?, "875318608908");
+        Ok(())
+    }
+}
@@ -1,3 +1,60 @@
-fn main() {
-    println!("Welcome to Day 13!");
+//! Day 13: Claw Contraption
+//!
+//! This program finds the fewest tokens needed to win the claw machine prizes,
+//! where button A costs 3 tokens and button B costs 1:
+//! 1. With the prize positions as given
+//! 2. With 10000000000000 added to both prize coordinates
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run -- path/to/input/file
+//! cargo run -- path/to/input/file --format json
+//! ```
+use std::error::Error;
+use std::time::Instant;
+
+use aoc_common::input::resolve_input;
+use aoc_common::logging;
+use aoc_common::messages;
+use aoc_common::msg;
+use aoc_common::output::{take_format, Format, Report};
+
+use day_13::calculations::{total_tokens, PRIZE_OFFSET};
+use day_13::file_io::read_machines;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let format = take_format(&mut args)?;
+    logging::init(logging::take_verbosity(&mut args));
+    messages::init(messages::take_lang(&mut args)?.as_deref())?;
+    if format == Format::Text {
+        println!("{}", msg!("welcome", 13));
+    }
+
+    let path = resolve_input(args.first().map(String::as_str), 13)?;
+    let machines = read_machines(&path)?;
+
+    let start = Instant::now();
+    let part1 = total_tokens(&machines, 0);
+    let part2 = total_tokens(&machines, PRIZE_OFFSET);
+    let elapsed = start.elapsed();
+
+    match format {
+        Format::Text => {
+            println!("{}", msg!("day13-part1", part1));
+            println!("{}", msg!("day13-part2", part2));
+        }
+        Format::Json => {
+            let report = Report {
+                day: 13,
+                part1: Some(part1.to_string()),
+                part2: Some(part2.to_string()),
+                elapsed,
+            };
+            println!("{}", report.to_json());
+        }
+    }
+
+    Ok(())
 }
@@ -0,0 +1,178 @@
+//! Module for simulating robots on a wrapping grid.
+//!
+//! Every robot moves in a straight line and wraps around the edges, so each
+//! x coordinate repeats every `width` seconds and each y coordinate every
+//! `height` seconds. The whole picture therefore repeats with a period of
+//! lcm(width, height), which bounds the search for the Christmas tree.
+
+/// Width of the real bathroom grid
+pub const WIDTH: i64 = 101;
+/// Height of the real bathroom grid
+pub const HEIGHT: i64 = 103;
+
+/// A robot's starting position and velocity per second
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Robot {
+    pub position: (i64, i64),
+    pub velocity: (i64, i64),
+}
+
+/// Greatest common divisor of two non-negative numbers
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+/// Number of seconds after which every robot is back at its starting position
+pub fn cycle_length(width: i64, height: i64) -> i64 {
+    width / gcd(width, height) * height
+}
+
+/// Position of a robot after `seconds`, wrapped onto the grid
+pub fn position_after(robot: &Robot, seconds: i64, width: i64, height: i64) -> (i64, i64) {
+    (
+        (robot.position.0 + robot.velocity.0 * seconds).rem_euclid(width),
+        (robot.position.1 + robot.velocity.1 * seconds).rem_euclid(height),
+    )
+}
+
+/// Multiplies the robot counts of the four quadrants after `seconds`.
+/// Robots exactly on the middle row or column don't count towards any quadrant.
+///
+/// # Arguments
+/// * `robots` - The robots
+/// * `seconds` - How long to simulate
+/// * `width`, `height` - Grid size
+///
+/// # Returns
+/// The safety factor
+pub fn safety_factor(robots: &[Robot], seconds: i64, width: i64, height: i64) -> usize {
+    let (mid_x, mid_y) = (width / 2, height / 2);
+    let mut quadrants = [0; 4];
+
+    for robot in robots {
+        let (x, y) = position_after(robot, seconds, width, height);
+        if x == mid_x || y == mid_y {
+            continue;
+        }
+        quadrants[usize::from(x > mid_x) + 2 * usize::from(y > mid_y)] += 1;
+    }
+
+    quadrants.iter().product()
+}
+
+/// Variance of a set of coordinates, scaled by the count squared to stay in integers
+fn scaled_variance(values: impl Iterator<Item = i64> + Clone) -> i64 {
+    let count = values.clone().count() as i64;
+    let sum: i64 = values.clone().sum();
+    let sum_of_squares: i64 = values.map(|v| v * v).sum();
+    count * sum_of_squares - sum * sum
+}
+
+/// Finds the second, within one period of the given length, at which one
+/// coordinate of the robots is most tightly clustered (lowest variance)
+fn most_clustered_second(period: i64, coordinate: impl Fn(i64) -> Vec<i64>) -> i64 {
+    (0..period)
+        .min_by_key(|&second| scaled_variance(coordinate(second).into_iter()))
+        .unwrap_or(0)
+}
+
+/// Finds the first second at which the robots form a picture.
+///
+/// The picture is where the robots cluster most tightly. Since x and y move
+/// independently, the most clustered x is found within one `width` period and
+/// the most clustered y within one `height` period; the two are then combined
+/// with the Chinese remainder theorem into a single second within the cycle.
+///
+/// # Arguments
+/// * `robots` - The robots
+/// * `width`, `height` - Grid size; must be coprime for the combination to be unique
+///
+/// # Returns
+/// The earliest second, within one cycle, at which both coordinates are most clustered
+pub fn find_picture(robots: &[Robot], width: i64, height: i64) -> i64 {
+    let best_x = most_clustered_second(width, |second| {
+        robots.iter().map(|robot| position_after(robot, second, width, height).0).collect()
+    });
+    let best_y = most_clustered_second(height, |second| {
+        robots.iter().map(|robot| position_after(robot, second, width, height).1).collect()
+    });
+
+    (0..cycle_length(width, height))
+        .step_by(width as usize)
+        .map(|base| base + best_x)
+        .find(|second| second % height == best_y)
+        .unwrap_or(best_x)
+}
+
+/// Renders the robots' positions after `seconds`, `#` marking cells with at least one robot
+pub fn render_frame(robots: &[Robot], seconds: i64, width: i64, height: i64) -> String {
+    let mut cells = vec![vec!['.'; width as usize]; height as usize];
+    for robot in robots {
+        let (x, y) = position_after(robot, seconds, width, height);
+        cells[y as usize][x as usize] = '#';
+    }
+    cells
+        .into_iter()
+        .map(|row| row.into_iter().collect::<String>() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::read_robots;
+    use std::error::Error;
+
+    #[test]
+    fn test_safety_factor() -> Result<(), Box<dyn Error>> {
+        let robots = read_robots("data/inputtest")?;
+        assert_eq!(safety_factor(&robots, 100, 11, 7), 12);
+        Ok(())
+    }
+
+    #[test]
+    fn test_position_after() {
+        let robot = Robot {
+            position: (2, 4),
+            velocity: (2, -3),
+        };
+        assert_eq!(position_after(&robot, 1, 11, 7), (4, 1));
+        assert_eq!(position_after(&robot, 5, 11, 7), (1, 3));
+    }
+
+    #[test]
+    fn test_cycle_length() {
+        assert_eq!(cycle_length(WIDTH, HEIGHT), 10403);
+        assert_eq!(cycle_length(4, 6), 12);
+    }
+
+    /// Robots are placed so that they all land in a small block at a known second
+    #[test]
+    fn test_find_picture() {
+        let target = 6789;
+        let mut robots = Vec::new();
+        for i in 0..200i64 {
+            let velocity = ((i * 37) % 41 - 20, (i * 53) % 43 - 21);
+            let block = (40 + i % 10, 50 + i / 10 % 10);
+            let position = (
+                (block.0 - velocity.0 * target).rem_euclid(WIDTH),
+                (block.1 - velocity.1 * target).rem_euclid(HEIGHT),
+            );
+            robots.push(Robot { position, velocity });
+        }
+        assert_eq!(find_picture(&robots, WIDTH, HEIGHT), target);
+    }
+
+    #[test]
+    fn test_render_frame() {
+        let robots = [Robot {
+            position: (1, 0),
+            velocity: (1, 1),
+        }];
+        assert_eq!(render_frame(&robots, 1, 3, 2), "...\n..#\n");
+    }
+}
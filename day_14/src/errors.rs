@@ -0,0 +1,30 @@
+//! Errors from reading day 14's robot list, which only this day can produce.
+
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{msg, AppError};
+
+/// What can be wrong with a robot, carried by `AppError::Puzzle`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PuzzleError {
+    /// A line that is not of the form `p=x,y v=dx,dy`
+    InvalidRobot(String),
+}
+
+impl Error for PuzzleError {}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::InvalidRobot(line) => msg!("error-invalid-robot", line),
+        };
+        f.write_str(&message)
+    }
+}
+
+impl From<PuzzleError> for AppError {
+    fn from(error: PuzzleError) -> Self {
+        Self::Puzzle(Box::new(error))
+    }
+}
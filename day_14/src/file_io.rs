@@ -0,0 +1,68 @@
+use crate::calculations::Robot;
+use crate::errors::PuzzleError;
+use aoc_common::AppError;
+use tracing::debug;
+
+/// Parses a comma-separated pair such as `6,-3`
+fn parse_pair(pair: &str) -> Option<Result<(i64, i64), AppError>> {
+    let (x, y) = pair.split_once(',')?;
+    Some(x.parse().and_then(|x| Ok((x, y.parse()?))).map_err(AppError::from))
+}
+
+/// Parses the robots, one `p=x,y v=dx,dy` line per robot.
+///
+/// # Arguments
+///
+/// * `content` - The puzzle input
+///
+/// # Returns
+///
+/// * `Result<Vec<Robot>, AppError>` - The robots in input order, or an error
+pub fn parse_robots(content: &str) -> Result<Vec<Robot>, AppError> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let invalid = || PuzzleError::InvalidRobot(line.to_string());
+            let (position, velocity) = line.trim().split_once(' ').ok_or_else(invalid)?;
+            let position = position.strip_prefix("p=").and_then(parse_pair).ok_or_else(invalid)??;
+            let velocity = velocity.strip_prefix("v=").and_then(parse_pair).ok_or_else(invalid)??;
+            Ok(Robot { position, velocity })
+        })
+        .collect()
+}
+
+/// Reads the robots from a file
+///
+/// # Arguments
+///
+/// * `path` - A string slice that holds the path to the file
+///
+/// # Returns
+///
+/// * `Result<Vec<Robot>, AppError>` - The parsed input, or an error if
+///   the file can't be read or parsed
+pub fn read_robots(path: &str) -> Result<Vec<Robot>, AppError> {
+    let content = std::fs::read_to_string(path)?;
+    debug!("Read {} bytes", content.len());
+    parse_robots(&content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_robots() -> Result<(), AppError> {
+        let robots = read_robots("data/inputtest")?;
+        assert_eq!(robots.len(), 12);
+        assert_eq!(
+            robots[0],
+            Robot {
+                position: (0, 4),
+                velocity: (3, -3)
+            }
+        );
+        Ok(())
+    }
+}
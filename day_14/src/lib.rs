@@ -0,0 +1,34 @@
+//! Day 14: Restroom Redoubt
+//!
+//! Library entry points shared by the binary and the `aoc` runner.
+
+pub mod calculations;
+pub mod errors;
+pub mod file_io;
+
+use aoc_common::{AppError, Solver};
+
+use calculations::{find_picture, safety_factor, Robot, HEIGHT, WIDTH};
+use file_io::parse_robots;
+
+/// Solver for the bathroom security robots
+pub struct Day14;
+
+impl Solver for Day14 {
+    /// The robots in input order
+    type Parsed = Vec<Robot>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AppError> {
+        parse_robots(input)
+    }
+
+    /// Safety factor of the quadrants after 100 seconds
+    fn part1(robots: &Self::Parsed) -> Result<String, AppError> {
+        Ok(safety_factor(robots, 100, WIDTH, HEIGHT).to_string())
+    }
+
+    /// First second at which the robots draw the picture
+    fn part2(robots: &Self::Parsed) -> Result<String, AppError> {
+        Ok(find_picture(robots, WIDTH, HEIGHT).to_string())
+    }
+}
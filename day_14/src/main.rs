@@ -1,3 +1,86 @@
-fn main() {
-    println!("Welcome to Day 14!");
+//! Day 14: Restroom Redoubt
+//!
+//! This program simulates robots moving on a grid that wraps around its edges:
+//! 1. Computes the safety factor (product of robots per quadrant) after 100 seconds
+//! 2. Finds the first second at which the robots arrange themselves into a picture
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run -- path/to/input/file
+//! cargo run -- path/to/input/file --format json
+//! cargo run -- path/to/input/file --size 11 7
+//! cargo run -- path/to/input/file --render
+//! ```
+//!
+//! `--size` overrides the default 101×103 grid (the puzzle example uses 11×7), and
+//! `--render` prints the detected frame so the picture can be checked by eye.
+use std::error::Error;
+use std::time::Instant;
+
+use aoc_common::input::resolve_input;
+use aoc_common::logging;
+use aoc_common::messages;
+use aoc_common::msg;
+use aoc_common::output::{take_format, Format, Report};
+use aoc_common::AppError;
+
+use day_14::calculations::{find_picture, render_frame, safety_factor, HEIGHT, WIDTH};
+use day_14::file_io::read_robots;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let format = take_format(&mut args)?;
+    logging::init(logging::take_verbosity(&mut args));
+    messages::init(messages::take_lang(&mut args)?.as_deref())?;
+    if format == Format::Text {
+        println!("{}", msg!("welcome", 14));
+    }
+    let mut args = args.into_iter().peekable();
+    let path = args.next_if(|arg| !arg.starts_with("--"));
+    let (mut width, mut height) = (WIDTH, HEIGHT);
+    let mut render = false;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => {
+                let (Some(w), Some(h)) = (args.next(), args.next()) else {
+                    return Err(Box::new(AppError::ArgError("--size requires a width and a height")));
+                };
+                (width, height) = (w.parse()?, h.parse()?);
+            }
+            "--render" => render = true,
+            _ => {
+                return Err(Box::new(AppError::ArgError(
+                    "Usage: day_14 [<input>] [--size <width> <height>] [--render] [--format <text|json>] [-v|-vv]",
+                )))
+            }
+        }
+    }
+    let robots = read_robots(&resolve_input(path.as_deref(), 14)?)?;
+
+    let start = Instant::now();
+    let safety = safety_factor(&robots, 100, width, height);
+    let second = find_picture(&robots, width, height);
+    let elapsed = start.elapsed();
+
+    match format {
+        Format::Text => {
+            println!("{}", msg!("day14-part1", safety));
+            println!("{}", msg!("day14-part2", second));
+            if render {
+                print!("{}", render_frame(&robots, second, width, height));
+            }
+        }
+        Format::Json => {
+            let report = Report {
+                day: 14,
+                part1: Some(safety.to_string()),
+                part2: Some(second.to_string()),
+                elapsed,
+            };
+            println!("{}", report.to_json());
+        }
+    }
+
+    Ok(())
 }
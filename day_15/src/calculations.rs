@@ -0,0 +1,239 @@
+use ndarray::Array2;
+use std::collections::HashSet;
+
+use aoc_common::AppError;
+
+use crate::errors::PuzzleError;
+
+/// A direction the robot can attempt to move in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// Parses one of `^`, `>`, `v`, `<`
+    pub fn from_char(c: char) -> Option<Direction> {
+        match c {
+            '^' => Some(Direction::Up),
+            '>' => Some(Direction::Right),
+            'v' => Some(Direction::Down),
+            '<' => Some(Direction::Left),
+            _ => None,
+        }
+    }
+
+    fn get_movement(&self) -> (isize, isize) {
+        match self {
+            Direction::Up => (-1, 0),
+            Direction::Right => (0, 1),
+            Direction::Down => (1, 0),
+            Direction::Left => (0, -1),
+        }
+    }
+
+    fn is_vertical(&self) -> bool {
+        matches!(self, Direction::Up | Direction::Down)
+    }
+}
+
+/// Returns the cell one step from `pos` in `direction`. `parse_warehouse`
+/// only accepts walled-in maps, so a step from any non-wall cell stays inside the grid.
+fn step(pos: (usize, usize), direction: Direction) -> (usize, usize) {
+    let (dr, dc) = direction.get_movement();
+    (pos.0.wrapping_add_signed(dr), pos.1.wrapping_add_signed(dc))
+}
+
+fn find_robot(grid: &Array2<char>) -> Option<(usize, usize)> {
+    grid.indexed_iter().find(|&(_, &cell)| cell == '@').map(|(pos, _)| pos)
+}
+
+/// Doubles the width of the warehouse for part 2: walls and empty cells are
+/// doubled, each box becomes a two-cell `[]` box, and the robot stays single-width.
+pub fn widen(grid: &Array2<char>) -> Array2<char> {
+    let data: Vec<char> = grid
+        .iter()
+        .flat_map(|&cell| match cell {
+            'O' => ['[', ']'],
+            '@' => ['@', '.'],
+            other => [other, other],
+        })
+        .collect();
+    Array2::from_shape_vec((grid.nrows(), grid.ncols() * 2), data)
+        .expect("widened data has exactly twice as many columns")
+}
+
+/// Collects every cell that has to move when the robot at `robot` pushes in
+/// `direction`, including the robot itself.
+///
+/// # Returns
+/// `None` if a wall blocks the push, otherwise the cells to move
+fn cells_to_push(grid: &Array2<char>, robot: (usize, usize), direction: Direction) -> Option<Vec<(usize, usize)>> {
+    let mut cells = vec![robot];
+    let mut seen = HashSet::from([robot]);
+    let mut i = 0;
+
+    while i < cells.len() {
+        let next = step(cells[i], direction);
+        i += 1;
+        let mut add = |pos| {
+            if seen.insert(pos) {
+                cells.push(pos);
+            }
+        };
+        match grid[next] {
+            '#' => return None,
+            'O' => add(next),
+            // A wide box pushed vertically drags its other half along
+            '[' => {
+                add(next);
+                if direction.is_vertical() {
+                    add(step(next, Direction::Right));
+                }
+            }
+            ']' => {
+                add(next);
+                if direction.is_vertical() {
+                    add(step(next, Direction::Left));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    Some(cells)
+}
+
+/// Attempts one robot move, pushing any boxes in the way
+///
+/// # Returns
+/// The robot's position after the move
+fn apply_move(grid: &mut Array2<char>, robot: (usize, usize), direction: Direction) -> (usize, usize) {
+    let Some(cells) = cells_to_push(grid, robot, direction) else {
+        return robot;
+    };
+
+    let contents: Vec<char> = cells.iter().map(|&pos| grid[pos]).collect();
+    for &pos in &cells {
+        grid[pos] = '.';
+    }
+    for (&pos, &cell) in cells.iter().zip(&contents) {
+        grid[step(pos, direction)] = cell;
+    }
+
+    step(robot, direction)
+}
+
+/// Runs every move on the warehouse, calling `on_frame` with the grid after each one.
+///
+/// Works on both the normal and the widened warehouse.
+///
+/// # Arguments
+///
+/// * `grid` - The warehouse, updated in place
+/// * `moves` - The robot's moves
+/// * `on_frame` - Called after every move, e.g. to record an animation
+///
+/// # Returns
+///
+/// * `Result<(), AppError>` - Success, or an error if there is no robot
+pub fn run_moves(
+    grid: &mut Array2<char>,
+    moves: &[Direction],
+    mut on_frame: impl FnMut(&Array2<char>),
+) -> Result<(), AppError> {
+    let mut robot = find_robot(grid).ok_or(PuzzleError::NoRobot)?;
+    for &direction in moves {
+        robot = apply_move(grid, robot, direction);
+        on_frame(grid);
+    }
+    Ok(())
+}
+
+/// Sums the GPS coordinates (100 × row + column) of every box, measured
+/// from the left edge of wide boxes
+pub fn gps_sum(grid: &Array2<char>) -> usize {
+    grid.indexed_iter()
+        .filter(|&(_, &cell)| cell == 'O' || cell == '[')
+        .map(|((row, col), _)| 100 * row + col)
+        .sum()
+}
+
+/// Renders the warehouse, one line per row
+pub fn render(grid: &Array2<char>) -> String {
+    grid.rows()
+        .into_iter()
+        .map(|row| row.iter().collect::<String>() + "\n")
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::read_file;
+    use std::error::Error;
+
+    #[test]
+    fn test_gps_sum_small() -> Result<(), Box<dyn Error>> {
+        let (mut grid, moves) = read_file("data/inputtest_small")?;
+        run_moves(&mut grid, &moves, |_| {})?;
+        assert_eq!(gps_sum(&grid), 2028);
+        Ok(())
+    }
+
+    #[test]
+    fn test_gps_sum() -> Result<(), Box<dyn Error>> {
+        let (mut grid, moves) = read_file("data/inputtest")?;
+        run_moves(&mut grid, &moves, |_| {})?;
+        assert_eq!(gps_sum(&grid), 10092);
+        Ok(())
+    }
+
+    #[test]
+    fn test_gps_sum_wide() -> Result<(), Box<dyn Error>> {
+        let (grid, moves) = read_file("data/inputtest")?;
+        let mut grid = widen(&grid);
+        run_moves(&mut grid, &moves, |_| {})?;
+        assert_eq!(gps_sum(&grid), 9021);
+        Ok(())
+    }
+
+    #[test]
+    fn test_widen() -> Result<(), Box<dyn Error>> {
+        let grid = Array2::from_shape_vec((1, 4), "#O@.".chars().collect())?;
+        assert_eq!(render(&widen(&grid)), "##[]@...\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_wide_push_blocked() -> Result<(), Box<dyn Error>> {
+        // The lower box pushes the upper one, whose left half is under a wall
+        let rows = ["#...", "[]..", ".[].", ".@.."];
+        let mut grid = Array2::from_shape_vec((4, 4), rows.concat().chars().collect())?;
+        let before = grid.clone();
+        run_moves(&mut grid, &[Direction::Up], |_| {})?;
+        assert_eq!(grid, before, "nothing moves when any half is blocked");
+        Ok(())
+    }
+
+    #[test]
+    fn test_wide_push_moves_both_boxes() -> Result<(), Box<dyn Error>> {
+        let rows = ["....", "[]..", ".[].", ".@.."];
+        let mut grid = Array2::from_shape_vec((4, 4), rows.concat().chars().collect())?;
+        run_moves(&mut grid, &[Direction::Up], |_| {})?;
+        assert_eq!(render(&grid), "[]..\n.[].\n.@..\n....\n");
+        Ok(())
+    }
+
+    #[test]
+    fn test_on_frame_called_per_move() -> Result<(), Box<dyn Error>> {
+        let (mut grid, moves) = read_file("data/inputtest_small")?;
+        let mut frames = 0;
+        run_moves(&mut grid, &moves, |_| frames += 1)?;
+        assert_eq!(frames, moves.len());
+        Ok(())
+    }
+}
@@ -0,0 +1,44 @@
+//! Errors from day 15's warehouse and move list, which only this day can produce.
+
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{msg, AppError};
+
+/// What can go wrong in the warehouse, carried by `AppError::Puzzle`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PuzzleError {
+    /// No robot in the warehouse
+    NoRobot,
+    /// A character in the move list that is not one of `^`, `v`, `<`, `>`
+    InvalidMove(char),
+}
+
+impl Error for PuzzleError {}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::NoRobot => msg!("error-no-robot"),
+            Self::InvalidMove(c) => msg!("error-invalid-move", format!("{:?}", c)),
+        };
+        f.write_str(&message)
+    }
+}
+
+impl From<PuzzleError> for AppError {
+    fn from(error: PuzzleError) -> Self {
+        Self::Puzzle(Box::new(error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(PuzzleError::InvalidMove('x').to_string(), "Invalid move: 'x'");
+        assert_eq!(AppError::from(PuzzleError::NoRobot).to_string(), "No robot found in warehouse");
+    }
+}
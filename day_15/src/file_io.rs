@@ -0,0 +1,109 @@
+use ndarray::Array2;
+
+use crate::AppError;
+use crate::calculations::Direction;
+use crate::errors::PuzzleError;
+
+/// Parses the warehouse map and the robot's move list.
+///
+/// The map comes first, one row per line, followed by a blank line and the
+/// moves, which may be split across several lines.
+///
+/// # Arguments
+///
+/// * `content` - The puzzle input
+///
+/// # Returns
+///
+/// * `Result<(Array2<char>, Vec<Direction>), AppError>` - The map and the moves in order, or an error
+///
+/// # Errors
+///
+/// Returns an error if:
+/// - The map contains lines of different lengths (`AppError::RaggedInput`)
+/// - The map's outer rows and columns are not all walls (`#`)
+/// - The move list contains a character other than `^`, `v`, `<`, `>`
+pub fn parse_warehouse(content: &str) -> Result<(Array2<char>, Vec<Direction>), AppError> {
+    let content = content.replace("\r\n", "\n");
+    let (map, moves) = content.split_once("\n\n").unwrap_or((&content, ""));
+
+    let lines: Vec<&str> = map.lines().collect();
+    let rows = lines.len();
+    let cols = lines.first().map_or(0, |line| line.chars().count());
+    let widths = lines.iter().map(|line| line.chars().count());
+    if let Some((index, got)) = widths.enumerate().find(|&(_, width)| width != cols) {
+        return Err(AppError::RaggedInput { line: index + 1, expected: cols, got });
+    }
+    let data: Vec<char> = lines.concat().chars().collect();
+    let grid = Array2::from_shape_vec((rows, cols), data).map_err(|_| AppError::Array2CreationError)?;
+    if !is_walled_in(&grid) {
+        return Err(AppError::InvalidInput("the warehouse must be surrounded by walls"));
+    }
+
+    let moves = moves
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| Direction::from_char(c).ok_or(AppError::from(PuzzleError::InvalidMove(c))))
+        .collect::<Result<_, _>>()?;
+
+    Ok((grid, moves))
+}
+
+/// Whether every cell on the edge of the map is a wall, so the robot and the
+/// boxes can never be pushed off it
+fn is_walled_in(grid: &Array2<char>) -> bool {
+    let (rows, cols) = grid.dim();
+    grid.indexed_iter()
+        .filter(|&((row, col), _)| row == 0 || col == 0 || row + 1 == rows || col + 1 == cols)
+        .all(|(_, &cell)| cell == '#')
+}
+
+/// Reads the warehouse map and the move list from a file
+///
+/// # Arguments
+///
+/// * `filename` - A string slice that holds the path to the file
+///
+/// # Returns
+///
+/// * `Result<(Array2<char>, Vec<Direction>), AppError>` - The parsed input, or an error if
+///   the file can't be read or parsed
+pub fn read_file(filename: &str) -> Result<(Array2<char>, Vec<Direction>), AppError> {
+    let content = std::fs::read_to_string(filename)?;
+    parse_warehouse(&content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_file() -> Result<(), AppError> {
+        let (grid, moves) = read_file("data/inputtest_small")?;
+        assert_eq!(grid.dim(), (8, 8));
+        assert_eq!(grid[[2, 2]], '@');
+        assert_eq!(moves.len(), 15);
+        assert_eq!(moves[0], Direction::Left);
+        Ok(())
+    }
+
+    #[test]
+    fn test_requires_walls() {
+        assert!(matches!(parse_warehouse("@O.\n\n<"), Err(AppError::InvalidInput(_))));
+        assert!(matches!(parse_warehouse("####\n#@O.\n####\n\n>"), Err(AppError::InvalidInput(_))));
+        assert!(parse_warehouse("####\n#@.#\n####\n\n>").is_ok());
+    }
+
+    #[test]
+    fn test_rejects_ragged_map() {
+        // Same total length as a 3x4 map, so the shape alone doesn't catch it
+        assert!(matches!(
+            parse_warehouse("####\n#@.##\n###\n\n>"),
+            Err(AppError::RaggedInput { line: 2, expected: 4, got: 5 })
+        ));
+        assert!(matches!(
+            parse_warehouse("####\n#@.#\n###\n\n>"),
+            Err(AppError::RaggedInput { line: 3, expected: 4, got: 3 })
+        ));
+    }
+}
@@ -0,0 +1,52 @@
+//! Day 15: Warehouse Woes
+//!
+//! Library entry points shared by the binary and the `aoc` runner.
+
+pub mod calculations;
+pub mod errors;
+pub mod file_io;
+
+use aoc_common::{AppError, Solver};
+use ndarray::Array2;
+
+use calculations::{gps_sum, run_moves, widen, Direction};
+use file_io::parse_warehouse;
+
+/// Solver for the lanternfish warehouse
+pub struct Day15;
+
+impl Solver for Day15 {
+    /// The warehouse map and the robot's moves
+    type Parsed = (Array2<char>, Vec<Direction>);
+
+    fn parse(input: &str) -> Result<Self::Parsed, AppError> {
+        parse_warehouse(input)
+    }
+
+    /// GPS sum of the boxes after every move
+    fn part1((grid, moves): &Self::Parsed) -> Result<String, AppError> {
+        let mut warehouse = grid.clone();
+        run_moves(&mut warehouse, moves, |_| {})?;
+        Ok(gps_sum(&warehouse).to_string())
+    }
+
+    /// GPS sum of the boxes after every move in the widened warehouse
+    fn part2((grid, moves): &Self::Parsed) -> Result<String, AppError> {
+        let mut warehouse = widen(grid);
+        run_moves(&mut warehouse, moves, |_| {})?;
+        Ok(gps_sum(&warehouse).to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<(), AppError> {
+        let parsed = Day15::parse(&std::fs::read_to_string("data/inputtest")?)?;
+        assert_eq!(Day15::part1(&parsed)?, "10092");
+        assert_eq!(Day15::part2(&parsed)?, "9021");
+        Ok(())
+    }
+}
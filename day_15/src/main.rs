@@ -1,3 +1,77 @@
-fn main() {
-    println!("Welcome to Day 15!");
+//! Day 15: Warehouse Woes
+//!
+//! This program simulates a robot pushing boxes around a warehouse and sums
+//! the boxes' GPS coordinates afterwards:
+//! 1. In the warehouse as given
+//! 2. In the widened warehouse, where every box is two cells wide
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run -- path/to/input/file
+//! cargo run -- path/to/input/file --format json
+//! cargo run -- path/to/input/file --show
+//! ```
+//!
+//! With `--show`, the final state of both warehouses is printed.
+use std::error::Error;
+use std::time::Instant;
+
+use aoc_common::input::resolve_input;
+use aoc_common::logging;
+use aoc_common::messages;
+use aoc_common::msg;
+use aoc_common::output::{take_format, Format, Report};
+use aoc_common::AppError;
+
+use day_15::calculations::{gps_sum, render, run_moves, widen};
+use day_15::file_io::read_file;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let format = take_format(&mut args)?;
+    logging::init(logging::take_verbosity(&mut args));
+    messages::init(messages::take_lang(&mut args)?.as_deref())?;
+    if format == Format::Text {
+        println!("{}", msg!("welcome", 15));
+    }
+    let (path, show) = match args.as_slice() {
+        [] => (None, false),
+        [flag] if flag == "--show" => (None, true),
+        [path] => (Some(path.as_str()), false),
+        [path, flag] if flag == "--show" => (Some(path.as_str()), true),
+        _ => return Err(Box::new(AppError::ArgError("Usage: day_15 [<input>] [--show] [--format <text|json>] [-v|-vv]"))),
+    };
+    let (grid, moves) = read_file(&resolve_input(path, 15)?)?;
+
+    let start = Instant::now();
+    let mut warehouse = grid.clone();
+    run_moves(&mut warehouse, &moves, |_| {})?;
+    let mut wide_warehouse = widen(&grid);
+    run_moves(&mut wide_warehouse, &moves, |_| {})?;
+    let elapsed = start.elapsed();
+
+    match format {
+        Format::Text => {
+            println!("{}", msg!("day15-part1", gps_sum(&warehouse)));
+            if show {
+                println!("{}", render(&warehouse));
+            }
+            println!("{}", msg!("day15-part2", gps_sum(&wide_warehouse)));
+            if show {
+                println!("{}", render(&wide_warehouse));
+            }
+        }
+        Format::Json => {
+            let report = Report {
+                day: 15,
+                part1: Some(gps_sum(&warehouse).to_string()),
+                part2: Some(gps_sum(&wide_warehouse).to_string()),
+                elapsed,
+            };
+            println!("{}", report.to_json());
+        }
+    }
+
+    Ok(())
 }
@@ -0,0 +1,253 @@
+//! Module implementing the 3-bit chronospatial computer.
+
+use aoc_common::AppError;
+
+use crate::errors::PuzzleError;
+
+/// The most instructions `Computer::run` executes before giving up on the program halting
+pub const MAX_STEPS: usize = 1_000_000;
+
+/// The computer's three registers
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Computer {
+    pub a: u64,
+    pub b: u64,
+    pub c: u64,
+}
+
+/// The eight instructions, identified by their 3-bit opcode
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Opcode {
+    /// A = A >> combo
+    Adv,
+    /// B = B ^ literal
+    Bxl,
+    /// B = combo % 8
+    Bst,
+    /// Jump to literal if A != 0
+    Jnz,
+    /// B = B ^ C (operand ignored)
+    Bxc,
+    /// Output combo % 8
+    Out,
+    /// B = A >> combo
+    Bdv,
+    /// C = A >> combo
+    Cdv,
+}
+
+impl Opcode {
+    fn from_value(value: u8) -> Opcode {
+        match value & 7 {
+            0 => Opcode::Adv,
+            1 => Opcode::Bxl,
+            2 => Opcode::Bst,
+            3 => Opcode::Jnz,
+            4 => Opcode::Bxc,
+            5 => Opcode::Out,
+            6 => Opcode::Bdv,
+            _ => Opcode::Cdv,
+        }
+    }
+}
+
+impl Computer {
+    /// Resolves a combo operand: 0-3 are literal, 4-6 read registers A-C
+    fn combo(&self, operand: u8) -> Result<u64, AppError> {
+        match operand {
+            0..=3 => Ok(u64::from(operand)),
+            4 => Ok(self.a),
+            5 => Ok(self.b),
+            6 => Ok(self.c),
+            _ => Err(PuzzleError::ReservedOperand.into()),
+        }
+    }
+
+    /// Shifts A right by `amount`, treating shifts past the register width as producing 0
+    fn shifted_a(&self, amount: u64) -> u64 {
+        u32::try_from(amount)
+            .ok()
+            .and_then(|amount| self.a.checked_shr(amount))
+            .unwrap_or(0)
+    }
+
+    /// Runs the program until the instruction pointer moves past its end,
+    /// executing at most `MAX_STEPS` instructions
+    ///
+    /// # Arguments
+    /// * `program` - The program as 3-bit values, alternating opcodes and operands
+    ///
+    /// # Returns
+    /// The values output by the program, or an error if it uses the reserved
+    /// operand or doesn't halt in time
+    pub fn run(&mut self, program: &[u8]) -> Result<Vec<u8>, AppError> {
+        self.run_with(program, MAX_STEPS)
+    }
+
+    /// Like `run`, but with a custom instruction limit
+    ///
+    /// # Arguments
+    /// * `program` - The program as 3-bit values, alternating opcodes and operands
+    /// * `max_steps` - The most instructions to execute
+    ///
+    /// # Returns
+    /// The values output by the program, or an error if it uses the reserved
+    /// operand or is still running after `max_steps` instructions
+    pub fn run_with(&mut self, program: &[u8], max_steps: usize) -> Result<Vec<u8>, AppError> {
+        let mut output = Vec::new();
+        let mut ip = 0;
+        let mut steps = 0;
+
+        while ip + 1 < program.len() {
+            if steps == max_steps {
+                return Err(PuzzleError::StepLimit(max_steps).into());
+            }
+            steps += 1;
+            let operand = program[ip + 1];
+            ip += 2;
+            match Opcode::from_value(program[ip - 2]) {
+                Opcode::Adv => self.a = self.shifted_a(self.combo(operand)?),
+                Opcode::Bxl => self.b ^= u64::from(operand),
+                Opcode::Bst => self.b = self.combo(operand)? % 8,
+                Opcode::Jnz => {
+                    if self.a != 0 {
+                        ip = usize::from(operand);
+                    }
+                }
+                Opcode::Bxc => self.b ^= self.c,
+                Opcode::Out => output.push((self.combo(operand)? % 8) as u8),
+                Opcode::Bdv => self.b = self.shifted_a(self.combo(operand)?),
+                Opcode::Cdv => self.c = self.shifted_a(self.combo(operand)?),
+            }
+        }
+
+        Ok(output)
+    }
+}
+
+/// Joins output values with commas, as the puzzle expects the answer
+pub fn format_output(output: &[u8]) -> String {
+    output.iter().map(u8::to_string).collect::<Vec<_>>().join(",")
+}
+
+/// Extends `prefix` (the high bits of A found so far) by one octal digit at a
+/// time, keeping only candidates whose output matches the end of the program
+fn search_quine(computer: &Computer, program: &[u8], remaining: usize, prefix: u64) -> Result<Option<u64>, AppError> {
+    for digit in 0..8 {
+        let a = prefix
+            .checked_mul(8)
+            .map(|a| a + digit)
+            .ok_or(AppError::Overflow("register A search"))?;
+        let output = Computer { a, ..*computer }.run(program)?;
+        if output != program[remaining..] {
+            continue;
+        }
+        if remaining == 0 {
+            return Ok(Some(a));
+        }
+        if let Some(a) = search_quine(computer, program, remaining - 1, a)? {
+            return Ok(Some(a));
+        }
+    }
+    Ok(None)
+}
+
+/// Finds the lowest value of register A that makes the program output a copy of itself.
+///
+/// Like the puzzle programs, the program must consume A three bits per loop
+/// iteration, so each output value depends only on the remaining high bits of
+/// A. The search therefore fixes A one octal digit at a time, from the most
+/// significant, matching the program's output from its last value backwards
+/// and backtracking when no digit fits. Trying digits in ascending order makes
+/// the first complete match the lowest.
+///
+/// # Arguments
+/// * `computer` - The computer whose B and C registers are used as given
+/// * `program` - The program as 3-bit values
+///
+/// # Returns
+/// The lowest such A, `PuzzleError::NoQuine` if there is none, or an error if a
+/// candidate doesn't halt or the search outgrows a 64-bit register
+pub fn find_quine(computer: &Computer, program: &[u8]) -> Result<u64, AppError> {
+    if program.is_empty() {
+        return Err(PuzzleError::NoQuine.into());
+    }
+    search_quine(computer, program, program.len() - 1, 0)?.ok_or_else(|| PuzzleError::NoQuine.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::read_program;
+    use std::error::Error;
+
+    #[test]
+    fn test_run_example() -> Result<(), Box<dyn Error>> {
+        let (mut computer, program) = read_program("data/inputtest")?;
+        assert_eq!(format_output(&computer.run(&program)?), "4,6,3,5,6,3,5,2,1,0");
+        Ok(())
+    }
+
+    #[test]
+    fn test_find_quine() -> Result<(), Box<dyn Error>> {
+        let (computer, program) = read_program("data/inputtest_quine")?;
+        let a = find_quine(&computer, &program)?;
+        assert_eq!(a, 117440);
+        assert_eq!(Computer { a, ..computer }.run(&program)?, program);
+        Ok(())
+    }
+
+    #[test]
+    fn test_small_programs() -> Result<(), AppError> {
+        let mut computer = Computer { a: 0, b: 0, c: 9 };
+        computer.run(&[2, 6])?;
+        assert_eq!(computer.b, 1);
+
+        let mut computer = Computer { a: 10, b: 0, c: 0 };
+        assert_eq!(computer.run(&[5, 0, 5, 1, 5, 4])?, vec![0, 1, 2]);
+
+        let mut computer = Computer { a: 2024, b: 0, c: 0 };
+        assert_eq!(
+            computer.run(&[0, 1, 5, 4, 3, 0])?,
+            vec![4, 2, 5, 6, 7, 7, 7, 7, 3, 1, 0]
+        );
+        assert_eq!(computer.a, 0);
+
+        let mut computer = Computer { a: 0, b: 29, c: 0 };
+        computer.run(&[1, 7])?;
+        assert_eq!(computer.b, 26);
+
+        let mut computer = Computer { a: 0, b: 2024, c: 43690 };
+        computer.run(&[4, 0])?;
+        assert_eq!(computer.b, 44354);
+        Ok(())
+    }
+
+    /// Returns the day's own error a result failed with, if any
+    fn puzzle_error<T>(result: Result<T, AppError>) -> Option<PuzzleError> {
+        result.err().and_then(|e| e.puzzle().cloned())
+    }
+
+    #[test]
+    fn test_reserved_operand() {
+        let mut computer = Computer { a: 0, b: 0, c: 0 };
+        assert_eq!(puzzle_error(computer.run(&[5, 7])), Some(PuzzleError::ReservedOperand));
+    }
+
+    #[test]
+    fn test_step_limit() -> Result<(), AppError> {
+        // Jumps back to the start forever while A is non-zero
+        let mut computer = Computer { a: 1, b: 0, c: 0 };
+        assert_eq!(puzzle_error(computer.run(&[3, 0])), Some(PuzzleError::StepLimit(MAX_STEPS)));
+
+        let mut computer = Computer { a: 1, b: 0, c: 0 };
+        assert_eq!(puzzle_error(computer.run_with(&[5, 4, 3, 0], 5)), Some(PuzzleError::StepLimit(5)));
+
+        let mut computer = Computer { a: 0, b: 0, c: 0 };
+        assert_eq!(computer.run_with(&[5, 4, 3, 0], 2)?, vec![0]);
+
+        let computer = Computer { a: 0, b: 0, c: 0 };
+        assert!(matches!(puzzle_error(find_quine(&computer, &[3, 0])), Some(PuzzleError::StepLimit(_))));
+        Ok(())
+    }
+}
@@ -0,0 +1,40 @@
+//! Errors from parsing and running day 17's 3-bit programs, which only this day
+//! can produce.
+
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{msg, AppError};
+
+/// What can go wrong parsing or running a program, carried by `AppError::Puzzle`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PuzzleError {
+    /// A program value that is not a 3-bit number
+    InvalidValue(u64),
+    /// Use of the reserved combo operand 7
+    ReservedOperand,
+    /// No register A value makes the program output itself
+    NoQuine,
+    /// The program still running after the given number of instructions
+    StepLimit(usize),
+}
+
+impl Error for PuzzleError {}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::InvalidValue(value) => msg!("error-invalid-value", value),
+            Self::ReservedOperand => msg!("error-reserved-operand"),
+            Self::NoQuine => msg!("error-no-quine"),
+            Self::StepLimit(steps) => msg!("error-step-limit", steps),
+        };
+        f.write_str(&message)
+    }
+}
+
+impl From<PuzzleError> for AppError {
+    fn from(error: PuzzleError) -> Self {
+        Self::Puzzle(Box::new(error))
+    }
+}
@@ -0,0 +1,80 @@
+use crate::calculations::Computer;
+use crate::errors::PuzzleError;
+use aoc_common::AppError;
+use tracing::debug;
+
+/// Parses the value after `label` on a line such as `Register A: 729`
+fn parse_value<T: std::str::FromStr<Err = std::num::ParseIntError>>(
+    line: Option<&str>,
+    label: &str,
+    missing: &'static str,
+) -> Result<T, AppError> {
+    let value = line
+        .and_then(|line| line.trim().strip_prefix(label))
+        .ok_or(AppError::InvalidInput(missing))?;
+    Ok(value.trim().parse()?)
+}
+
+/// Parses the initial register values and the program.
+///
+/// # Arguments
+///
+/// * `content` - The puzzle input
+///
+/// # Returns
+///
+/// * `Result<(Computer, Vec<u8>), AppError>` - The computer with its registers set,
+///   and the program as 3-bit values, or an error
+pub fn parse_program(content: &str) -> Result<(Computer, Vec<u8>), AppError> {
+    let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+    let computer = Computer {
+        a: parse_value(lines.next(), "Register A:", "missing register A")?,
+        b: parse_value(lines.next(), "Register B:", "missing register B")?,
+        c: parse_value(lines.next(), "Register C:", "missing register C")?,
+    };
+
+    let program = lines
+        .next()
+        .and_then(|line| line.trim().strip_prefix("Program:"))
+        .ok_or(AppError::InvalidInput("missing program"))?
+        .split(',')
+        .map(|value| {
+            let value: u64 = value.trim().parse()?;
+            u8::try_from(value)
+                .ok()
+                .filter(|&v| v < 8)
+                .ok_or(AppError::from(PuzzleError::InvalidValue(value)))
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok((computer, program))
+}
+
+/// Reads the registers and the program from a file
+///
+/// # Arguments
+///
+/// * `path` - A string slice that holds the path to the file
+///
+/// # Returns
+///
+/// * `Result<(Computer, Vec<u8>), AppError>` - The parsed input, or an error if
+///   the file can't be read or parsed
+pub fn read_program(path: &str) -> Result<(Computer, Vec<u8>), AppError> {
+    let content = std::fs::read_to_string(path)?;
+    debug!("Read {} bytes", content.len());
+    parse_program(&content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_program() -> Result<(), AppError> {
+        let (computer, program) = read_program("data/inputtest")?;
+        assert_eq!(computer, Computer { a: 729, b: 0, c: 0 });
+        assert_eq!(program, vec![0, 1, 5, 4, 3, 0]);
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+//! Day 17: Chronospatial Computer
+//!
+//! Library entry points shared by the binary and the `aoc` runner.
+
+pub mod calculations;
+pub mod errors;
+pub mod file_io;
+
+use aoc_common::{AppError, Solver};
+
+use calculations::{find_quine, format_output, Computer};
+use file_io::parse_program;
+
+/// Solver for the chronospatial computer
+pub struct Day17;
+
+impl Solver for Day17 {
+    /// The computer's initial registers and the program
+    type Parsed = (Computer, Vec<u8>);
+
+    fn parse(input: &str) -> Result<Self::Parsed, AppError> {
+        parse_program(input)
+    }
+
+    /// The program's output, joined by commas
+    fn part1((computer, program): &Self::Parsed) -> Result<String, AppError> {
+        let mut computer = *computer;
+        Ok(format_output(&computer.run(program)?))
+    }
+
+    /// The lowest register A value that makes the program output itself
+    fn part2((computer, program): &Self::Parsed) -> Result<String, AppError> {
+        Ok(find_quine(computer, program)?.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_example() -> Result<(), AppError> {
+        let parsed = Day17::parse(&std::fs::read_to_string("data/inputtest")?)?;
+        assert_eq!(Day17::part1(&parsed)?, "4,6,3,5,6,3,5,2,1,0");
+        let parsed = Day17::parse(&std::fs::read_to_string("data/inputtest_quine")?)?;
+        assert_eq!(Day17::part2(&parsed)?, "117440");
+        Ok(())
+    }
+}
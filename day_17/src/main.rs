@@ -1,3 +1,63 @@
-fn main() {
-    println!("Welcome to Day 17!");
+//! Day 17: Chronospatial Computer
+//!
+//! This program emulates the 3-bit computer:
+//! 1. Runs the program and prints its comma-separated output
+//! 2. Finds the lowest value of register A that makes the program output itself
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run -- path/to/input/file
+//! cargo run -- path/to/input/file --format json
+//! ```
+use std::error::Error;
+use std::time::Instant;
+
+use aoc_common::input::resolve_input;
+use aoc_common::logging;
+use aoc_common::messages;
+use aoc_common::msg;
+use aoc_common::output::{take_format, Format, Report};
+
+use day_17::calculations::{find_quine, format_output};
+use day_17::file_io::read_program;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let format = take_format(&mut args)?;
+    logging::init(logging::take_verbosity(&mut args));
+    messages::init(messages::take_lang(&mut args)?.as_deref())?;
+    if format == Format::Text {
+        println!("{}", msg!("welcome", 17));
+    }
+
+    let path = resolve_input(args.first().map(String::as_str), 17)?;
+    let (mut computer, program) = read_program(&path)?;
+    let initial = computer;
+
+    let start = Instant::now();
+    let output = computer.run(&program)?;
+    let quine = find_quine(&initial, &program);
+    let elapsed = start.elapsed();
+
+    match format {
+        Format::Text => {
+            println!("{}", msg!("day17-part1", format_output(&output)));
+            match quine {
+                Ok(a) => println!("{}", msg!("day17-part2", a)),
+                Err(e) => println!("{}", e),
+            }
+        }
+        Format::Json => {
+            let report = Report {
+                day: 17,
+                part1: Some(format_output(&output)),
+                part2: quine.ok().map(|a| a.to_string()),
+                elapsed,
+            };
+            println!("{}", report.to_json());
+        }
+    }
+
+    Ok(())
 }
@@ -0,0 +1,133 @@
+//! Module for finding paths through the corrupted memory space.
+
+use aoc_common::AppError;
+use ndarray::Array2;
+use std::collections::VecDeque;
+
+use crate::errors::PuzzleError;
+
+/// Side length of the puzzle's memory space (coordinates 0..=70)
+pub const SIZE: usize = 71;
+
+/// Number of bytes that have fallen when part 1 is measured
+pub const FALLEN: usize = 1024;
+
+/// Builds the memory space with the first `count` bytes marked as corrupted
+///
+/// # Arguments
+/// * `bytes` - The byte positions as `(x, y)` in the order they fall
+/// * `count` - How many of them have fallen
+/// * `size` - Side length of the square memory space
+///
+/// # Returns
+/// A grid indexed `[y, x]` where `true` marks a corrupted cell
+pub fn corrupt(bytes: &[(usize, usize)], count: usize, size: usize) -> Result<Array2<bool>, AppError> {
+    let mut grid = Array2::from_elem((size, size), false);
+    for &(x, y) in bytes.iter().take(count) {
+        *grid.get_mut((y, x)).ok_or(PuzzleError::OutOfBounds(x, y))? = true;
+    }
+    Ok(grid)
+}
+
+/// Finds the fewest steps from the top-left to the bottom-right corner by breadth-first search
+///
+/// # Arguments
+/// * `grid` - The memory space, `true` marking corrupted cells
+///
+/// # Returns
+/// The number of steps, or `None` if the exit is unreachable
+pub fn shortest_path(grid: &Array2<bool>) -> Option<usize> {
+    let (rows, cols) = grid.dim();
+    let exit = (rows.checked_sub(1)?, cols.checked_sub(1)?);
+    if grid[(0, 0)] || grid[exit] {
+        return None;
+    }
+
+    let mut distance = Array2::from_elem((rows, cols), usize::MAX);
+    let mut queue = VecDeque::from([(0, 0)]);
+    distance[(0, 0)] = 0;
+
+    while let Some((row, col)) = queue.pop_front() {
+        let steps = distance[(row, col)];
+        if (row, col) == exit {
+            return Some(steps);
+        }
+        let neighbors = [
+            (row.wrapping_sub(1), col),
+            (row + 1, col),
+            (row, col.wrapping_sub(1)),
+            (row, col + 1),
+        ];
+        for next in neighbors {
+            if grid.get(next) == Some(&false) && distance[next] == usize::MAX {
+                distance[next] = steps + 1;
+                queue.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Finds the first byte whose fall cuts the exit off from the start.
+///
+/// Once blocked the path stays blocked, so the number of fallen bytes is
+/// binary searched for the smallest count with no path; that count's last
+/// byte is the culprit.
+///
+/// # Arguments
+/// * `bytes` - The byte positions as `(x, y)` in the order they fall
+/// * `size` - Side length of the square memory space
+///
+/// # Returns
+/// The blocking byte's position, or `None` if no byte is to blame: the exit is
+/// reachable after every byte falls, or in an empty memory space never was
+pub fn first_blocking_byte(bytes: &[(usize, usize)], size: usize) -> Result<Option<(usize, usize)>, AppError> {
+    let blocked = |count: usize| -> Result<bool, AppError> {
+        Ok(shortest_path(&corrupt(bytes, count, size)?).is_none())
+    };
+
+    let (mut low, mut high) = (0, bytes.len());
+    if !blocked(high)? {
+        return Ok(None);
+    }
+    // Invariant: `low` fallen bytes leave a path (or low == 0), `high` block it
+    while low + 1 < high {
+        let mid = low + (high - low) / 2;
+        if blocked(mid)? {
+            high = mid;
+        } else {
+            low = mid;
+        }
+    }
+    // With `high == 0` the exit was blocked before any byte fell
+    Ok(high.checked_sub(1).map(|last| bytes[last]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_io::read_bytes;
+
+    #[test]
+    fn test_shortest_path() -> Result<(), AppError> {
+        let bytes = read_bytes("data/inputtest")?;
+        let grid = corrupt(&bytes, 12, 7)?;
+        assert_eq!(shortest_path(&grid), Some(22));
+        Ok(())
+    }
+
+    #[test]
+    fn test_first_blocking_byte() -> Result<(), AppError> {
+        let bytes = read_bytes("data/inputtest")?;
+        assert_eq!(first_blocking_byte(&bytes, 7)?, Some((6, 1)));
+        assert_eq!(first_blocking_byte(&bytes[..12], 7)?, None);
+        assert_eq!(first_blocking_byte(&[], 0)?, None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_out_of_bounds() {
+        let error = corrupt(&[(7, 0)], 1, 7).err();
+        assert_eq!(error.as_ref().and_then(AppError::puzzle), Some(&PuzzleError::OutOfBounds(7, 0)));
+    }
+}
@@ -0,0 +1,44 @@
+//! Errors from day 18's falling byte positions, which only this day can produce.
+
+use std::error::Error;
+use std::fmt;
+
+use aoc_common::{msg, AppError};
+
+/// What can be wrong with a falling byte, carried by `AppError::Puzzle`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PuzzleError {
+    /// A line that is not an `x,y` coordinate pair
+    InvalidByte(String),
+    /// A byte that falls outside the memory space
+    OutOfBounds(usize, usize),
+}
+
+impl Error for PuzzleError {}
+
+impl fmt::Display for PuzzleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::InvalidByte(line) => msg!("error-invalid-byte", format!("{:?}", line)),
+            Self::OutOfBounds(x, y) => msg!("error-out-of-bounds", x, y),
+        };
+        f.write_str(&message)
+    }
+}
+
+impl From<PuzzleError> for AppError {
+    fn from(error: PuzzleError) -> Self {
+        Self::Puzzle(Box::new(error))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        assert_eq!(AppError::from(PuzzleError::OutOfBounds(7, 71)).to_string(), "Byte 7,71 is outside the memory space");
+        assert_eq!(PuzzleError::InvalidByte("3;4".to_string()).to_string(), "Invalid byte position: \"3;4\"");
+    }
+}
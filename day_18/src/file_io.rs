@@ -0,0 +1,57 @@
+use aoc_common::AppError;
+use tracing::debug;
+
+use crate::errors::PuzzleError;
+
+/// Parses the falling byte positions, one `x,y` pair per line.
+///
+/// # Arguments
+///
+/// * `content` - The puzzle input
+///
+/// # Returns
+///
+/// * `Result<Vec<(usize, usize)>, AppError>` - The positions in the order they fall, or an error
+pub fn parse_bytes(content: &str) -> Result<Vec<(usize, usize)>, AppError> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            let (x, y) = line
+                .trim()
+                .split_once(',')
+                .ok_or_else(|| PuzzleError::InvalidByte(line.to_string()))?;
+            Ok((x.trim().parse()?, y.trim().parse()?))
+        })
+        .collect()
+}
+
+/// Reads the falling byte positions from a file
+///
+/// # Arguments
+///
+/// * `path` - A string slice that holds the path to the file
+///
+/// # Returns
+///
+/// * `Result<Vec<(usize, usize)>, AppError>` - The parsed input, or an error if
+///   the file can't be read or parsed
+pub fn read_bytes(path: &str) -> Result<Vec<(usize, usize)>, AppError> {
+    let content = std::fs::read_to_string(path)?;
+    debug!("Read {} bytes", content.len());
+    parse_bytes(&content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read_bytes() -> Result<(), AppError> {
+        let bytes = read_bytes("data/inputtest")?;
+        assert_eq!(bytes.len(), 25);
+        assert_eq!(bytes[0], (5, 4));
+        assert_eq!(bytes[24], (2, 0));
+        Ok(())
+    }
+}
@@ -0,0 +1,38 @@
+//! Day 18: RAM Run
+//!
+//! Library entry points shared by the binary and the `aoc` runner.
+
+pub mod calculations;
+pub mod errors;
+pub mod file_io;
+
+use aoc_common::{AppError, Solver};
+
+use calculations::{corrupt, first_blocking_byte, shortest_path, FALLEN, SIZE};
+use file_io::parse_bytes;
+
+/// Solver for the falling bytes in memory
+pub struct Day18;
+
+impl Solver for Day18 {
+    /// The byte positions in the order they fall
+    type Parsed = Vec<(usize, usize)>;
+
+    fn parse(input: &str) -> Result<Self::Parsed, AppError> {
+        parse_bytes(input)
+    }
+
+    /// Fewest steps to the exit after the first `FALLEN` bytes have fallen
+    fn part1(bytes: &Self::Parsed) -> Result<String, AppError> {
+        let grid = corrupt(bytes, FALLEN, SIZE)?;
+        Ok(shortest_path(&grid).map_or_else(|| "unreachable".to_string(), |steps| steps.to_string()))
+    }
+
+    /// Position of the first byte that cuts off the exit
+    fn part2(bytes: &Self::Parsed) -> Result<String, AppError> {
+        Ok(match first_blocking_byte(bytes, SIZE)? {
+            Some((x, y)) => format!("{},{}", x, y),
+            None => "never blocked".to_string(),
+        })
+    }
+}
@@ -1,3 +1,87 @@
-fn main() {
-    println!("Welcome to Day 18!");
+//! Day 18: RAM Run
+//!
+//! This program finds paths across a memory space as bytes fall into it:
+//! 1. The fewest steps to the exit once the first 1024 bytes have fallen
+//! 2. The first byte that cuts the exit off entirely
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run -- path/to/input/file
+//! cargo run -- path/to/input/file --format json
+//! cargo run -- path/to/input/file --size 7 --bytes 12
+//! ```
+//!
+//! `--size` overrides the default 71×71 space and `--bytes` the default 1024
+//! fallen bytes (the puzzle example uses a 7×7 space after 12 bytes).
+use std::error::Error;
+use std::time::Instant;
+
+use aoc_common::input::resolve_input;
+use aoc_common::logging;
+use aoc_common::messages;
+use aoc_common::msg;
+use aoc_common::output::{take_format, Format, Report};
+use aoc_common::AppError;
+
+use day_18::calculations::{corrupt, first_blocking_byte, shortest_path, FALLEN, SIZE};
+use day_18::file_io::read_bytes;
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let format = take_format(&mut args)?;
+    logging::init(logging::take_verbosity(&mut args));
+    messages::init(messages::take_lang(&mut args)?.as_deref())?;
+    if format == Format::Text {
+        println!("{}", msg!("welcome", 18));
+    }
+    let mut args = args.into_iter().peekable();
+    let path = args.next_if(|arg| !arg.starts_with("--"));
+    let (mut size, mut fallen) = (SIZE, FALLEN);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => {
+                size = args.next().ok_or(AppError::ArgError("--size requires a side length"))?.parse()?;
+                if size == 0 {
+                    return Err(Box::new(AppError::ArgError("--size needs a side length of at least 1")));
+                }
+            }
+            "--bytes" => fallen = args.next().ok_or(AppError::ArgError("--bytes requires a byte count"))?.parse()?,
+            _ => {
+                return Err(Box::new(AppError::ArgError(
+                    "Usage: day_18 [<input>] [--size <side>] [--bytes <count>] [--format <text|json>] [-v|-vv]",
+                )))
+            }
+        }
+    }
+    let bytes = read_bytes(&resolve_input(path.as_deref(), 18)?)?;
+
+    let start = Instant::now();
+    let steps = shortest_path(&corrupt(&bytes, fallen, size)?);
+    let blocking = first_blocking_byte(&bytes, size)?;
+    let elapsed = start.elapsed();
+
+    match format {
+        Format::Text => {
+            match steps {
+                Some(steps) => println!("{}", msg!("day18-part1", fallen, steps)),
+                None => println!("{}", msg!("day18-part1-unreachable", fallen)),
+            }
+            match blocking {
+                Some((x, y)) => println!("{}", msg!("day18-part2", x, y)),
+                None => println!("{}", msg!("day18-part2-never-blocked")),
+            }
+        }
+        Format::Json => {
+            let report = Report {
+                day: 18,
+                part1: steps.map(|steps| steps.to_string()),
+                part2: blocking.map(|(x, y)| format!("{},{}", x, y)),
+                elapsed,
+            };
+            println!("{}", report.to_json());
+        }
+    }
+
+    Ok(())
 }
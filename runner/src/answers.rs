@@ -0,0 +1,118 @@
+//! Expected answers checked by `aoc check`.
+//!
+//! The answers file has one table per day, named after the day's crate:
+//!
+//! ```toml
+//! [day_03]
+//! part1 = 161
+//! part2 = "48"
+//! ```
+//!
+//! Answers may be written as strings or integers, and either part may be left out.
+
+use std::collections::BTreeMap;
+
+use serde::Deserialize;
+
+use crate::errors::AppError;
+
+/// An answer as written in the file
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum Answer {
+    Text(String),
+    Number(i64),
+}
+
+impl From<Answer> for String {
+    fn from(answer: Answer) -> Self {
+        match answer {
+            Answer::Text(text) => text,
+            Answer::Number(number) => number.to_string(),
+        }
+    }
+}
+
+/// One day's table as written in the file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DayTable {
+    part1: Option<Answer>,
+    part2: Option<Answer>,
+}
+
+/// The expected answers for one day
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Expected {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+impl Expected {
+    /// Returns the expected answer for `part` (1 or 2), if one is recorded
+    pub fn part(&self, part: u32) -> Option<&str> {
+        match part {
+            1 => self.part1.as_deref(),
+            2 => self.part2.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+/// Parses an answers file into expected answers keyed by day number
+pub fn parse_answers(text: &str) -> Result<BTreeMap<u32, Expected>, AppError> {
+    let tables: BTreeMap<String, DayTable> =
+        toml::from_str(text).map_err(|e| AppError::AnswersError(e.to_string()))?;
+
+    tables
+        .into_iter()
+        .map(|(name, table)| {
+            let day = name
+                .strip_prefix("day_")
+                .and_then(|number| number.parse().ok())
+                .ok_or_else(|| AppError::AnswersError(format!("table [{}] is not named like [day_03]", name)))?;
+            let expected = Expected {
+                part1: table.part1.map(String::from),
+                part2: table.part2.map(String::from),
+            };
+            Ok((day, expected))
+        })
+        .collect()
+}
+
+/// Reads and parses the answers file at `path`
+pub fn load_answers(path: &str) -> Result<BTreeMap<u32, Expected>, AppError> {
+    parse_answers(&std::fs::read_to_string(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_answers() -> Result<(), AppError> {
+        let answers = parse_answers(
+            r#"
+            [day_03]
+            part1 = 161
+            part2 = "48"
+
+            [day_17]
+            part1 = "4,6,3,5,6,3,5,2,1,0"
+            "#,
+        )?;
+        assert_eq!(answers.len(), 2);
+        assert_eq!(answers[&3].part(1), Some("161"));
+        assert_eq!(answers[&3].part(2), Some("48"));
+        assert_eq!(answers[&17].part(1), Some("4,6,3,5,6,3,5,2,1,0"));
+        assert_eq!(answers[&17].part(2), None);
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_answers_errors() {
+        assert!(matches!(parse_answers("[three]\npart1 = 1"), Err(AppError::AnswersError(_))));
+        assert!(matches!(parse_answers("[day_03]\npart3 = 1"), Err(AppError::AnswersError(_))));
+        assert!(matches!(parse_answers("[day_03"), Err(AppError::AnswersError(_))));
+    }
+}
@@ -0,0 +1,226 @@
+//! Client for adventofcode.com.
+//!
+//! Requests are authenticated with the `session` cookie of a logged-in
+//! browser, read from the `AOC_SESSION` environment variable.
+
+use std::fmt;
+
+use aoc_common::msg;
+
+use crate::errors::AppError;
+
+/// Environment variable holding the session cookie
+pub const SESSION_VAR: &str = "AOC_SESSION";
+
+const BASE_URL: &str = "https://adventofcode.com";
+
+/// Identifies the tool to the Advent of Code servers, as they ask automated clients to do
+const USER_AGENT: &str = "AdventOfCode_WIR aoc runner";
+
+/// Returns the URL of a day's personal puzzle input
+pub fn input_url(year: u32, day: u32) -> String {
+    format!("{}/{}/day/{}/input", BASE_URL, year, day)
+}
+
+/// Returns the URL of a day's puzzle statement
+pub fn puzzle_url(year: u32, day: u32) -> String {
+    format!("{}/{}/day/{}", BASE_URL, year, day)
+}
+
+/// Reads the session cookie from the environment
+pub fn session_from_env() -> Result<String, AppError> {
+    match std::env::var(SESSION_VAR) {
+        Ok(session) if !session.trim().is_empty() => Ok(session.trim().to_string()),
+        _ => Err(AppError::MissingSession),
+    }
+}
+
+/// Downloads a day's personal puzzle input
+///
+/// # Arguments
+/// * `year` - The event year
+/// * `day` - The puzzle day (1-25)
+/// * `session` - The `session` cookie value
+///
+/// # Returns
+/// The input text, or an error if the request fails or is rejected
+pub fn fetch_input(year: u32, day: u32, session: &str) -> Result<String, AppError> {
+    let response = ureq::get(&input_url(year, day))
+        .set("Cookie", &format!("session={}", session))
+        .set("User-Agent", USER_AGENT)
+        .call()?;
+    Ok(response.into_string()?)
+}
+
+/// Downloads a day's puzzle page. Without a session only part 1 is shown;
+/// with one, part 2 is included once part 1 is solved.
+///
+/// # Arguments
+/// * `year` - The event year
+/// * `day` - The puzzle day (1-25)
+/// * `session` - The `session` cookie value, if there is one
+///
+/// # Returns
+/// The page's HTML, or an error if the request fails or is rejected
+pub fn fetch_puzzle(year: u32, day: u32, session: Option<&str>) -> Result<String, AppError> {
+    let mut request = ureq::get(&puzzle_url(year, day)).set("User-Agent", USER_AGENT);
+    if let Some(session) = session {
+        request = request.set("Cookie", &format!("session={}", session));
+    }
+    Ok(request.call()?.into_string()?)
+}
+
+/// Converts a puzzle page to plain text wrapped at `width` columns, keeping
+/// only the puzzle descriptions (the page's `<article>` elements) if it has any
+pub fn puzzle_text(html: &str, width: usize) -> Result<String, AppError> {
+    let mut articles = String::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<article") {
+        let Some(end) = rest[start..].find("</article>") else { break };
+        let end = start + end + "</article>".len();
+        articles.push_str(&rest[start..end]);
+        rest = &rest[end..];
+    }
+    let body = if articles.is_empty() { html } else { &articles };
+    html2text::config::plain()
+        .string_from_read(body.as_bytes(), width)
+        .map_err(|e| AppError::PuzzleError(e.to_string()))
+}
+
+/// How adventofcode.com judged a submitted answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    /// The answer is right
+    Correct,
+    /// The answer is wrong and too high
+    TooHigh,
+    /// The answer is wrong and too low
+    TooLow,
+    /// The answer is wrong, with no hint
+    Incorrect,
+    /// An answer was submitted too recently; holds the remaining wait, e.g. `4m 32s`
+    Wait(String),
+    /// The part has already been solved, or isn't unlocked yet
+    WrongLevel,
+    /// The response didn't match any known message
+    Unrecognized,
+}
+
+impl fmt::Display for SubmitOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::Correct => msg!("submit-correct"),
+            Self::TooHigh => msg!("submit-too-high"),
+            Self::TooLow => msg!("submit-too-low"),
+            Self::Incorrect => msg!("submit-incorrect"),
+            Self::Wait(wait) => msg!("submit-wait", wait),
+            Self::WrongLevel => msg!("submit-wrong-level"),
+            Self::Unrecognized => msg!("submit-unrecognized"),
+        };
+        f.write_str(&message)
+    }
+}
+
+/// Reads the outcome out of the HTML page returned for a submitted answer
+pub fn parse_submit_response(html: &str) -> SubmitOutcome {
+    if html.contains("That's the right answer") {
+        SubmitOutcome::Correct
+    } else if html.contains("That's not the right answer") {
+        if html.contains("too high") {
+            SubmitOutcome::TooHigh
+        } else if html.contains("too low") {
+            SubmitOutcome::TooLow
+        } else {
+            SubmitOutcome::Incorrect
+        }
+    } else if html.contains("You gave an answer too recently") {
+        let wait = html
+            .split_once(" left to wait")
+            .and_then(|(before, _)| before.rsplit_once("have "))
+            .map_or("a while", |(_, wait)| wait);
+        SubmitOutcome::Wait(wait.to_string())
+    } else if html.contains("You don't seem to be solving the right level") {
+        SubmitOutcome::WrongLevel
+    } else {
+        SubmitOutcome::Unrecognized
+    }
+}
+
+/// Submits an answer for one part of a day's puzzle
+///
+/// # Arguments
+/// * `year` - The event year
+/// * `day` - The puzzle day (1-25)
+/// * `part` - The part (1 or 2)
+/// * `answer` - The answer to submit
+/// * `session` - The `session` cookie value
+///
+/// # Returns
+/// How the answer was judged, or an error if the request fails or is rejected
+pub fn submit_answer(year: u32, day: u32, part: u32, answer: &str, session: &str) -> Result<SubmitOutcome, AppError> {
+    let response = ureq::post(&format!("{}/{}/day/{}/answer", BASE_URL, year, day))
+        .set("Cookie", &format!("session={}", session))
+        .set("User-Agent", USER_AGENT)
+        .send_form(&[("level", &part.to_string()), ("answer", answer)])?;
+    Ok(parse_submit_response(&response.into_string()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_submit_response() {
+        let page = |message: &str| format!("<main><article><p>{}</p></article></main>", message);
+        assert_eq!(
+            parse_submit_response(&page("That's the right answer! You are one gold star closer.")),
+            SubmitOutcome::Correct
+        );
+        assert_eq!(
+            parse_submit_response(&page("That's not the right answer; your answer is too high.")),
+            SubmitOutcome::TooHigh
+        );
+        assert_eq!(
+            parse_submit_response(&page("That's not the right answer; your answer is too low.")),
+            SubmitOutcome::TooLow
+        );
+        assert_eq!(
+            parse_submit_response(&page("That's not the right answer. If you're stuck, ...")),
+            SubmitOutcome::Incorrect
+        );
+        assert_eq!(
+            parse_submit_response(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 4m 32s left to wait."
+            )),
+            SubmitOutcome::Wait("4m 32s".to_string())
+        );
+        assert_eq!(
+            parse_submit_response(&page("You don't seem to be solving the right level.  Did you already complete it?")),
+            SubmitOutcome::WrongLevel
+        );
+        assert_eq!(parse_submit_response("<html></html>"), SubmitOutcome::Unrecognized);
+    }
+
+    #[test]
+    fn test_puzzle_text() -> Result<(), AppError> {
+        let html = "<html><header>Advent of Code</header><main>\
+            <article class=\"day-desc\"><h2>--- Day 1: Historian Hysteria ---</h2><p>Pair up the <em>smallest</em> numbers.</p></article>\
+            <p>Your puzzle answer was <code>11</code>.</p>\
+            <article class=\"day-desc\"><h2 id=\"part2\">--- Part Two ---</h2><p>Count the repeats.</p></article>\
+            </main></html>";
+        let text = puzzle_text(html, 80)?;
+        assert!(text.contains("--- Day 1: Historian Hysteria ---"));
+        assert!(text.contains("Pair up the *smallest* numbers."));
+        assert!(text.contains("--- Part Two ---"));
+        assert!(!text.contains("Advent of Code"));
+        assert!(!text.contains("Your puzzle answer"));
+        assert!(puzzle_text("<p>No articles here</p>", 80)?.contains("No articles here"));
+        Ok(())
+    }
+
+    #[test]
+    fn test_input_url() {
+        assert_eq!(input_url(2024, 5), "https://adventofcode.com/2024/day/5/input");
+        assert_eq!(puzzle_url(2024, 5), "https://adventofcode.com/2024/day/5");
+    }
+}
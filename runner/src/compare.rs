@@ -0,0 +1,74 @@
+//! Side-by-side answers printed by `aoc compare`.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use aoc_common::msg;
+
+/// One input's results in the comparison
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    /// The input path, as given on the command line
+    pub input: String,
+    /// What to show for each part: the answer, or a short reason it's missing
+    pub cells: [String; 2],
+    /// Wall-clock time for parsing and both parts; `None` if the input couldn't be solved
+    pub elapsed: Option<Duration>,
+}
+
+/// Formats the entries as an aligned table, one row per input
+pub fn format_comparison(entries: &[Entry]) -> String {
+    let headers = [msg!("table-input"), msg!("table-part1"), msg!("table-part2"), msg!("table-time")];
+    let times: Vec<String> = entries
+        .iter()
+        .map(|entry| entry.elapsed.map_or("-".to_string(), |elapsed| format!("{:.1?}", elapsed)))
+        .collect();
+
+    let width = |header: &str, cells: &mut dyn Iterator<Item = usize>| cells.fold(header.chars().count(), usize::max);
+    let input_width = width(&headers[0], &mut entries.iter().map(|entry| entry.input.chars().count()));
+    let part1_width = width(&headers[1], &mut entries.iter().map(|entry| entry.cells[0].chars().count()));
+    let part2_width = width(&headers[2], &mut entries.iter().map(|entry| entry.cells[1].chars().count()));
+    let time_width = width(&headers[3], &mut times.iter().map(|time| time.chars().count()));
+
+    let mut table = String::new();
+    let _ = writeln!(
+        table,
+        "{:<input_width$}  {:<part1_width$}  {:<part2_width$}  {:>time_width$}",
+        headers[0], headers[1], headers[2], headers[3]
+    );
+    for (entry, time) in entries.iter().zip(&times) {
+        let _ = writeln!(
+            table,
+            "{:<input_width$}  {:<part1_width$}  {:<part2_width$}  {:>time_width$}",
+            entry.input, entry.cells[0], entry.cells[1], time
+        );
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_comparison() {
+        let entries = [
+            Entry {
+                input: "alice.txt".to_string(),
+                cells: ["143".to_string(), "123".to_string()],
+                elapsed: Some(Duration::from_micros(250)),
+            },
+            Entry {
+                input: "bob.txt".to_string(),
+                cells: ["failed".to_string(), "failed".to_string()],
+                elapsed: None,
+            },
+        ];
+        let expected = "\
+Input      Part 1  Part 2     Time
+alice.txt  143     123     250.0µs
+bob.txt    failed  failed        -
+";
+        assert_eq!(format_comparison(&entries), expected);
+    }
+}
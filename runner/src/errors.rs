@@ -0,0 +1,76 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+
+use aoc_common::msg;
+
+/// Custom error types for the application
+#[derive(Debug)]
+pub enum AppError {
+    /// Represents I/O operation failures
+    IoError(io::Error),
+    /// Represents missing or invalid command line arguments
+    ArgError(&'static str),
+    /// Represents errors in parsing string to integers
+    ParseError(std::num::ParseIntError),
+    /// Represents a day that has no solver registered
+    UnknownDay(u32),
+    /// Represents a part other than 1 or 2
+    InvalidPart(u32),
+    /// Represents a missing `AOC_SESSION` environment variable
+    MissingSession,
+    /// Represents a request that adventofcode.com answered with an error status
+    HttpStatus(u16),
+    /// Represents a request that couldn't reach adventofcode.com
+    HttpError(String),
+    /// Represents a malformed answers file
+    AnswersError(String),
+    /// Represents a puzzle page that couldn't be turned into text
+    PuzzleError(String),
+    /// Represents `aoc check` finding the given number of wrong or failed answers
+    CheckFailed(usize),
+}
+
+impl From<io::Error> for AppError {
+    fn from(error: io::Error) -> Self {
+        Self::IoError(error)
+    }
+}
+
+impl From<ureq::Error> for AppError {
+    fn from(error: ureq::Error) -> Self {
+        match error {
+            ureq::Error::Status(code, _) => Self::HttpStatus(code),
+            ureq::Error::Transport(transport) => Self::HttpError(transport.to_string()),
+        }
+    }
+}
+
+impl From<std::num::ParseIntError> for AppError {
+    fn from(error: std::num::ParseIntError) -> Self {
+        Self::ParseError(error)
+    }
+}
+
+impl Error for AppError {}
+
+impl fmt::Display for AppError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let message = match self {
+            Self::IoError(e) => msg!("error-io", e),
+            Self::ArgError(text) => msg!("error-arg", text),
+            Self::ParseError(e) => msg!("error-parse", e),
+            Self::UnknownDay(day) => msg!("error-unknown-day", day),
+            Self::InvalidPart(part) => msg!("error-invalid-part", part),
+            Self::MissingSession => msg!("error-missing-session"),
+            Self::HttpStatus(400) => msg!("error-http-400"),
+            Self::HttpStatus(404) => msg!("error-http-404"),
+            Self::HttpStatus(code) => msg!("error-http-status", code),
+            Self::HttpError(e) => msg!("error-http", e),
+            Self::AnswersError(e) => msg!("error-answers", e),
+            Self::PuzzleError(e) => msg!("error-puzzle", e),
+            Self::CheckFailed(count) => msg!("error-check-failed", count),
+        };
+        f.write_str(&message)
+    }
+}
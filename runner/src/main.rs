@@ -0,0 +1,741 @@
+//! Advent of Code runner
+//!
+//! Runs any registered day's solvers from the workspace root, so there is no
+//! need to `cd` into each `day_NN` directory, and downloads puzzle inputs.
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo run -p runner -- run --day 6
+//! cargo run -p runner -- run --day 6 --part 1 --input day_06/data/inputtest
+//! cargo run -p runner -- run --day 5 --explain
+//! cargo run --release -p runner -- run --all [--format json] [--timeout 10]
+//! AOC_SESSION=<cookie> cargo run -p runner -- fetch --day 6 [--force]
+//! AOC_INPUT_KEY=<passphrase> cargo run -p runner -- encrypt --day 6
+//! AOC_SESSION=<cookie> cargo run -p runner -- submit --day 6 --part 1
+//! cargo run -p runner -- puzzle --day 6 [--force] [--open]
+//! cargo run --release -p runner -- check [--answers answers.toml]
+//! cargo run --release -p runner -- bench --day 6 [--iterations 20] [--warmup 3]
+//! cargo run --release -p runner -- compare --day 5 alice.txt bob.txt
+//! aoc day 6 [<input>] [--part 1]
+//! aoc --stdio-protocol < requests.jsonl
+//! cargo run --release -p runner -- tui [--answers answers.toml]
+//! day_06 [<input>] [--part 1]        # a link to aoc named after the day
+//! ```
+//!
+//! `run` runs both parts unless `--part` is given. Without `--input` it reads
+//! the cached download, falling back to the day's `day_NN/data/input.txt` or
+//! `day_NN/data/input`. `run --all` runs every registered day that has an
+//! input and prints a table of answers and wall-clock times. With
+//! `--format json`, `run` prints one JSON object per day instead, such as
+//! `{"day":4,"part1":18,"part2":9,"elapsed_ms":1.2}`, and reports failed
+//! parts on stderr.
+//!
+//! `--timeout <seconds>` gives each day of `run --all` that long. A day still
+//! running by then is shown as TIMEOUT and the runner moves on. The day is
+//! cancelled through a `CancelToken`, which solvers check between parsing and
+//! each part, so a part that has already started finishes in the background
+//! unless the runner exits first.
+//!
+//! `--explain` makes `run` follow each answer with the day's reasoning, such
+//! as which reports passed or where the guard looped. Days that have nothing
+//! to explain print no extra lines. With `--format json` the explanation goes
+//! to stderr.
+//!
+//! `fetch` downloads your personal input to the cache at
+//! `data/{year}/{day}/input.txt`, using the session cookie of a logged-in
+//! adventofcode.com browser from `AOC_SESSION`. A cached input is reused
+//! unless `--force` is given.
+//!
+//! `encrypt` encrypts a day's input (the default input, or `--input`) with
+//! the passphrase in `AOC_INPUT_KEY` and writes it to `inputs/{year}/{day}.age`,
+//! which can be committed. Any day without a cached input decrypts it into the
+//! cache when `AOC_INPUT_KEY` is set.
+//!
+//! `puzzle` prints a day's puzzle statement as plain text. The page is cached
+//! at `data/{year}/{day}/puzzle.html` and downloaded again with `--force`,
+//! such as after solving part 1 to see part 2, which only shows up when
+//! `AOC_SESSION` is set. `--open` opens the puzzle in the default browser
+//! instead.
+//!
+//! Every subcommand accepts `-v` or `-vv` to log the days' diagnostics to stderr,
+//! and `--lang <code>` to print messages in another language (see
+//! `aoc_common::messages`).
+//!
+//! `submit` computes one part's answer, the same way `run` does, posts it to
+//! adventofcode.com and prints the verdict.
+//!
+//! `check` runs every day listed in the answers file (`answers.toml` by
+//! default) and compares each part's answer with the expected one, exiting
+//! with a non-zero status if any answer is wrong or fails.
+//!
+//! `bench` solves a day repeatedly, after some untimed warmup runs, and prints
+//! the min, median, max and standard deviation of each part's time. Days that
+//! parse once for both parts report parsing as its own stage.
+//!
+//! `--stdio-protocol` keeps `aoc` running as a server for editors and other
+//! tools: it reads one JSON request per line on stdin, such as
+//! `{"day":2,"part":1,"input":"..."}`, and writes one JSON response per line
+//! to stdout. See the `protocol` module for the format.
+//!
+//! `tui` opens a terminal dashboard listing every day, whether it has an
+//! input and which answers the answers file records. Selecting a day and
+//! pressing `1`, `2` or Enter runs it in the background, with results and
+//! timings appearing in a log pane as each part finishes.
+//!
+//! `compare` runs one day's solver on each of the given inputs and prints a
+//! table with a row per input: both answers and the time taken.
+//!
+//! Since every solver is linked into `aoc`, it also works as a busybox-style
+//! multi-call binary: `aoc day 6 <input>` is `aoc run --day 6 --input <input>`,
+//! and so is running `aoc` through a link or copy named `day_06` (or `day06`,
+//! `day6`). `cargo build --profile dist -p runner` builds it stripped and with
+//! link-time optimisation, for copying to machines without the workspace; add
+//! `--target x86_64-unknown-linux-musl` for a statically linked build.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::path::Path;
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc_common::cancel::CancelToken;
+use aoc_common::input;
+use aoc_common::logging;
+use aoc_common::messages;
+use aoc_common::msg;
+use aoc_common::output::{Format, Report};
+
+mod answers;
+mod client;
+mod compare;
+mod errors;
+mod protocol;
+mod registry;
+mod stats;
+mod summary;
+mod tui;
+
+use errors::AppError;
+use registry::{find_day, find_day_by_program, Day, Solution, DAYS};
+use compare::{format_comparison, Entry};
+use stats::{format_stats_table, stats};
+use summary::{format_table, Row};
+
+const USAGE: &str = "Usage: aoc run --day <n> [--part <1|2>] [--input <path>] [--format <text|json>] [--explain]\n       aoc run --all [--format <text|json>] [--timeout <seconds>]\n       aoc fetch --day <n> [--force]\n       aoc encrypt --day <n> [--input <path>]\n       aoc submit --day <n> --part <1|2> [--input <path>]\n       aoc puzzle --day <n> [--force] [--open]\n       aoc check [--answers <path>]\n       aoc bench --day <n> [--part <1|2>] [--input <path>] [--iterations <n>] [--warmup <n>]\n       aoc compare --day <n> <input>...\n       aoc day <n> [<input>] [run options]\n       aoc tui [--answers <path>]\n       aoc --stdio-protocol";
+
+/// The answers file `check` reads when `--answers` isn't given
+const DEFAULT_ANSWERS: &str = "answers.toml";
+
+/// Timed runs `bench` does when `--iterations` isn't given
+const DEFAULT_ITERATIONS: u32 = 10;
+/// Untimed runs `bench` does first when `--warmup` isn't given
+const DEFAULT_WARMUP: u32 = 1;
+
+/// Options shared by the subcommands
+#[derive(Debug, Default)]
+struct Options {
+    day: Option<u32>,
+    part: Option<u32>,
+    input: Option<String>,
+    force: bool,
+    open: bool,
+    all: bool,
+    answers: Option<String>,
+    iterations: Option<u32>,
+    warmup: Option<u32>,
+    format: Format,
+    explain: bool,
+    /// How long each day of `run --all` may take
+    timeout: Option<Duration>,
+    /// Input paths given without a flag, for `compare`
+    inputs: Vec<String>,
+}
+
+/// Parses the options following the subcommand
+fn parse_options(mut args: impl Iterator<Item = String>) -> Result<Options, AppError> {
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => {
+                let value = args.next().ok_or(AppError::ArgError("--day requires a number"))?;
+                options.day = Some(value.parse()?);
+            }
+            "--part" => {
+                let value = args.next().ok_or(AppError::ArgError("--part requires 1 or 2"))?;
+                options.part = Some(value.parse()?);
+            }
+            "--input" => {
+                options.input = Some(args.next().ok_or(AppError::ArgError("--input requires a path"))?)
+            }
+            "--answers" => {
+                options.answers = Some(args.next().ok_or(AppError::ArgError("--answers requires a path"))?)
+            }
+            "--iterations" => {
+                let value = args.next().ok_or(AppError::ArgError("--iterations requires a number"))?;
+                options.iterations = Some(value.parse()?);
+            }
+            "--warmup" => {
+                let value = args.next().ok_or(AppError::ArgError("--warmup requires a number"))?;
+                options.warmup = Some(value.parse()?);
+            }
+            "--format" => {
+                let value = args.next().ok_or(AppError::ArgError("--format requires text or json"))?;
+                options.format = value.parse().map_err(|_| AppError::ArgError("--format must be text or json"))?;
+            }
+            "--timeout" => {
+                let value = args.next().ok_or(AppError::ArgError("--timeout requires a number of seconds"))?;
+                let timeout = value
+                    .parse()
+                    .ok()
+                    .and_then(|seconds| Duration::try_from_secs_f64(seconds).ok())
+                    .filter(|timeout| !timeout.is_zero())
+                    .ok_or(AppError::ArgError("--timeout must be a positive number of seconds"))?;
+                options.timeout = Some(timeout);
+            }
+            "--force" => options.force = true,
+            "--open" => options.open = true,
+            "--all" => options.all = true,
+            "--explain" => options.explain = true,
+            _ if !arg.starts_with("--") => options.inputs.push(arg),
+            _ => return Err(AppError::ArgError(USAGE)),
+        }
+    }
+    Ok(options)
+}
+
+/// Returns the directory of a day's data files
+fn data_dir(day: u32) -> String {
+    format!("day_{:02}/data", day)
+}
+
+/// Picks the input file used when `--input` isn't given: the cached download
+/// (or decrypted input) if there is one, otherwise whichever input file the
+/// day's data directory has
+///
+/// # Returns
+/// The path, or an error if the day's encrypted input can't be decrypted
+fn default_input(day: u32) -> Result<String, aoc_common::AppError> {
+    if let Some(cached) = input::cached_input(day)? {
+        return Ok(cached.to_string_lossy().into_owned());
+    }
+    let txt = format!("{}/input.txt", data_dir(day));
+    let bare = format!("{}/input", data_dir(day));
+    if !Path::new(&txt).exists() && Path::new(&bare).exists() {
+        Ok(bare)
+    } else {
+        Ok(txt)
+    }
+}
+
+/// Builds the JSON report for a day's solution, printing failed parts to stderr
+fn json_report(day: u32, parts: &[u32], solution: Solution) -> Report {
+    let elapsed = solution.parse + solution.parts.iter().sum::<Duration>();
+    let mut report = Report { day, part1: None, part2: None, elapsed };
+    for (&part, answer) in parts.iter().zip(solution.answers) {
+        match answer {
+            Ok(answer) if part == 1 => report.part1 = Some(answer),
+            Ok(answer) => report.part2 = Some(answer),
+            Err(e) => eprintln!("{}", msg!("run-part-failed", day, part, e)),
+        }
+    }
+    report
+}
+
+/// Runs `solve` on its own thread and waits at most `timeout` for it, or
+/// runs it directly if there is no timeout
+///
+/// # Returns
+/// What `solve` returned, or `None` if it timed out. The token given to
+/// `solve` is then cancelled and its thread is left to stop on its own.
+fn with_timeout<T: Send + 'static>(
+    timeout: Option<Duration>,
+    solve: impl FnOnce(&CancelToken) -> T + Send + 'static,
+) -> Option<T> {
+    let cancel = CancelToken::new();
+    let Some(timeout) = timeout else {
+        return Some(solve(&cancel));
+    };
+
+    let (sender, receiver) = mpsc::channel();
+    let token = cancel.clone();
+    let handle = thread::spawn(move || {
+        let _ = sender.send(solve(&token));
+    });
+    match receiver.recv_timeout(timeout) {
+        Ok(result) => Some(result),
+        Err(RecvTimeoutError::Timeout) => {
+            cancel.cancel();
+            None
+        }
+        // The solver panicked before sending; report it as if it had run here
+        Err(RecvTimeoutError::Disconnected) => match handle.join() {
+            Err(panic) => std::panic::resume_unwind(panic),
+            Ok(()) => None,
+        },
+    }
+}
+
+/// Runs every registered day on its default input and prints one JSON object per day
+fn run_all_json(timeout: Option<Duration>) {
+    for day in DAYS {
+        let input = match default_input(day.number) {
+            Ok(input) => input,
+            Err(e) => {
+                eprintln!("{}", msg!("run-day-failed", day.number, e));
+                continue;
+            }
+        };
+        if !Path::new(&input).exists() {
+            continue;
+        }
+        let report = with_timeout(timeout, move |cancel| match (day.solve_with)(&input, &[1, 2], cancel) {
+            // Timed out already, so nobody is waiting for the report or its failed parts
+            Ok(_) if cancel.is_cancelled() => Err(aoc_common::AppError::Cancelled.to_string()),
+            Ok(solution) => Ok(json_report(day.number, &[1, 2], solution).to_json()),
+            Err(e) => Err(e.to_string()),
+        });
+        match report {
+            Some(Ok(json)) => println!("{}", json),
+            Some(Err(e)) => eprintln!("{}", msg!("run-day-failed", day.number, e)),
+            None => eprintln!("{}", msg!("run-timed-out", day.number, format!("{:.1?}", timeout.unwrap_or_default()))),
+        }
+    }
+}
+
+/// Solves both parts of a day for `run --all`
+///
+/// # Returns
+/// What to show for each part in the summary, and a message for each failure
+fn solve_for_summary(day: &Day, input: &str, cancel: &CancelToken) -> ([String; 2], Vec<String>) {
+    let mut cells = [msg!("cell-failed"), msg!("cell-failed")];
+    let mut failures = Vec::new();
+    match (day.solve_with)(input, &[1, 2], cancel) {
+        Ok(solution) => {
+            for (part, answer) in (1..).zip(solution.answers) {
+                match answer {
+                    Ok(answer) => cells[part - 1] = answer,
+                    Err(e) => failures.push(msg!("run-answer", day.number, part, e)),
+                }
+            }
+        }
+        Err(e) => failures.push(msg!("run-day-failed", day.number, e)),
+    }
+    (cells, failures)
+}
+
+/// Runs every registered day on its default input and prints a timing table
+fn run_all(timeout: Option<Duration>) {
+    let mut rows = Vec::new();
+    let mut failures = Vec::new();
+    for day in DAYS {
+        let input = match default_input(day.number) {
+            Ok(input) => input,
+            Err(e) => {
+                failures.push(msg!("run-day-failed", day.number, e));
+                rows.push(Row { day: day.number, cells: [msg!("cell-failed"), msg!("cell-failed")], elapsed: None });
+                continue;
+            }
+        };
+        if !Path::new(&input).exists() {
+            rows.push(Row {
+                day: day.number,
+                cells: [msg!("cell-no-input"), msg!("cell-no-input")],
+                elapsed: None,
+            });
+            continue;
+        }
+
+        let start = Instant::now();
+        let solved = with_timeout(timeout, move |cancel| solve_for_summary(day, &input, cancel));
+        let elapsed = start.elapsed();
+
+        let cells = match solved {
+            Some((cells, day_failures)) => {
+                failures.extend(day_failures);
+                cells
+            }
+            None => [msg!("cell-timeout"), msg!("cell-timeout")],
+        };
+        rows.push(Row { day: day.number, cells, elapsed: Some(elapsed) });
+    }
+
+    print!("{}", format_table(&rows));
+    for failure in failures {
+        println!("{}", failure);
+    }
+}
+
+/// Returns the parts selected by `--part`, or both if it wasn't given
+fn selected_parts(part: Option<u32>) -> Result<Vec<u32>, AppError> {
+    match part {
+        None => Ok(vec![1, 2]),
+        Some(part @ (1 | 2)) => Ok(vec![part]),
+        Some(part) => Err(AppError::InvalidPart(part)),
+    }
+}
+
+/// Runs the requested parts of a day and prints their answers
+fn run(options: Options) -> Result<(), Box<dyn Error>> {
+    if options.all {
+        if options.explain {
+            return Err(Box::new(AppError::ArgError("--explain needs a single --day")));
+        }
+        match options.format {
+            Format::Text => run_all(options.timeout),
+            Format::Json => run_all_json(options.timeout),
+        }
+        return Ok(());
+    }
+    if options.timeout.is_some() {
+        return Err(Box::new(AppError::ArgError("--timeout needs --all")));
+    }
+    let day_number = options.day.ok_or(AppError::ArgError(USAGE))?;
+    let day = find_day(day_number).ok_or(AppError::UnknownDay(day_number))?;
+    let parts = selected_parts(options.part)?;
+    let input = match options.input {
+        Some(input) => input,
+        None => default_input(day.number)?,
+    };
+
+    let solution = (day.solve)(&input, &parts)?;
+    if options.format == Format::Json {
+        println!("{}", json_report(day.number, &parts, solution).to_json());
+        if options.explain {
+            for &part in &parts {
+                for line in (day.explain)(&input, part)? {
+                    eprintln!("{}", line);
+                }
+            }
+        }
+        return Ok(());
+    }
+    for (&part, answer) in parts.iter().zip(solution.answers) {
+        match answer {
+            Ok(answer) => println!("{}", msg!("run-answer", day.number, part, answer)),
+            Err(e) => println!("{}", msg!("run-part-failed", day.number, part, e)),
+        }
+        if options.explain {
+            for line in (day.explain)(&input, part)? {
+                println!("  {}", line);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Downloads a day's personal input into the input cache, unless it's already there
+fn fetch(options: Options) -> Result<(), Box<dyn Error>> {
+    let day = match options.day {
+        Some(day @ 1..=25) => day,
+        Some(_) => return Err(Box::new(AppError::ArgError("--day must be between 1 and 25"))),
+        None => return Err(Box::new(AppError::ArgError(USAGE))),
+    };
+    let path = input::cache_path(input::YEAR, day);
+    if path.exists() && !options.force {
+        println!("{}", msg!("fetch-cached", path.display()));
+        return Ok(());
+    }
+    let session = client::session_from_env()?;
+
+    let text = client::fetch_input(input::YEAR, day, &session)?;
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(AppError::from)?;
+    }
+    std::fs::write(&path, &text).map_err(AppError::from)?;
+    println!("{}", msg!("fetch-wrote", text.len(), path.display()));
+
+    Ok(())
+}
+
+/// Encrypts a day's input with the passphrase in `AOC_INPUT_KEY`, so it can
+/// be committed and decrypted into the input cache on another checkout
+fn encrypt(options: Options) -> Result<(), Box<dyn Error>> {
+    let day_number = options.day.ok_or(AppError::ArgError(USAGE))?;
+    let day = find_day(day_number).ok_or(AppError::UnknownDay(day_number))?;
+    let passphrase = std::env::var(input::KEY_VAR)
+        .ok()
+        .filter(|key| !key.is_empty())
+        .ok_or(AppError::ArgError("Set AOC_INPUT_KEY to the passphrase to encrypt with"))?;
+    let source = match options.input {
+        Some(input) => input,
+        None => default_input(day.number)?,
+    };
+
+    let plaintext = std::fs::read(&source).map_err(AppError::from)?;
+    let path = input::encrypted_path(input::YEAR, day.number);
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).map_err(AppError::from)?;
+    }
+    std::fs::write(&path, input::encrypt_input(&plaintext, &passphrase)?).map_err(AppError::from)?;
+    println!("{}", msg!("encrypt-wrote", source, path.display()));
+    Ok(())
+}
+
+/// Opens the terminal dashboard, marking the answers recorded in the answers
+/// file if there is one
+fn tui(options: Options) -> Result<(), Box<dyn Error>> {
+    let path = options.answers.as_deref().unwrap_or(DEFAULT_ANSWERS);
+    let expected = if Path::new(path).exists() {
+        answers::load_answers(path)?
+    } else {
+        BTreeMap::new()
+    };
+    let inputs = DAYS.iter().map(|day| default_input(day.number)).collect::<Result<_, _>>()?;
+    Ok(tui::run(tui::App::new(DAYS, expected, inputs))?)
+}
+
+/// Width puzzle statements are wrapped to
+const PUZZLE_WIDTH: usize = 80;
+
+/// Prints a day's puzzle statement, downloading it unless it's cached, or
+/// opens it in the browser
+fn puzzle(options: Options) -> Result<(), Box<dyn Error>> {
+    let day = match options.day {
+        Some(day @ 1..=25) => day,
+        Some(_) => return Err(Box::new(AppError::ArgError("--day must be between 1 and 25"))),
+        None => return Err(Box::new(AppError::ArgError(USAGE))),
+    };
+    if options.open {
+        webbrowser::open(&client::puzzle_url(input::YEAR, day)).map_err(AppError::from)?;
+        return Ok(());
+    }
+
+    let path = input::puzzle_cache_path(input::YEAR, day);
+    let html = if path.exists() && !options.force {
+        std::fs::read_to_string(&path).map_err(AppError::from)?
+    } else {
+        let session = client::session_from_env().ok();
+        let html = client::fetch_puzzle(input::YEAR, day, session.as_deref())?;
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir).map_err(AppError::from)?;
+        }
+        std::fs::write(&path, &html).map_err(AppError::from)?;
+        html
+    };
+    print!("{}", client::puzzle_text(&html, PUZZLE_WIDTH)?);
+
+    Ok(())
+}
+
+/// Computes one part's answer and submits it to adventofcode.com
+fn submit(options: Options) -> Result<(), Box<dyn Error>> {
+    let day_number = options.day.ok_or(AppError::ArgError(USAGE))?;
+    let part = match options.part {
+        Some(part @ (1 | 2)) => part,
+        Some(part) => return Err(Box::new(AppError::InvalidPart(part))),
+        None => return Err(Box::new(AppError::ArgError("submit requires --part"))),
+    };
+    let day = find_day(day_number).ok_or(AppError::UnknownDay(day_number))?;
+    let session = client::session_from_env()?;
+    let input = match options.input {
+        Some(input) => input,
+        None => default_input(day.number)?,
+    };
+
+    let answer = (day.solve)(&input, &[part])?
+        .answers
+        .pop()
+        .ok_or(AppError::ArgError("no answer was computed"))??;
+    println!("{}", msg!("submit-answer", day.number, part, answer));
+    let outcome = client::submit_answer(input::YEAR, day.number, part, &answer, &session)?;
+    println!("{}", outcome);
+
+    Ok(())
+}
+
+/// Runs every day in the answers file and compares its answers with the expected ones
+fn check(options: Options) -> Result<(), Box<dyn Error>> {
+    let path = options.answers.as_deref().unwrap_or(DEFAULT_ANSWERS);
+    let expected = answers::load_answers(path)?;
+    let mut failures = 0;
+
+    for (&day_number, expected) in &expected {
+        let day = find_day(day_number).ok_or(AppError::UnknownDay(day_number))?;
+        let parts: Vec<u32> = [1, 2].into_iter().filter(|&part| expected.part(part).is_some()).collect();
+        let input = default_input(day.number)?;
+        if !Path::new(&input).exists() {
+            println!("{}", msg!("check-skipped", day.number));
+            continue;
+        }
+
+        let answers = match (day.solve)(&input, &parts) {
+            Ok(solution) => solution.answers,
+            Err(e) => {
+                println!("{}", msg!("check-day-failed", day.number, e));
+                failures += parts.len();
+                continue;
+            }
+        };
+        for (&part, answer) in parts.iter().zip(answers) {
+            let wanted = expected.part(part).unwrap_or_default();
+            match answer {
+                Ok(answer) if answer == wanted => println!("{}", msg!("check-ok", day.number, part, answer)),
+                Ok(answer) => {
+                    println!("{}", msg!("check-mismatch", day.number, part, answer, wanted));
+                    failures += 1;
+                }
+                Err(e) => {
+                    println!("{}", msg!("check-part-failed", day.number, part, e));
+                    failures += 1;
+                }
+            }
+        }
+    }
+
+    if failures > 0 {
+        return Err(Box::new(AppError::CheckFailed(failures)));
+    }
+    Ok(())
+}
+
+/// Solves a day repeatedly and prints timing statistics for each stage
+fn bench(options: Options) -> Result<(), Box<dyn Error>> {
+    let day_number = options.day.ok_or(AppError::ArgError(USAGE))?;
+    let day = find_day(day_number).ok_or(AppError::UnknownDay(day_number))?;
+    let parts = selected_parts(options.part)?;
+    let input = match options.input {
+        Some(input) => input,
+        None => default_input(day.number)?,
+    };
+    let iterations = options.iterations.unwrap_or(DEFAULT_ITERATIONS);
+    if iterations == 0 {
+        return Err(Box::new(AppError::ArgError("--iterations must be at least 1")));
+    }
+
+    for _ in 0..options.warmup.unwrap_or(DEFAULT_WARMUP) {
+        (day.solve)(&input, &parts)?;
+    }
+
+    let mut parse_times = Vec::new();
+    let mut part_times = vec![Vec::new(); parts.len()];
+    let mut failures = vec![None; parts.len()];
+    for _ in 0..iterations {
+        let solution = (day.solve)(&input, &parts)?;
+        parse_times.push(solution.parse);
+        for (i, (answer, elapsed)) in solution.answers.into_iter().zip(solution.parts).enumerate() {
+            // A failing part is only timed up to its error, so it isn't reported
+            match answer {
+                Ok(_) => part_times[i].push(elapsed),
+                Err(e) => failures[i] = Some(e.to_string()),
+            }
+        }
+    }
+
+    let mut rows = Vec::new();
+    rows.extend(stats(&parse_times).map(|stats| (msg!("stage-parse"), stats)));
+    for (part, (times, failure)) in parts.iter().zip(part_times.iter().zip(&failures)) {
+        if failure.is_none() {
+            rows.extend(stats(times).map(|stats| (msg!("stage-part", part), stats)));
+        }
+    }
+    println!("{}", msg!("bench-header", day.number, iterations));
+    print!("{}", format_stats_table(&rows));
+    for (part, failure) in parts.iter().zip(failures) {
+        if let Some(e) = failure {
+            println!("{}", msg!("run-part-failed", day.number, part, e));
+        }
+    }
+
+    Ok(())
+}
+
+/// Solves a day on each of several inputs and prints the answers side by side
+fn compare(options: Options) -> Result<(), Box<dyn Error>> {
+    let day_number = options.day.ok_or(AppError::ArgError(USAGE))?;
+    let day = find_day(day_number).ok_or(AppError::UnknownDay(day_number))?;
+    if options.inputs.is_empty() {
+        return Err(Box::new(AppError::ArgError("compare needs at least one input path")));
+    }
+
+    let mut entries = Vec::new();
+    let mut failures = Vec::new();
+    for input in options.inputs {
+        let start = Instant::now();
+        let answers = (day.solve)(&input, &[1, 2]);
+        let elapsed = start.elapsed();
+
+        let mut cells = [msg!("cell-failed"), msg!("cell-failed")];
+        let elapsed = match answers {
+            Ok(solution) => {
+                for (part, answer) in (1..).zip(solution.answers) {
+                    match answer {
+                        Ok(answer) => cells[part - 1] = answer,
+                        Err(e) => failures.push(format!("{} part {}: {}", input, part, e)),
+                    }
+                }
+                Some(elapsed)
+            }
+            Err(e) => {
+                failures.push(format!("{}: {}", input, e));
+                None
+            }
+        };
+        entries.push(Entry { input, cells, elapsed });
+    }
+
+    println!("{}", msg!("compare-header", day.number));
+    print!("{}", format_comparison(&entries));
+    for failure in failures {
+        println!("{}", failure);
+    }
+    Ok(())
+}
+
+/// Runs one day like `run --day`, taking its input as an optional positional
+/// argument the way the day binaries do
+fn run_day(day: u32, args: impl Iterator<Item = String>) -> Result<(), Box<dyn Error>> {
+    let mut options = parse_options(args)?;
+    if options.inputs.len() > 1 || (options.input.is_some() && !options.inputs.is_empty()) {
+        return Err(Box::new(AppError::ArgError("give at most one input path")));
+    }
+    options.day = Some(day);
+    options.input = options.input.or(options.inputs.pop());
+    run(options)
+}
+
+/// Dispatches to the subcommand named by the first argument, or straight to a
+/// day when invoked through a link named after it
+fn dispatch() -> Result<(), Box<dyn Error>> {
+    let mut args: Vec<String> = std::env::args().collect();
+    logging::init(logging::take_verbosity(&mut args));
+    messages::init(messages::take_lang(&mut args)?.as_deref())?;
+    let mut args = args.into_iter();
+    let program = args.next().unwrap_or_default();
+    if let Some(day) = find_day_by_program(&program) {
+        return run_day(day.number, args);
+    }
+
+    let command = args.next();
+    if command.as_deref() == Some("day") {
+        let day = args.next().ok_or(AppError::ArgError("day requires a number"))?.parse()?;
+        return run_day(day, args);
+    }
+    let options = parse_options(args)?;
+    if !options.inputs.is_empty() && command.as_deref() != Some("compare") {
+        return Err(Box::new(AppError::ArgError(USAGE)));
+    }
+    match command.as_deref() {
+        Some("run") => run(options),
+        Some("fetch") => fetch(options),
+        Some("encrypt") => encrypt(options),
+        Some("puzzle") => puzzle(options),
+        Some("submit") => submit(options),
+        Some("check") => check(options),
+        Some("bench") => bench(options),
+        Some("compare") => compare(options),
+        Some("tui") => tui(options),
+        Some("--stdio-protocol") => Ok(protocol::serve(std::io::stdin().lock(), std::io::stdout().lock())?),
+        _ => Err(Box::new(AppError::ArgError(USAGE))),
+    }
+}
+
+fn main() {
+    // Print errors with Display rather than Debug, since some carry instructions
+    if let Err(e) = dispatch() {
+        eprintln!("{}", msg!("error", e));
+        std::process::exit(1);
+    }
+}
@@ -0,0 +1,116 @@
+//! The JSON-lines protocol spoken by `aoc --stdio-protocol`.
+//!
+//! Each line read is a request such as
+//! `{"id":7,"day":2,"part":1,"input":"7 6 4 2 1\n..."}` and each line written
+//! is the matching response, `{"id":7,"day":2,"part":1,"answer":2,"elapsed_ms":0.1}`
+//! or `{"id":7,"error":"..."}`. `id` is optional and echoed back unchanged, so
+//! a client can match responses to requests. The process keeps serving until
+//! its input is closed, which saves editors and other tools starting it once
+//! per request.
+
+use std::error::Error;
+use std::io::{self, BufRead, Write};
+use std::time::Duration;
+
+use aoc_common::msg;
+use aoc_common::output::answer_value;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+use crate::errors::AppError;
+use crate::registry::find_day;
+
+/// One request line
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Request {
+    #[serde(default)]
+    id: Value,
+    day: u32,
+    part: u32,
+    /// The puzzle input text itself, not a path
+    input: String,
+}
+
+/// Solves one request
+///
+/// # Returns
+/// The answer and how long parsing and the part took, or why it couldn't be solved
+fn solve(request: &Request) -> Result<(String, Duration), Box<dyn Error>> {
+    if !matches!(request.part, 1 | 2) {
+        return Err(Box::new(AppError::InvalidPart(request.part)));
+    }
+    let day = find_day(request.day).ok_or(AppError::UnknownDay(request.day))?;
+    let mut solution = (day.solve_input)(&request.input, &[request.part])?;
+    let elapsed = solution.parse + solution.parts.iter().sum::<Duration>();
+    let answer = solution.answers.pop().ok_or(AppError::ArgError("no answer was computed"))??;
+    Ok((answer, elapsed))
+}
+
+/// Answers one request line
+///
+/// # Returns
+/// The response line, without a trailing newline
+pub fn respond(line: &str) -> String {
+    let request: Request = match serde_json::from_str(line) {
+        Ok(request) => request,
+        Err(e) => return json!({ "id": Value::Null, "error": msg!("protocol-invalid-request", e) }).to_string(),
+    };
+    match solve(&request) {
+        Ok((answer, elapsed)) => json!({
+            "id": request.id,
+            "day": request.day,
+            "part": request.part,
+            "answer": answer_value(Some(&answer)),
+            "elapsed_ms": elapsed.as_secs_f64() * 1000.0,
+        }),
+        Err(e) => json!({ "id": request.id, "error": e.to_string() }),
+    }
+    .to_string()
+}
+
+/// Answers every request line from `reader` on `writer`, flushing after each
+/// response, until `reader` runs out. Blank lines are skipped.
+pub fn serve(reader: impl BufRead, mut writer: impl Write) -> io::Result<()> {
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        writeln!(writer, "{}", respond(&line))?;
+        writer.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serve() -> Result<(), Box<dyn Error>> {
+        let input = std::fs::read_to_string("../day_02/data/inputtest.txt")?;
+        let requests = format!(
+            "{}\n\n{}\n{}\nnot json\n",
+            json!({ "id": "a", "day": 2, "part": 1, "input": input }),
+            json!({ "day": 2, "part": 3, "input": input }),
+            json!({ "id": 9, "day": 16, "part": 1, "input": "" }),
+        );
+        let mut output = Vec::new();
+        serve(requests.as_bytes(), &mut output)?;
+
+        let responses: Vec<Value> = String::from_utf8(output)?
+            .lines()
+            .map(serde_json::from_str)
+            .collect::<Result<_, _>>()?;
+        assert_eq!(responses.len(), 4);
+        assert_eq!(responses[0]["id"], "a");
+        assert_eq!(responses[0]["answer"], 2);
+        assert_eq!(responses[1]["id"], Value::Null);
+        assert_eq!(responses[1]["error"], "Part must be 1 or 2, got 3");
+        assert_eq!(responses[2]["id"], 9);
+        assert_eq!(responses[2]["error"], "No solver registered for day 16");
+        assert!(responses[3]["error"].as_str().is_some_and(|e| e.starts_with("Invalid request")));
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+//! The days the runner can dispatch to.
+//!
+//! Every day implements `aoc_common::Solver`, so its input is parsed once and
+//! shared by both parts.
+
+use std::error::Error;
+use std::time::Duration;
+
+use aoc_common::cancel::CancelToken;
+use aoc_common::solver::Timings;
+use aoc_common::{solver, AppError, Solver};
+
+/// The answer to one part, or the reason it couldn't be computed
+pub type Answer = Result<String, Box<dyn Error>>;
+
+/// The answers to the requested parts, with how long each stage took
+pub struct Solution {
+    /// One answer per requested part
+    pub answers: Vec<Answer>,
+    /// Time spent parsing the input, once for all requested parts
+    pub parse: Duration,
+    /// Time spent on each requested part, in the order requested
+    pub parts: Vec<Duration>,
+}
+
+/// Solves the given parts (1 or 2) for the input file at a path
+pub type SolveFn = fn(&str, &[u32]) -> Result<Solution, Box<dyn Error>>;
+
+/// Like `SolveFn`, but skips the stages not yet started once the token is cancelled
+pub type SolveWithFn = fn(&str, &[u32], &CancelToken) -> Result<Solution, Box<dyn Error>>;
+
+/// Solves the given parts (1 or 2) for input text that has already been read
+pub type SolveInputFn = fn(&str, &[u32]) -> Result<Solution, Box<dyn Error>>;
+
+/// Explains how one part (1 or 2) reaches its answer for the input file at a path
+pub type ExplainFn = fn(&str, u32) -> Result<Vec<String>, Box<dyn Error>>;
+
+/// A registered day
+pub struct Day {
+    pub number: u32,
+    pub solve: SolveFn,
+    pub solve_with: SolveWithFn,
+    pub solve_input: SolveInputFn,
+    pub explain: ExplainFn,
+}
+
+/// Registers `S` as the solver for day `number`
+const fn register<S: Solver>(number: u32) -> Day {
+    Day {
+        number,
+        solve: with_solver::<S>,
+        solve_with: cancellable_with_solver::<S>,
+        solve_input: solve_input_with::<S>,
+        explain: explain_with::<S>,
+    }
+}
+
+/// Reads the input at `path` and solves it with `S`
+fn with_solver<S: Solver>(path: &str, parts: &[u32]) -> Result<Solution, Box<dyn Error>> {
+    cancellable_with_solver::<S>(path, parts, &CancelToken::new())
+}
+
+/// Reads the input at `path` and solves it with `S`, until `cancel` is cancelled
+fn cancellable_with_solver<S: Solver>(
+    path: &str,
+    parts: &[u32],
+    cancel: &CancelToken,
+) -> Result<Solution, Box<dyn Error>> {
+    let input = std::fs::read_to_string(path)?;
+    Ok(solution(solver::solve_timed_with::<S>(&input, parts, cancel)?))
+}
+
+/// Solves the input text itself with `S`
+fn solve_input_with<S: Solver>(input: &str, parts: &[u32]) -> Result<Solution, Box<dyn Error>> {
+    Ok(solution(solver::solve_timed::<S>(input, parts)?))
+}
+
+/// Collects a solver's answers and timings into a `Solution`
+fn solution((answers, timings): (Vec<Result<String, AppError>>, Timings)) -> Solution {
+    Solution {
+        answers: answers.into_iter().map(|answer| answer.map_err(Into::into)).collect(),
+        parse: timings.parse,
+        parts: timings.parts,
+    }
+}
+
+/// Reads the input at `path` and explains one part with `S`
+fn explain_with<S: Solver>(path: &str, part: u32) -> Result<Vec<String>, Box<dyn Error>> {
+    let input = std::fs::read_to_string(path)?;
+    Ok(solver::explain::<S>(&input, part)?)
+}
+
+/// Every registered day, in ascending order
+pub const DAYS: &[Day] = &[
+    register::<day_01::Day01>(1),
+    register::<day_02::Day02>(2),
+    register::<day_03::Day03>(3),
+    register::<day_04::Day04>(4),
+    register::<day_05::Day05>(5),
+    register::<day_06::Day06>(6),
+    register::<day_07::Day07>(7),
+    register::<day_08::Day08>(8),
+    register::<day_09::Day09>(9),
+    register::<day_10::Day10>(10),
+    register::<day_11::Day11>(11),
+    register::<day_12::Day12>(12),
+    register::<day_13::Day13>(13),
+    register::<day_14::Day14>(14),
+    register::<day_15::Day15>(15),
+    register::<day_17::Day17>(17),
+    register::<day_18::Day18>(18),
+];
+
+/// Looks up a registered day by number
+pub fn find_day(number: u32) -> Option<&'static Day> {
+    DAYS.iter().find(|day| day.number == number)
+}
+
+/// Looks up the day a multi-call binary was invoked as, from the file name
+/// in `argv[0]`: `day_06`, `day06` or `day6`, with or without an extension
+pub fn find_day_by_program(program: &str) -> Option<&'static Day> {
+    let name = std::path::Path::new(program).file_stem()?.to_str()?;
+    let number = name.strip_prefix("day")?.trim_start_matches('_').parse().ok()?;
+    find_day(number)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Unwraps each answer, turning failed parts into their error message
+    fn answers(day: u32, path: &str, parts: &[u32]) -> Result<Vec<String>, Box<dyn Error>> {
+        let day = find_day(day).ok_or("day not registered")?;
+        Ok((day.solve)(path, parts)?
+            .answers
+            .into_iter()
+            .map(|answer| answer.unwrap_or_else(|e| e.to_string()))
+            .collect())
+    }
+
+    #[test]
+    fn test_days_are_ascending_and_unique() {
+        assert!(DAYS.windows(2).all(|pair| pair[0].number < pair[1].number));
+    }
+
+    #[test]
+    fn test_dispatch_to_solver() -> Result<(), Box<dyn Error>> {
+        assert_eq!(answers(5, "../day_05/data/inputtest", &[1, 2])?, ["143", "123"]);
+        assert_eq!(answers(6, "../day_06/data/inputtest", &[2])?, ["6"]);
+        assert_eq!(answers(7, "../day_07/data/inputtest", &[1, 2])?, ["3749", "11387"]);
+        Ok(())
+    }
+
+    #[test]
+    fn test_solve_cancelled() -> Result<(), Box<dyn Error>> {
+        let day = find_day(5).ok_or("day not registered")?;
+        let cancel = CancelToken::new();
+        cancel.cancel();
+        assert!((day.solve_with)("../day_05/data/inputtest", &[1, 2], &cancel).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_explain() -> Result<(), Box<dyn Error>> {
+        let day = find_day(5).ok_or("day not registered")?;
+        let explanation = (day.explain)("../day_05/data/inputtest", 1)?;
+        assert_eq!(explanation.len(), 6);
+        assert!((find_day(7).ok_or("day not registered")?.explain)("../day_07/data/inputtest", 1)?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_unknown_day() {
+        assert!(find_day(16).is_none());
+    }
+
+    #[test]
+    fn test_find_day_by_program() {
+        let number = |program| find_day_by_program(program).map(|day| day.number);
+        assert_eq!(number("/usr/local/bin/day_06"), Some(6));
+        assert_eq!(number("day17.exe"), Some(17));
+        assert_eq!(number("day5"), Some(5));
+        assert_eq!(number("day_16"), None);
+        assert_eq!(number("aoc"), None);
+        assert_eq!(number("/bin/days"), None);
+    }
+}
@@ -0,0 +1,107 @@
+//! Timing statistics printed by `aoc bench`.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use aoc_common::msg;
+
+/// Summary statistics of a set of timing samples
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Stats {
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+    /// Population standard deviation
+    pub std_dev: Duration,
+}
+
+/// Computes the statistics of `samples`, or `None` if there are none
+pub fn stats(samples: &[Duration]) -> Option<Stats> {
+    let mut sorted = samples.to_vec();
+    sorted.sort_unstable();
+    let (&min, &max) = (sorted.first()?, sorted.last()?);
+
+    let middle = sorted.len() / 2;
+    let median = if sorted.len().is_multiple_of(2) {
+        (sorted[middle - 1] + sorted[middle]) / 2
+    } else {
+        sorted[middle]
+    };
+
+    let seconds: Vec<f64> = sorted.iter().map(Duration::as_secs_f64).collect();
+    let mean = seconds.iter().sum::<f64>() / seconds.len() as f64;
+    let variance = seconds.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / seconds.len() as f64;
+
+    Some(Stats { min, median, max, std_dev: Duration::from_secs_f64(variance.sqrt()) })
+}
+
+/// Formats one line per labelled stage as an aligned table
+pub fn format_stats_table(rows: &[(String, Stats)]) -> String {
+    let headers = [
+        msg!("table-stage"),
+        msg!("table-min"),
+        msg!("table-median"),
+        msg!("table-max"),
+        msg!("table-std-dev"),
+    ];
+    let cells: Vec<[String; 4]> = rows
+        .iter()
+        .map(|(_, stats)| [stats.min, stats.median, stats.max, stats.std_dev].map(|d| format!("{:.1?}", d)))
+        .collect();
+
+    let label_width = rows.iter().map(|(label, _)| label.chars().count()).fold(headers[0].chars().count(), usize::max);
+    let mut widths = [0; 4];
+    for (i, width) in widths.iter_mut().enumerate() {
+        // Durations contain 'µ', so measure in characters rather than bytes
+        *width = cells.iter().map(|row| row[i].chars().count()).fold(headers[i + 1].chars().count(), usize::max);
+    }
+
+    let mut table = String::new();
+    let _ = write!(table, "{:<label_width$}", headers[0]);
+    for (header, width) in headers[1..].iter().zip(widths) {
+        let _ = write!(table, "  {:>width$}", header);
+    }
+    table.push('\n');
+    for ((label, _), row) in rows.iter().zip(&cells) {
+        let _ = write!(table, "{:<label_width$}", label);
+        for (cell, width) in row.iter().zip(widths) {
+            let _ = write!(table, "  {:>width$}", cell);
+        }
+        table.push('\n');
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stats() {
+        let samples = [4, 2, 8, 6].map(Duration::from_millis);
+        let stats = stats(&samples).unwrap();
+        assert_eq!(stats.min, Duration::from_millis(2));
+        assert_eq!(stats.median, Duration::from_millis(5));
+        assert_eq!(stats.max, Duration::from_millis(8));
+        // Mean 5ms, squared deviations 9+1+1+9 = 20, variance 5
+        assert_eq!(stats.std_dev.as_micros(), 2236);
+
+        assert_eq!(super::stats(&[Duration::from_millis(3)]).unwrap().median, Duration::from_millis(3));
+        assert!(super::stats(&[]).is_none());
+    }
+
+    #[test]
+    fn test_format_stats_table() {
+        let stats = Stats {
+            min: Duration::from_micros(10),
+            median: Duration::from_micros(12),
+            max: Duration::from_micros(20),
+            std_dev: Duration::from_nanos(3500),
+        };
+        assert_eq!(
+            format_stats_table(&[("parse".to_string(), stats)]),
+            "Stage     Min  Median     Max  Std dev\n\
+             parse  10.0µs  12.0µs  20.0µs    3.5µs\n"
+        );
+    }
+}
@@ -0,0 +1,79 @@
+//! Timing summary printed by `aoc run --all`.
+
+use std::fmt::Write;
+use std::time::Duration;
+
+use aoc_common::msg;
+
+/// One day's results in the summary
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub day: u32,
+    /// What to show for each part: the answer, or a short reason it's missing
+    pub cells: [String; 2],
+    /// Wall-clock time for parsing and both parts; `None` if the day wasn't run
+    pub elapsed: Option<Duration>,
+}
+
+/// Formats the rows as an aligned table with a total time line
+pub fn format_table(rows: &[Row]) -> String {
+    let headers = [msg!("table-day"), msg!("table-part1"), msg!("table-part2"), msg!("table-time")];
+    let times: Vec<String> = rows
+        .iter()
+        .map(|row| row.elapsed.map_or("-".to_string(), |elapsed| format!("{:.1?}", elapsed)))
+        .collect();
+    let total: Duration = rows.iter().filter_map(|row| row.elapsed).sum();
+    let total = format!("{:.1?}", total);
+
+    // Durations contain 'µ' and translations may have accents, so measure in characters
+    let width = |header: &str, cells: &mut dyn Iterator<Item = usize>| cells.fold(header.chars().count(), usize::max);
+    let day_width = width(&headers[0], &mut rows.iter().map(|row| row.day.to_string().len()));
+    let part1_width = width(&headers[1], &mut rows.iter().map(|row| row.cells[0].chars().count()));
+    let part2_width = width(&headers[2], &mut rows.iter().map(|row| row.cells[1].chars().count()));
+    let time_width = width(&headers[3], &mut times.iter().chain([&total]).map(|time| time.chars().count()));
+
+    let mut table = String::new();
+    let _ = writeln!(
+        table,
+        "{:>day_width$}  {:<part1_width$}  {:<part2_width$}  {:>time_width$}",
+        headers[0], headers[1], headers[2], headers[3]
+    );
+    for (row, time) in rows.iter().zip(&times) {
+        let _ = writeln!(
+            table,
+            "{:>day_width$}  {:<part1_width$}  {:<part2_width$}  {:>time_width$}",
+            row.day, row.cells[0], row.cells[1], time
+        );
+    }
+    let label_width = day_width + part1_width + part2_width + 4;
+    let _ = writeln!(table, "{:<label_width$}  {:>time_width$}", msg!("table-total"), total);
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_table() {
+        let rows = [
+            Row {
+                day: 3,
+                cells: ["161".to_string(), "48".to_string()],
+                elapsed: Some(Duration::from_micros(1500)),
+            },
+            Row {
+                day: 12,
+                cells: ["no input".to_string(), "no input".to_string()],
+                elapsed: None,
+            },
+        ];
+        let expected = "\
+Day  Part 1    Part 2     Time
+  3  161       48        1.5ms
+ 12  no input  no input      -
+Total                    1.5ms
+";
+        assert_eq!(format_table(&rows), expected);
+    }
+}
@@ -0,0 +1,320 @@
+//! Terminal dashboard opened by `aoc tui`.
+//!
+//! Lists every registered day with whether it has an input and which answers
+//! are recorded in the answers file, and runs the selected day in the
+//! background while its progress and timings stream into the log pane.
+//!
+//! Keys: ↑/↓ (or `k`/`j`) select a day, `1` or `2` runs that part, Enter runs
+//! both, and `q` or Esc quits.
+
+use std::collections::BTreeMap;
+use std::io;
+use std::path::Path;
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::Line;
+use ratatui::widgets::{Block, Paragraph, Row, Table, TableState};
+use ratatui::{DefaultTerminal, Frame};
+
+use aoc_common::msg;
+
+use crate::answers::Expected;
+use crate::registry::Day;
+
+/// How often the screen is redrawn while waiting for keys or results
+const TICK: Duration = Duration::from_millis(100);
+/// Lines of the log pane, including its border
+const LOG_HEIGHT: u16 = 10;
+
+/// What the background run reports back for one part
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartResult {
+    pub day: u32,
+    pub part: u32,
+    /// The answer, or why there is none
+    pub outcome: Result<String, String>,
+    pub elapsed: Duration,
+}
+
+/// A run in progress
+struct Running {
+    day: u32,
+    started: Instant,
+    receiver: Receiver<PartResult>,
+}
+
+/// The dashboard's state, kept apart from drawing so it can be tested
+pub struct App {
+    days: &'static [Day],
+    expected: BTreeMap<u32, Expected>,
+    /// Default input path per day
+    inputs: Vec<String>,
+    selected: usize,
+    /// The latest answer shown for each day's parts
+    cells: BTreeMap<u32, [String; 2]>,
+    log: Vec<String>,
+    running: Option<Running>,
+}
+
+impl App {
+    /// Creates the dashboard for `days`, whose default inputs are `inputs`
+    pub fn new(days: &'static [Day], expected: BTreeMap<u32, Expected>, inputs: Vec<String>) -> Self {
+        Self { days, expected, inputs, selected: 0, cells: BTreeMap::new(), log: Vec::new(), running: None }
+    }
+
+    /// Handles a key press
+    ///
+    /// # Returns
+    /// `true` if the key quits the dashboard
+    pub fn on_key(&mut self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return true,
+            KeyCode::Up | KeyCode::Char('k') => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.selected = (self.selected + 1).min(self.days.len() - 1),
+            KeyCode::Char('1') => self.start(&[1]),
+            KeyCode::Char('2') => self.start(&[2]),
+            KeyCode::Enter => self.start(&[1, 2]),
+            _ => {}
+        }
+        false
+    }
+
+    /// Runs `parts` of the selected day on a background thread, one part at
+    /// a time so each result shows up as soon as it's ready
+    fn start(&mut self, parts: &[u32]) {
+        if self.running.is_some() {
+            self.log.push(msg!("tui-busy"));
+            return;
+        }
+        let day = &self.days[self.selected];
+        let input = self.inputs[self.selected].clone();
+        if !Path::new(&input).exists() {
+            self.log.push(msg!("tui-no-input", day.number, input));
+            return;
+        }
+
+        let (sender, receiver) = mpsc::channel();
+        let (number, solve, parts) = (day.number, day.solve, parts.to_vec());
+        thread::spawn(move || {
+            for part in parts {
+                let start = Instant::now();
+                let outcome = match solve(&input, &[part]) {
+                    Ok(mut solution) => match solution.answers.pop() {
+                        Some(Ok(answer)) => Ok(answer),
+                        Some(Err(e)) => Err(e.to_string()),
+                        None => Err("no answer was computed".to_string()),
+                    },
+                    Err(e) => Err(e.to_string()),
+                };
+                let result = PartResult { day: number, part, outcome, elapsed: start.elapsed() };
+                if sender.send(result).is_err() {
+                    return;
+                }
+            }
+        });
+        self.log.push(msg!("tui-running", day.number));
+        self.running = Some(Running { day: day.number, started: Instant::now(), receiver });
+    }
+
+    /// Takes in the results the background run has sent so far
+    fn poll(&mut self) {
+        let Some(running) = &self.running else { return };
+        let mut results = Vec::new();
+        let finished = loop {
+            match running.receiver.try_recv() {
+                Ok(result) => results.push(result),
+                Err(TryRecvError::Empty) => break false,
+                Err(TryRecvError::Disconnected) => break true,
+            }
+        };
+        for result in results {
+            self.record(result);
+        }
+        if !finished {
+            return;
+        }
+        if let Some(running) = self.running.take() {
+            self.log.push(msg!("tui-done", running.day, format!("{:.1?}", running.started.elapsed())));
+        }
+    }
+
+    /// Shows one part's result in the table and the log
+    pub fn record(&mut self, result: PartResult) {
+        let cell = match &result.outcome {
+            Ok(answer) => match self.expected.get(&result.day).and_then(|expected| expected.part(result.part)) {
+                Some(wanted) if wanted != answer => msg!("tui-expected", answer, wanted),
+                _ => answer.clone(),
+            },
+            Err(_) => msg!("cell-failed"),
+        };
+        let message = match &result.outcome {
+            Ok(_) => cell.clone(),
+            Err(e) => e.clone(),
+        };
+        self.log.push(msg!("tui-part-result", result.day, result.part, message, format!("{:.1?}", result.elapsed)));
+        self.cells.entry(result.day).or_insert_with(|| ["-".to_string(), "-".to_string()])[result.part as usize - 1] =
+            cell;
+    }
+
+    /// Returns the "answers known" column for a day, such as `1, 2`
+    fn known(&self, day: u32) -> String {
+        let parts: Vec<String> = [1, 2]
+            .into_iter()
+            .filter(|&part| self.expected.get(&day).is_some_and(|expected| expected.part(part).is_some()))
+            .map(|part| part.to_string())
+            .collect();
+        if parts.is_empty() {
+            "-".to_string()
+        } else {
+            parts.join(", ")
+        }
+    }
+
+    /// Draws the day table, the log pane and the key help
+    fn draw(&self, frame: &mut Frame) {
+        let [table_area, log_area, help_area] =
+            Layout::vertical([Constraint::Min(3), Constraint::Length(LOG_HEIGHT), Constraint::Length(1)])
+                .areas(frame.area());
+
+        let rows = self.days.iter().zip(&self.inputs).map(|(day, input)| {
+            let cells = self.cells.get(&day.number);
+            let running = self.running.as_ref().is_some_and(|running| running.day == day.number);
+            Row::new([
+                day.number.to_string(),
+                if Path::new(input).exists() { msg!("tui-yes") } else { msg!("tui-no") },
+                self.known(day.number),
+                cells.map_or("-".to_string(), |cells| cells[0].clone()),
+                cells.map_or("-".to_string(), |cells| cells[1].clone()),
+                if running { msg!("tui-running-cell") } else { String::new() },
+            ])
+        });
+        let widths = [
+            Constraint::Length(4),
+            Constraint::Length(6),
+            Constraint::Length(7),
+            Constraint::Fill(1),
+            Constraint::Fill(1),
+            Constraint::Length(8),
+        ];
+        let table = Table::new(rows, widths)
+            .header(
+                Row::new([
+                    msg!("table-day"),
+                    msg!("table-input"),
+                    msg!("tui-known"),
+                    msg!("table-part1"),
+                    msg!("table-part2"),
+                    String::new(),
+                ])
+                    .style(Style::new().add_modifier(Modifier::BOLD)),
+            )
+            .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED))
+            .block(Block::bordered().title(" Days "));
+        frame.render_stateful_widget(table, table_area, &mut TableState::new().with_selected(self.selected));
+
+        let mut lines: Vec<Line> = self.log.iter().map(|line| Line::raw(line.as_str())).collect();
+        if let Some(running) = &self.running {
+            lines.push(Line::raw(msg!("tui-so-far", running.day, format!("{:.1?}", running.started.elapsed()))));
+        }
+        let visible = usize::from(LOG_HEIGHT - 2);
+        let lines = lines.split_off(lines.len().saturating_sub(visible));
+        frame.render_widget(Paragraph::new(lines).block(Block::bordered().title(" Log ")), log_area);
+
+        frame.render_widget(Line::raw(msg!("tui-help")), help_area);
+    }
+}
+
+/// Opens the dashboard and runs it until the user quits. The terminal is
+/// restored even if drawing fails.
+pub fn run(app: App) -> io::Result<()> {
+    let mut terminal = ratatui::try_init()?;
+    let result = event_loop(&mut terminal, app);
+    ratatui::restore();
+    result
+}
+
+/// Redraws, collects results and handles keys until the user quits
+fn event_loop(terminal: &mut DefaultTerminal, mut app: App) -> io::Result<()> {
+    loop {
+        app.poll();
+        terminal.draw(|frame| app.draw(frame))?;
+        if !event::poll(TICK)? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && app.on_key(key.code) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::backend::TestBackend;
+    use ratatui::Terminal;
+
+    use super::*;
+    use crate::registry::DAYS;
+
+    fn app() -> App {
+        let expected = BTreeMap::from([(5, Expected { part1: Some("143".to_string()), part2: None })]);
+        let inputs = DAYS.iter().map(|day| format!("../day_{:02}/data/missing", day.number)).collect();
+        App::new(DAYS, expected, inputs)
+    }
+
+    #[test]
+    fn test_selection() {
+        let mut app = app();
+        assert!(!app.on_key(KeyCode::Up));
+        assert_eq!(app.selected, 0);
+        for _ in 0..DAYS.len() + 3 {
+            app.on_key(KeyCode::Down);
+        }
+        assert_eq!(app.selected, DAYS.len() - 1);
+        assert!(app.on_key(KeyCode::Char('q')));
+    }
+
+    #[test]
+    fn test_record() {
+        let mut app = app();
+        let result = |part, outcome| PartResult { day: 5, part, outcome, elapsed: Duration::from_millis(2) };
+        app.record(result(1, Ok("140".to_string())));
+        app.record(result(2, Err("Part 2 is not solved yet".to_string())));
+        assert_eq!(app.cells[&5], ["140 (expected 143)".to_string(), "failed".to_string()]);
+        assert_eq!(app.log, ["Day 5 part 1: 140 (expected 143) in 2.0ms", "Day 5 part 2: Part 2 is not solved yet in 2.0ms"]);
+        assert_eq!(app.known(5), "1");
+        assert_eq!(app.known(6), "-");
+    }
+
+    #[test]
+    fn test_run_in_background() {
+        let mut app = app();
+        app.selected = DAYS.iter().position(|day| day.number == 5).unwrap_or_default();
+        app.inputs[app.selected] = "../day_05/data/inputtest".to_string();
+        app.on_key(KeyCode::Enter);
+        while app.running.is_some() {
+            thread::sleep(Duration::from_millis(1));
+            app.poll();
+        }
+        assert_eq!(app.cells[&5], ["143".to_string(), "123".to_string()]);
+        assert_eq!(app.log.len(), 4);
+    }
+
+    #[test]
+    fn test_missing_input() -> io::Result<()> {
+        let mut app = app();
+        app.on_key(KeyCode::Enter);
+        assert!(app.running.is_none());
+        assert_eq!(app.log, ["Day 1: no input at ../day_01/data/missing"]);
+
+        let mut terminal = Terminal::new(TestBackend::new(60, 30))?;
+        terminal.draw(|frame| app.draw(frame))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+//! Workspace maintenance tasks, run with `cargo xtask <task>`.
+//!
+//! # Usage
+//!
+//! ```bash
+//! cargo xtask check-inputs
+//! ```
+//!
+//! `check-inputs` compares every day's `day_NN/data` directory with
+//! `data-manifest.toml` at the workspace root. Example files must be present
+//! and match their recorded SHA-256 hash. Personal puzzle inputs may be
+//! missing, since not everyone commits theirs, but one that is present must
+//! have the recorded size, which catches truncated or re-downloaded inputs.
+//! Data files the manifest doesn't know about, and days with a data directory
+//! but no manifest entry, are reported too. Any problem makes the command exit
+//! with a non-zero status.
+
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+/// The manifest, relative to the workspace root
+const MANIFEST: &str = "data-manifest.toml";
+
+const USAGE: &str = "Usage: cargo xtask check-inputs";
+
+/// One day's expected data files, by file name
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct DayFiles {
+    /// Example files and their SHA-256 hashes
+    #[serde(default)]
+    examples: BTreeMap<String, String>,
+    /// Personal inputs and their sizes in bytes
+    #[serde(default)]
+    inputs: BTreeMap<String, u64>,
+}
+
+/// Returns the workspace root
+fn workspace_root() -> PathBuf {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    manifest_dir.parent().unwrap_or(manifest_dir).to_path_buf()
+}
+
+/// Returns the lowercase hex SHA-256 hash of `bytes`
+fn sha256_hex(bytes: &[u8]) -> String {
+    Sha256::digest(bytes).iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{:02x}", byte);
+        hex
+    })
+}
+
+/// Checks one day's data directory against its manifest entry
+///
+/// # Arguments
+/// * `day` - The day's crate name, such as `day_05`, used in messages
+/// * `dir` - The day's data directory
+/// * `files` - The files the manifest expects there
+///
+/// # Returns
+/// One message per problem found, or an error if the directory can't be read
+fn check_day(day: &str, dir: &Path, files: &DayFiles) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut problems = Vec::new();
+    for (name, expected) in &files.examples {
+        match std::fs::read(dir.join(name)) {
+            Ok(bytes) if sha256_hex(&bytes) == *expected => {}
+            Ok(_) => problems.push(format!("{}/data/{}: contents differ from the recorded hash", day, name)),
+            Err(_) => problems.push(format!("{}/data/{}: example is missing", day, name)),
+        }
+    }
+    for (name, &expected) in &files.inputs {
+        if let Ok(metadata) = std::fs::metadata(dir.join(name)) {
+            if metadata.len() != expected {
+                problems.push(format!("{}/data/{}: {} bytes, expected {}", day, name, metadata.len(), expected));
+            }
+        }
+    }
+
+    if dir.is_dir() {
+        for entry in std::fs::read_dir(dir)? {
+            let name = entry?.file_name().to_string_lossy().into_owned();
+            if !files.examples.contains_key(&name) && !files.inputs.contains_key(&name) {
+                problems.push(format!("{}/data/{}: not in {}", day, name, MANIFEST));
+            }
+        }
+    }
+    problems.sort();
+    Ok(problems)
+}
+
+/// Checks every day's data directory under `root` against the manifest text
+///
+/// # Returns
+/// One message per problem found, or an error if the manifest is malformed or
+/// a directory can't be read
+fn check_inputs(root: &Path, manifest: &str) -> Result<Vec<String>, Box<dyn Error>> {
+    let mut days: BTreeMap<String, DayFiles> = toml::from_str(manifest)?;
+    for entry in std::fs::read_dir(root)? {
+        let path = entry?.path();
+        let name = path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+        if name.starts_with("day_") && path.join("data").is_dir() && !days.contains_key(&name) {
+            days.insert(name, DayFiles::default());
+        }
+    }
+
+    let mut problems = Vec::new();
+    for (day, files) in &days {
+        if files.examples.is_empty() && files.inputs.is_empty() {
+            problems.push(format!("{}: has data files but no entry in {}", day, MANIFEST));
+            continue;
+        }
+        problems.extend(check_day(day, &root.join(day).join("data"), files)?);
+    }
+    Ok(problems)
+}
+
+/// Runs `check-inputs` on the workspace and prints the problems found
+fn check_inputs_task() -> Result<(), Box<dyn Error>> {
+    let root = workspace_root();
+    let manifest = std::fs::read_to_string(root.join(MANIFEST))?;
+    let problems = check_inputs(&root, &manifest)?;
+    if problems.is_empty() {
+        println!("All data files match {}", MANIFEST);
+        return Ok(());
+    }
+    for problem in &problems {
+        println!("{}", problem);
+    }
+    Err(format!("{} problem(s) found", problems.len()).into())
+}
+
+fn main() {
+    let result = match std::env::args().nth(1).as_deref() {
+        Some("check-inputs") => check_inputs_task(),
+        _ => Err(USAGE.into()),
+    };
+    if let Err(e) = result {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sha256_hex() {
+        assert_eq!(sha256_hex(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+    }
+
+    #[test]
+    fn test_workspace_manifest() -> Result<(), Box<dyn Error>> {
+        let root = workspace_root();
+        let manifest = std::fs::read_to_string(root.join(MANIFEST))?;
+        assert_eq!(check_inputs(&root, &manifest)?, Vec::<String>::new());
+        Ok(())
+    }
+
+    #[test]
+    fn test_check_day() -> Result<(), Box<dyn Error>> {
+        let dir = workspace_root().join("day_05/data");
+        let files = DayFiles {
+            examples: BTreeMap::from([
+                ("inputtest".to_string(), "0".repeat(64)),
+                ("inputtest_missing".to_string(), "0".repeat(64)),
+            ]),
+            inputs: BTreeMap::from([("input".to_string(), 1), ("input.txt".to_string(), 1)]),
+        };
+        let problems = check_day("day_05", &dir, &files)?;
+        assert!(problems.contains(&"day_05/data/inputtest: contents differ from the recorded hash".to_string()));
+        assert!(problems.contains(&"day_05/data/inputtest_missing: example is missing".to_string()));
+        // A missing personal input is fine
+        assert!(!problems.iter().any(|problem| problem.contains("input.txt")));
+        if dir.join("input").exists() {
+            assert!(problems.iter().any(|problem| problem.starts_with("day_05/data/input: ")));
+        }
+        Ok(())
+    }
+}